@@ -0,0 +1,70 @@
+contract;
+
+use interfaces::{data_structures::PoolId, hook::IBaseHook};
+
+/// Arguments the AMM called the hook with
+pub struct HookCall {
+    pub pool_id: PoolId,
+    pub sender: Identity,
+    pub to: Identity,
+    pub asset_0_in: u64,
+    pub asset_1_in: u64,
+    pub asset_0_out: u64,
+    pub asset_1_out: u64,
+    pub lp_token_in: u64,
+    pub lp_token_out: u64,
+}
+
+/// Hook recording the calls it receives, so that tests can check the AMM made them
+abi MockHook {
+    #[storage(read)]
+    fn calls() -> u64;
+
+    #[storage(read)]
+    fn last_call() -> Option<HookCall>;
+}
+
+storage {
+    calls: u64 = 0,
+    last_call: Option<HookCall> = None,
+}
+
+impl IBaseHook for Contract {
+    #[storage(read, write)]
+    fn hook(
+        pool_id: PoolId,
+        sender: Identity,
+        to: Identity,
+        asset_0_in: u64,
+        asset_1_in: u64,
+        asset_0_out: u64,
+        asset_1_out: u64,
+        lp_token_in: u64,
+        lp_token_out: u64,
+    ) {
+        storage.calls.write(storage.calls.read() + 1);
+        storage.last_call.write(Some(HookCall {
+            pool_id,
+            sender,
+            to,
+            asset_0_in,
+            asset_1_in,
+            asset_0_out,
+            asset_1_out,
+            lp_token_in,
+            lp_token_out,
+        }));
+    }
+}
+
+impl MockHook for Contract {
+    #[storage(read)]
+    fn calls() -> u64 {
+        storage.calls.read()
+    }
+
+    #[storage(read)]
+    fn last_call() -> Option<HookCall> {
+        storage.last_call.read()
+    }
+}
@@ -0,0 +1,269 @@
+contract;
+
+use interfaces::{
+    data_structures::{PoolId, PoolMetadata},
+    hook::IBaseHook,
+    mira_amm::MiraAMM,
+    twap_oracle::{Observation, TwapOracle},
+};
+use math::pool_math::try_spot_price;
+use std::{
+    block::timestamp,
+    math::*,
+    primitive_conversions::{u64::*, u8::*},
+};
+use utils::blockchain_utils::is_stable;
+
+configurable {
+    AMM_CONTRACT_ID: ContractId = ContractId::zero(),
+    /// Observations kept for every pool until a keeper increases its cardinality, at least 1.
+    /// Slots are only written once used, so a large default costs nothing to pools updated
+    /// less often.
+    INITIAL_CARDINALITY: u64 = 3_600,
+}
+
+/// Ring buffer of the observations of a pool
+struct Ring {
+    /// Slot of the latest observation
+    index: u64,
+    /// Observations written, up to `cardinality`
+    len: u64,
+    /// Slots the ring wraps around
+    cardinality: u64,
+    /// Slots the ring grows to once it reaches its last slot
+    cardinality_next: u64,
+}
+
+impl Ring {
+    fn new() -> Self {
+        Self {
+            index: 0,
+            len: 0,
+            cardinality: 0,
+            cardinality_next: if INITIAL_CARDINALITY > 0 {
+                INITIAL_CARDINALITY
+            } else {
+                1
+            },
+        }
+    }
+
+    /// Slot of the `position`-th oldest observation
+    fn slot(self, position: u64) -> u64 {
+        // the ring only grows from its last slot, so the slots beyond are yet to be written
+        let oldest = if self.len < self.cardinality {
+            0
+        } else {
+            (self.index + 1) % self.cardinality
+        };
+        (oldest + position) % self.cardinality
+    }
+}
+
+storage {
+    /// Observation rings of the pools
+    rings: StorageMap<PoolId, Ring> = StorageMap {},
+    /// Observations by pool and slot, at most one per block. The oldest ones are overwritten
+    /// once the ring of the pool is full.
+    observations: StorageMap<(PoolId, u64), Observation> = StorageMap {},
+}
+
+#[storage(read)]
+fn read_ring(pool_id: PoolId) -> Ring {
+    storage.rings.get(pool_id).try_read().unwrap_or(Ring::new())
+}
+
+/// `position`-th oldest observation of `pool_id`, `ring` being its ring
+#[storage(read)]
+fn read_observation(pool_id: PoolId, ring: Ring, position: u64) -> Observation {
+    storage.observations.get((pool_id, ring.slot(position))).read()
+}
+
+fn pow_decimals(decimals: u8) -> u256 {
+    10.as_u256().pow(decimals.into())
+}
+
+/// Accumulators of `observation` extrapolated to `time`, its prices having held since
+fn cumulatives_at(observation: Observation, time: u64) -> (u256, u256) {
+    let elapsed = (time - observation.timestamp).as_u256();
+    (
+        observation.price_0_cumulative + observation.price_0 * elapsed,
+        observation.price_1_cumulative + observation.price_1 * elapsed,
+    )
+}
+
+/// Accumulators of `observation` extrapolated to `time`, or `None` if they overflow
+fn try_cumulatives_at(observation: Observation, time: u64) -> Option<(u256, u256)> {
+    let elapsed = (time - observation.timestamp).as_u256();
+    let max = u256::max();
+    if elapsed > 0
+        && (observation.price_0 > (max - observation.price_0_cumulative) / elapsed
+            || observation.price_1 > (max - observation.price_1_cumulative) / elapsed)
+    {
+        return None;
+    }
+    Some(cumulatives_at(observation, time))
+}
+
+/// Records the prices of `pool_id` from its `metadata`. Skips the observation rather than
+/// reverting when a price is zero or overflows, as the AMM calls it from its hook.
+#[storage(read, write)]
+fn record(pool_id: PoolId, metadata: PoolMetadata) {
+    let (reserve_0, reserve_1) = (metadata.reserve_0.as_u256(), metadata.reserve_1.as_u256());
+    let (pow_decimals_0, pow_decimals_1) = (
+        pow_decimals(metadata.decimals_0),
+        pow_decimals(metadata.decimals_1),
+    );
+    let prices = (
+        try_spot_price(
+            is_stable(pool_id),
+            reserve_0,
+            reserve_1,
+            pow_decimals_0,
+            pow_decimals_1,
+        ),
+        try_spot_price(
+            is_stable(pool_id),
+            reserve_1,
+            reserve_0,
+            pow_decimals_1,
+            pow_decimals_0,
+        ),
+    );
+    let (price_0, price_1) = match prices {
+        (Some(price_0), Some(price_1)) => (price_0, price_1),
+        _ => return,
+    };
+
+    let now = timestamp();
+    let mut ring = read_ring(pool_id);
+    if ring.len == 0 {
+        ring.cardinality = ring.cardinality_next;
+        ring.len = 1;
+        storage.observations.insert(
+            (pool_id, 0),
+            Observation {
+                timestamp: now,
+                price_0_cumulative: 0,
+                price_1_cumulative: 0,
+                price_0,
+                price_1,
+            },
+        );
+        storage.rings.insert(pool_id, ring);
+        return;
+    }
+
+    let last = storage.observations.get((pool_id, ring.index)).read();
+    // the prices since the previous update are the ones it recorded, so that a trade
+    // only weighs on the average from its block on
+    let (price_0_cumulative, price_1_cumulative) = match try_cumulatives_at(last, now) {
+        Some(cumulatives) => cumulatives,
+        None => return,
+    };
+    let observation = Observation {
+        timestamp: now,
+        price_0_cumulative,
+        price_1_cumulative,
+        price_0,
+        price_1,
+    };
+    if last.timestamp != now {
+        if ring.index == ring.cardinality - 1 && ring.cardinality_next > ring.cardinality {
+            ring.cardinality = ring.cardinality_next;
+        }
+        ring.index = (ring.index + 1) % ring.cardinality;
+        if ring.len < ring.cardinality {
+            ring.len += 1;
+        }
+        storage.rings.insert(pool_id, ring);
+    }
+    storage.observations.insert((pool_id, ring.index), observation);
+}
+
+impl IBaseHook for Contract {
+    /// Updates the oracle, the AMM having settled the pool reserves
+    #[storage(read, write)]
+    fn hook(
+        pool_id: PoolId,
+        sender: Identity,
+        to: Identity,
+        asset_0_in: u64,
+        asset_1_in: u64,
+        asset_0_out: u64,
+        asset_1_out: u64,
+        lp_token_in: u64,
+        lp_token_out: u64,
+    ) {
+        // a revert here would revert the AMM call
+        if let Some(metadata) = abi(MiraAMM, AMM_CONTRACT_ID.into()).pool_metadata(pool_id) {
+            record(pool_id, metadata);
+        }
+    }
+}
+
+impl TwapOracle for Contract {
+    #[storage(read, write)]
+    fn update(pool_id: PoolId) {
+        let metadata = abi(MiraAMM, AMM_CONTRACT_ID.into()).pool_metadata(pool_id);
+        require(metadata.is_some(), "Pool not found");
+        record(pool_id, metadata.unwrap());
+    }
+
+    #[storage(read)]
+    fn consult(pool_id: PoolId, window: u64) -> Option<(u256, u256)> {
+        require(window > 0, "Zero window");
+        let ring = read_ring(pool_id);
+        let now = timestamp();
+        if ring.len == 0 || now - read_observation(pool_id, ring, 0).timestamp < window {
+            return None;
+        }
+        let start = now - window;
+
+        // latest observation at or before the start of the window
+        let mut low = 0;
+        let mut high = ring.len - 1;
+        while low < high {
+            let middle = (low + high + 1) / 2;
+            if read_observation(pool_id, ring, middle).timestamp <= start {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+
+        let (start_0, start_1) = cumulatives_at(read_observation(pool_id, ring, low), start);
+        let (end_0, end_1) = cumulatives_at(read_observation(pool_id, ring, ring.len - 1), now);
+        let window = window.as_u256();
+        Some(((end_0 - start_0) / window, (end_1 - start_1) / window))
+    }
+
+    #[storage(read, write)]
+    fn increase_cardinality(pool_id: PoolId, cardinality: u64) {
+        let mut ring = read_ring(pool_id);
+        if cardinality > ring.cardinality_next {
+            ring.cardinality_next = cardinality;
+            storage.rings.insert(pool_id, ring);
+        }
+    }
+
+    #[storage(read)]
+    fn cardinality(pool_id: PoolId) -> (u64, u64) {
+        let ring = read_ring(pool_id);
+        (ring.cardinality, ring.cardinality_next)
+    }
+
+    #[storage(read)]
+    fn observation_count(pool_id: PoolId) -> u64 {
+        read_ring(pool_id).len
+    }
+
+    #[storage(read)]
+    fn observation(pool_id: PoolId, index: u64) -> Option<Observation> {
+        let ring = read_ring(pool_id);
+        if index >= ring.len {
+            return None;
+        }
+        Some(read_observation(pool_id, ring, index))
+    }
+}
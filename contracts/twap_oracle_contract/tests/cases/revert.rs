@@ -0,0 +1,22 @@
+use crate::utils::setup;
+use fuels::types::AssetId;
+use test_harness::interface::oracle::{consult, update};
+
+#[tokio::test]
+#[should_panic(expected = "Zero window")]
+async fn panics_on_zero_window() {
+    let (env, oracle) = setup(false, true).await;
+    let pool_id = env.pools[0];
+
+    update(&oracle, &env.amm.instance, pool_id).await;
+    consult(&oracle, pool_id, 0).await;
+}
+
+#[tokio::test]
+#[should_panic(expected = "Pool not found")]
+async fn panics_on_update_of_missing_pool() {
+    let (env, oracle) = setup(false, false).await;
+    let pool_id = (env.tokens[0].id, AssetId::new([1; 32]), false);
+
+    update(&oracle, &env.amm.instance, pool_id).await;
+}
@@ -0,0 +1,148 @@
+use crate::utils::{setup, setup_with_cardinality, spot_prices, swap};
+use fuels::types::U256;
+use std::time::Duration;
+use test_harness::interface::oracle::{
+    cardinality, consult, increase_cardinality, observation, observation_count, update,
+};
+use test_harness::setup::common::advance_time;
+
+const HOUR: u64 = 3_600;
+const TRADE: u64 = 1_000_000_000_000;
+
+async fn averages_prices_over_time(is_stable: bool) {
+    let (env, oracle) = setup(is_stable, true).await;
+    let (pool_id, token_0_id, token_1_id) = (env.pools[0], env.tokens[0].id, env.tokens[1].id);
+
+    // a keeper starts the history, the hook extends it
+    update(&oracle, &env.amm.instance, pool_id).await;
+    let initial_prices = spot_prices(&env, pool_id).await;
+    assert_eq!(consult(&oracle, pool_id, HOUR).await.value, None);
+
+    advance_time(&env.provider, Duration::from_secs(HOUR)).await;
+    swap(&env, &oracle, token_0_id, TRADE).await;
+    let traded_prices = spot_prices(&env, pool_id).await;
+    assert!(traded_prices.0 < initial_prices.0);
+    assert!(traded_prices.1 > initial_prices.1);
+
+    assert_eq!(observation_count(&oracle, pool_id).await.value, 2);
+    let first = observation(&oracle, pool_id, 0).await.value.unwrap();
+    let second = observation(&oracle, pool_id, 1).await.value.unwrap();
+    assert_eq!((first.price_0, first.price_1), initial_prices);
+    assert_eq!((second.price_0, second.price_1), traded_prices);
+    let elapsed = second.timestamp - first.timestamp;
+    assert!(elapsed >= HOUR);
+    assert_eq!(
+        second.price_0_cumulative,
+        initial_prices.0 * U256::from(elapsed)
+    );
+    assert_eq!(
+        second.price_1_cumulative,
+        initial_prices.1 * U256::from(elapsed)
+    );
+
+    advance_time(&env.provider, Duration::from_secs(HOUR)).await;
+
+    // within the last hour, only the traded price held
+    assert_eq!(
+        consult(&oracle, pool_id, HOUR / 2).await.value,
+        Some(traded_prices)
+    );
+    // across the trade, the average lies between both prices
+    let (average_0, average_1) = consult(&oracle, pool_id, 3 * HOUR / 2).await.value.unwrap();
+    assert!(traded_prices.0 < average_0 && average_0 < initial_prices.0);
+    assert!(initial_prices.1 < average_1 && average_1 < traded_prices.1);
+
+    // a trade moving the spot price back weighs on the average only from its block on
+    swap(&env, &oracle, token_1_id, 4 * TRADE).await;
+    let manipulated_prices = spot_prices(&env, pool_id).await;
+    assert!(manipulated_prices.0 > initial_prices.0);
+    let (average_0, _) = consult(&oracle, pool_id, HOUR / 2).await.value.unwrap();
+    assert!(average_0 - traded_prices.0 < (manipulated_prices.0 - traded_prices.0) / 100);
+    assert_eq!(observation_count(&oracle, pool_id).await.value, 3);
+}
+
+#[tokio::test]
+async fn averages_volatile_pool_prices() {
+    averages_prices_over_time(false).await;
+}
+
+#[tokio::test]
+async fn averages_stable_pool_prices() {
+    averages_prices_over_time(true).await;
+}
+
+#[tokio::test]
+async fn keeper_updates_without_hook() {
+    let (env, oracle) = setup(false, false).await;
+    let (pool_id, token_0_id) = (env.pools[0], env.tokens[0].id);
+
+    update(&oracle, &env.amm.instance, pool_id).await;
+    let initial_prices = spot_prices(&env, pool_id).await;
+    advance_time(&env.provider, Duration::from_secs(HOUR)).await;
+
+    // the AMM doesn't call the oracle, which keeps the price of its last update
+    swap(&env, &oracle, token_0_id, TRADE).await;
+    assert_eq!(observation_count(&oracle, pool_id).await.value, 1);
+    assert_eq!(
+        consult(&oracle, pool_id, HOUR).await.value,
+        Some(initial_prices)
+    );
+
+    update(&oracle, &env.amm.instance, pool_id).await;
+    let last = observation(&oracle, pool_id, 1).await.value.unwrap();
+    assert_eq!(
+        (last.price_0, last.price_1),
+        spot_prices(&env, pool_id).await
+    );
+}
+
+#[tokio::test]
+async fn overwrites_the_oldest_observation_once_full() {
+    let (env, oracle) = setup_with_cardinality(false, false, Some(2)).await;
+    let pool_id = env.pools[0];
+
+    update(&oracle, &env.amm.instance, pool_id).await;
+    assert_eq!(cardinality(&oracle, pool_id).await.value, (2, 2));
+    let first = observation(&oracle, pool_id, 0).await.value.unwrap();
+    for _ in 0..2 {
+        advance_time(&env.provider, Duration::from_secs(HOUR)).await;
+        update(&oracle, &env.amm.instance, pool_id).await;
+    }
+
+    assert_eq!(observation_count(&oracle, pool_id).await.value, 2);
+    let oldest = observation(&oracle, pool_id, 0).await.value.unwrap();
+    let latest = observation(&oracle, pool_id, 1).await.value.unwrap();
+    assert!(oldest.timestamp >= first.timestamp + HOUR);
+    assert!(latest.timestamp >= oldest.timestamp + HOUR);
+    assert_eq!(observation(&oracle, pool_id, 2).await.value, None);
+
+    // the windows are limited to the observations kept
+    assert!(consult(&oracle, pool_id, HOUR).await.value.is_some());
+    assert_eq!(consult(&oracle, pool_id, 3 * HOUR / 2).await.value, None);
+}
+
+#[tokio::test]
+async fn keeper_grows_the_ring_once_it_reaches_its_last_slot() {
+    let (env, oracle) = setup_with_cardinality(false, false, Some(2)).await;
+    let pool_id = env.pools[0];
+
+    update(&oracle, &env.amm.instance, pool_id).await;
+    increase_cardinality(&oracle, pool_id, 3).await;
+    // lowering it is ignored
+    increase_cardinality(&oracle, pool_id, 1).await;
+    assert_eq!(cardinality(&oracle, pool_id).await.value, (2, 3));
+
+    let first = observation(&oracle, pool_id, 0).await.value.unwrap();
+    for _ in 0..2 {
+        advance_time(&env.provider, Duration::from_secs(HOUR)).await;
+        update(&oracle, &env.amm.instance, pool_id).await;
+    }
+
+    assert_eq!(cardinality(&oracle, pool_id).await.value, (3, 3));
+    assert_eq!(observation_count(&oracle, pool_id).await.value, 3);
+    assert_eq!(observation(&oracle, pool_id, 0).await.value.unwrap(), first);
+    assert!(consult(&oracle, pool_id, 3 * HOUR / 2)
+        .await
+        .value
+        .is_some());
+}
@@ -0,0 +1,99 @@
+use fuels::prelude::{VariableOutputPolicy, WalletUnlocked};
+use fuels::types::{AssetId, U256};
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::{pool_metadata, set_hook};
+use test_harness::interface::oracle::deploy_twap_oracle_contract;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::TwapOracle;
+use test_harness::math::{pow_decimals, spot_price};
+use test_harness::types::PoolId;
+
+/// Seeds a pool between two 9 decimals tokens at a 1:1 price and deploys the oracle,
+/// installed as the AMM hook if `as_hook`
+pub async fn setup(
+    is_stable: bool,
+    as_hook: bool,
+) -> (TestEnvironment, TwapOracle<WalletUnlocked>) {
+    setup_with_cardinality(is_stable, as_hook, None).await
+}
+
+/// `setup` with an oracle keeping `initial_cardinality` observations per pool, if given
+pub async fn setup_with_cardinality(
+    is_stable: bool,
+    as_hook: bool,
+    initial_cardinality: Option<u64>,
+) -> (TestEnvironment, TwapOracle<WalletUnlocked>) {
+    let pool = if is_stable {
+        PoolConfig::stable(0, 1)
+    } else {
+        PoolConfig::volatile(0, 1)
+    };
+    let env = TestEnvironment::builder()
+        .tokens(&[9, 9])
+        .mint_amount(100_000_000_000_000)
+        .pool(pool.with_liquidity(10_000_000_000_000, 10_000_000_000_000))
+        .scripts(&[PeripheryScript::SwapExactInput])
+        .build()
+        .await;
+    let (oracle_id, oracle) =
+        deploy_twap_oracle_contract(&env.wallet, env.amm.id, initial_cardinality).await;
+    if as_hook {
+        set_hook(&env.amm.instance, Some(oracle_id)).await;
+    }
+    (env, oracle)
+}
+
+/// Swaps `amount_in` of `asset_in` through the pool, calling the oracle if it is the hook
+pub async fn swap(
+    env: &TestEnvironment,
+    oracle: &TwapOracle<WalletUnlocked>,
+    asset_in: AssetId,
+    amount_in: u64,
+) {
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(asset_in, amount_in)]).await;
+    env.scripts
+        .swap_exact_input()
+        .main(
+            amount_in,
+            asset_in,
+            0,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance, oracle])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+/// Current spot prices of the pool, `(price_0, price_1)`, as the oracle computes them
+pub async fn spot_prices(env: &TestEnvironment, pool_id: PoolId) -> (U256, U256) {
+    let pool = pool_metadata(&env.amm.instance, pool_id)
+        .await
+        .value
+        .unwrap();
+    let (reserve_0, reserve_1) = (U256::from(pool.reserve_0), U256::from(pool.reserve_1));
+    let (pow_decimals_0, pow_decimals_1) =
+        (pow_decimals(pool.decimals_0), pow_decimals(pool.decimals_1));
+    (
+        spot_price(
+            pool_id.2,
+            reserve_0,
+            reserve_1,
+            pow_decimals_0,
+            pow_decimals_1,
+        ),
+        spot_price(
+            pool_id.2,
+            reserve_1,
+            reserve_0,
+            pow_decimals_1,
+            pow_decimals_0,
+        ),
+    )
+}
@@ -0,0 +1,19 @@
+library;
+
+use ::data_structures::PoolId;
+
+/// Called by the AMM after every mint, burn and swap once a hook is set
+abi IBaseHook {
+    #[storage(read, write)]
+    fn hook(
+        pool_id: PoolId,
+        sender: Identity,
+        to: Identity,
+        asset_0_in: u64,
+        asset_1_in: u64,
+        asset_0_out: u64,
+        asset_1_out: u64,
+        lp_token_in: u64,
+        lp_token_out: u64,
+    );
+}
@@ -4,3 +4,5 @@ library;
 pub mod mira_amm;
 pub mod data_structures;
 pub mod callee;
+pub mod hook;
+pub mod twap_oracle;
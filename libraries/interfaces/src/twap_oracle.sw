@@ -0,0 +1,55 @@
+library;
+
+use ::data_structures::PoolId;
+
+/// Price accumulators of a pool at the time of an update
+pub struct Observation {
+    /// Time of the block, in TAI64 seconds
+    pub timestamp: u64,
+    /// Sum of `price_0` over the seconds elapsed since the first observation of the pool
+    pub price_0_cumulative: u256,
+    pub price_1_cumulative: u256,
+    /// Spot price of one whole asset_0 in whole asset_1, scaled by 1e18, from this update on
+    pub price_0: u256,
+    /// Spot price of one whole asset_1 in whole asset_0, scaled by 1e18, from this update on
+    pub price_1: u256,
+}
+
+/// Time-weighted average prices of the AMM pools.
+///
+/// Every pool keeps its latest observations in a ring buffer of `cardinality` slots, at most one
+/// per block, overwriting the oldest one once full. Rings start with the `INITIAL_CARDINALITY`
+/// the oracle is deployed with, 3_600 by default. The windows `consult` answers for are limited
+/// to the time these observations span, which a keeper extends with `increase_cardinality`.
+abi TwapOracle {
+    /// Accumulates the price of `pool_id` since the previous update and records its current
+    /// spot price. Called by the AMM after every mint, burn and swap when installed as the hook,
+    /// or by a keeper otherwise. The observation is skipped when a spot price is zero or
+    /// overflows, so that the hook never reverts the AMM call.
+    #[storage(read, write)]
+    fn update(pool_id: PoolId);
+
+    /// Average `(price_0, price_1)` of `pool_id` over the last `window` seconds,
+    /// or `None` if the observations kept don't go back that far
+    #[storage(read)]
+    fn consult(pool_id: PoolId, window: u64) -> Option<(u256, u256)>;
+
+    /// Grows the ring of `pool_id` to `cardinality` observations, if it is larger than the
+    /// current target. The ring grows once it reaches its last slot, so that the observations
+    /// already kept stay in order.
+    #[storage(read, write)]
+    fn increase_cardinality(pool_id: PoolId, cardinality: u64);
+
+    /// `(cardinality, cardinality_next)` of `pool_id`: the slots of its ring, zero until its
+    /// first update, and the slots it grows to
+    #[storage(read)]
+    fn cardinality(pool_id: PoolId) -> (u64, u64);
+
+    /// Observations kept for `pool_id`, up to its cardinality
+    #[storage(read)]
+    fn observation_count(pool_id: PoolId) -> u64;
+
+    /// `index`-th oldest observation kept for `pool_id`
+    #[storage(read)]
+    fn observation(pool_id: PoolId, index: u64) -> Option<Observation>;
+}
@@ -0,0 +1,7 @@
+library;
+
+/// Errors of the stable curve solver
+pub enum SolverError {
+    /// Neither Newton's method nor bisection found `y` within the curve domain
+    NoConvergence: (),
+}
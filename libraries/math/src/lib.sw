@@ -1,3 +1,4 @@
 library;
 
+pub mod errors;
 pub mod pool_math;
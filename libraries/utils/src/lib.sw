@@ -1,3 +1,5 @@
 library;
 
 pub mod blockchain_utils;
+pub mod predicate_utils;
+pub mod swap_utils;
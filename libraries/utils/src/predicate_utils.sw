@@ -0,0 +1,72 @@
+library;
+
+use interfaces::data_structures::PoolId;
+use std::{
+    b512::B512,
+    ecr::ec_recover_address,
+    outputs::{
+        Output,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+    primitive_conversions::u16::*,
+    tx::{
+        tx_id,
+        tx_witness_data,
+    },
+};
+
+/// Arguments of the `swap_exact_input_script` main function:
+/// (amount_in, asset_in, amount_out_min, pools, recipient, deadline)
+pub type SwapExactInputArguments = (u64, AssetId, u64, Vec<PoolId>, Identity, u32);
+
+/// Checks that the witness at the provided index is a signature of the transaction by `signer`
+pub fn is_signed_by(signer: Address, witness_index: u64) -> bool {
+    let signature: Option<B512> = tx_witness_data(witness_index);
+    match signature {
+        Some(signature) => match ec_recover_address(signature, tx_id()) {
+            Ok(address) => address == signer,
+            Err(_) => false,
+        },
+        None => false,
+    }
+}
+
+/// Returns the asset received at the end of the swap path starting with `asset_in`
+pub fn get_final_asset(asset_in: AssetId, pools: Vec<PoolId>) -> AssetId {
+    let mut asset = asset_in;
+    let mut i = 0;
+    while i < pools.len() {
+        let pool_id = pools.get(i).unwrap();
+        asset = if pool_id.0 == asset {
+            pool_id.1
+        } else {
+            pool_id.0
+        };
+        i += 1;
+    }
+    asset
+}
+
+/// Checks that every coin and change output of `asset` goes to `to`
+pub fn outputs_of_asset_go_to(asset: AssetId, to: Address) -> bool {
+    let outputs = output_count().as_u64();
+    let mut i = 0;
+    while i < outputs {
+        let is_coin_or_change = match output_type(i) {
+            Some(Output::Coin) => true,
+            Some(Output::Change) => true,
+            _ => false,
+        };
+        if is_coin_or_change
+            && output_asset_id(i).unwrap() == asset
+            && output_asset_to(i).unwrap() != to
+        {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
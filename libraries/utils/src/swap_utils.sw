@@ -0,0 +1,36 @@
+library;
+
+use interfaces::{data_structures::PoolId, mira_amm::MiraAMM};
+use std::{asset::transfer, bytes::Bytes};
+
+/// Sends the route input to the AMM and executes the swaps of the route one after another.
+/// `amounts_out` are the hop amounts as returned by `get_amounts_out`, starting with the input.
+/// Every hop pays the AMM for the next one, the last one pays `recipient`.
+pub fn swap_exact_input_along_route(
+    amm_contract_id: ContractId,
+    amounts_out: Vec<(u64, AssetId)>,
+    pools: Vec<PoolId>,
+    recipient: Identity,
+) {
+    let (amount_in, asset_in) = amounts_out.get(0).unwrap();
+    transfer(Identity::ContractId(amm_contract_id), asset_in, amount_in);
+    let amm = abi(MiraAMM, amm_contract_id.into());
+
+    let mut i = 0;
+    while i < pools.len() {
+        let pool_id = pools.get(i).unwrap();
+        let (amount_out, asset_out) = amounts_out.get(i + 1).unwrap();
+        let to = if i == pools.len() - 1 {
+            recipient
+        } else {
+            Identity::ContractId(amm_contract_id)
+        };
+        let (amount_0_out, amount_1_out) = if asset_out == pool_id.0 {
+            (amount_out, 0)
+        } else {
+            (0, amount_out)
+        };
+        amm.swap(pool_id, amount_0_out, amount_1_out, to, Bytes::new());
+        i += 1;
+    }
+}
@@ -0,0 +1,7 @@
+predicate;
+
+/// Spendable by any transaction. Stands for a custody account in the tests, which fund
+/// periphery calls from a predicate rather than a wallet.
+fn main() -> bool {
+    true
+}
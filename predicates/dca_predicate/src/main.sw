@@ -0,0 +1,133 @@
+predicate;
+
+use std::{
+    auth::predicate_address,
+    codec::decode_script_data,
+    inputs::{
+        input_amount,
+        input_asset_id,
+        input_coin_owner,
+        input_count,
+    },
+    primitive_conversions::{u16::*, u32::*},
+    tx::{
+        tx_maturity,
+        tx_script_bytecode_hash,
+    },
+};
+use utils::predicate_utils::{
+    get_final_asset,
+    is_signed_by,
+    outputs_of_asset_go_to,
+    SwapExactInputArguments,
+};
+
+configurable {
+    /// Owner of the position: receives the output asset and is the only one able to withdraw the deposit
+    OWNER: Address = Address::zero(),
+    /// Only account able to submit the tranches, choosing their minimum output from a fresh quote
+    KEEPER: Address = Address::zero(),
+    ASSET_IN: AssetId = AssetId::zero(),
+    ASSET_OUT: AssetId = AssetId::zero(),
+    /// Amount deposited into the predicate for the whole position
+    TOTAL_AMOUNT: u64 = 0,
+    /// Amount of `ASSET_IN` released in every tranche
+    CHUNK_AMOUNT: u64 = 0,
+    /// Minimum amount of `ASSET_OUT` received for every tranche
+    MIN_AMOUNT_OUT_PER_CHUNK: u64 = 0,
+    /// Block height from which the first tranche can be swapped
+    START_HEIGHT: u32 = 0,
+    /// Number of blocks between two tranches
+    BLOCK_INTERVAL: u32 = 0,
+    /// Hash of the `swap_exact_input_script` bytecode with its configurables applied
+    SWAP_SCRIPT_BYTECODE_HASH: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+}
+
+/// Signer of a transaction spending the deposit, with the index of its signature witness
+enum DcaSigner {
+    /// Withdrawal of the deposit
+    Owner: u64,
+    /// Execution of the next unlocked tranche
+    Keeper: u64,
+}
+
+/// Spends the deposit either when the owner signed the transaction (withdrawal),
+/// or when the keeper signed a transaction swapping the next unlocked tranche for the owner
+/// (execution)
+fn main(signer: DcaSigner) -> bool {
+    match signer {
+        DcaSigner::Owner(witness_index) => is_signed_by(OWNER, witness_index),
+        DcaSigner::Keeper(witness_index) => {
+            is_signed_by(KEEPER, witness_index) && is_valid_tranche()
+        },
+    }
+}
+
+fn is_valid_tranche() -> bool {
+    match tx_script_bytecode_hash() {
+        Some(hash) => {
+            if hash != SWAP_SCRIPT_BYTECODE_HASH {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    let (amount_in, asset_in, amount_out_min, pools, recipient, _deadline) = decode_script_data::<SwapExactInputArguments>();
+    if amount_in != CHUNK_AMOUNT || asset_in != ASSET_IN || recipient != Identity::Address(OWNER) {
+        return false;
+    }
+    if amount_out_min < MIN_AMOUNT_OUT_PER_CHUNK {
+        return false;
+    }
+    if get_final_asset(asset_in, pools) != ASSET_OUT {
+        return false;
+    }
+
+    // The tranche index is derived from the deposit left in the predicate, rounding the tranches
+    // executed up so that coins sent to the predicate unlock nothing unless they are worth a
+    // whole chunk, swapped for the owner. Leaving some of the predicate coins out of the
+    // transaction only makes the required maturity higher.
+    let remaining = predicate_balance();
+    let spent = if remaining < TOTAL_AMOUNT {
+        TOTAL_AMOUNT - remaining
+    } else {
+        0
+    };
+    let executed_tranches = if spent % CHUNK_AMOUNT == 0 {
+        spent / CHUNK_AMOUNT
+    } else {
+        spent / CHUNK_AMOUNT + 1
+    };
+    let unlock_height = START_HEIGHT.as_u64() + executed_tranches * BLOCK_INTERVAL.as_u64();
+    match tx_maturity() {
+        Some(maturity) => {
+            if maturity.as_u64() < unlock_height {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    // what is left of the deposit stays locked in the predicate
+    outputs_of_asset_go_to(ASSET_IN, predicate_address().unwrap())
+}
+
+/// Sums the `ASSET_IN` coins spent from this predicate in the transaction
+fn predicate_balance() -> u64 {
+    let this = predicate_address().unwrap();
+    let inputs = input_count().as_u64();
+    let mut balance = 0;
+    let mut i = 0;
+    while i < inputs {
+        let owner = input_coin_owner(i);
+        if owner.is_some()
+            && owner.unwrap() == this
+            && input_asset_id(i).unwrap() == ASSET_IN
+        {
+            balance += input_amount(i).unwrap();
+        }
+        i += 1;
+    }
+    balance
+}
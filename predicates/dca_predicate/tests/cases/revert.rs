@@ -0,0 +1,242 @@
+use crate::utils::{setup, swap_script};
+use fuels::accounts::{predicate::Predicate, wallet::WalletUnlocked, Account, ViewOnlyAccount};
+use fuels::prelude::{TxPolicies, VariableOutputPolicy};
+use fuels::types::output::Output;
+use test_harness::dca::{DcaKeeper, DcaPosition};
+use test_harness::types::PoolId;
+
+const CHUNK_AMOUNT: u64 = 1_000;
+
+async fn funded_position(
+    dca_keeper: &mut DcaKeeper,
+    wallet: &WalletUnlocked,
+    pool_id: PoolId,
+    start_height: u32,
+) -> Predicate {
+    let provider = wallet.try_provider().unwrap();
+    let position = DcaPosition {
+        owner: wallet.address().into(),
+        asset_in: pool_id.0,
+        asset_out: pool_id.1,
+        total_amount: 3 * CHUNK_AMOUNT,
+        chunk_amount: CHUNK_AMOUNT,
+        min_amount_out_per_chunk: 900,
+        start_height,
+        block_interval: 100,
+        pools: vec![pool_id],
+    };
+    let predicate = dca_keeper.create_position(position.clone(), provider);
+    wallet
+        .transfer(
+            predicate.address(),
+            position.total_amount,
+            pool_id.0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+    predicate
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_tranche_is_not_unlocked() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+    let height = env.provider.latest_block_height().await.unwrap();
+
+    let predicate = funded_position(&mut dca_keeper, &env.wallet, env.pools[0], height + 50).await;
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, 3 * CHUNK_AMOUNT, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: predicate.address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            CHUNK_AMOUNT,
+            env.pools[0].0,
+            900,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_tx_policies(TxPolicies::default().with_maturity(height))
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_swapping_more_than_a_chunk() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+    let height = env.provider.latest_block_height().await.unwrap();
+
+    let predicate = funded_position(&mut dca_keeper, &env.wallet, env.pools[0], height).await;
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, 3 * CHUNK_AMOUNT, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: predicate.address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            2 * CHUNK_AMOUNT,
+            env.pools[0].0,
+            1_800,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_tx_policies(TxPolicies::default().with_maturity(height))
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_remaining_deposit_leaves_the_predicate() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+    let height = env.provider.latest_block_height().await.unwrap();
+
+    let predicate = funded_position(&mut dca_keeper, &env.wallet, env.pools[0], height).await;
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, 3 * CHUNK_AMOUNT, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: env.wallets[0].address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            CHUNK_AMOUNT,
+            env.pools[0].0,
+            900,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_tx_policies(TxPolicies::default().with_maturity(height))
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_dust_is_sent_to_unlock_the_next_tranche() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+    let height = env.provider.latest_block_height().await.unwrap();
+
+    let predicate = funded_position(&mut dca_keeper, &env.wallet, env.pools[0], height).await;
+    let responses = dca_keeper
+        .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+        .await;
+    assert!(responses[0].is_ok());
+
+    // a unit short of a whole chunk executed less, the next tranche being 100 blocks away
+    env.wallet
+        .transfer(
+            predicate.address(),
+            1,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+    let height = env.provider.latest_block_height().await.unwrap();
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, 2 * CHUNK_AMOUNT + 1, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: predicate.address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            CHUNK_AMOUNT,
+            env.pools[0].0,
+            900,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_tx_policies(TxPolicies::default().with_maturity(height))
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_tranche_is_not_signed_by_the_keeper() {
+    let (env, _, mut dca_keeper) = setup().await;
+    let height = env.provider.latest_block_height().await.unwrap();
+
+    let predicate = funded_position(&mut dca_keeper, &env.wallet, env.pools[0], height).await;
+    // same script bytecode, paid for and signed by the owner
+    let owner_swap_script = swap_script(env.wallet.clone(), env.amm.id);
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, 3 * CHUNK_AMOUNT, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: predicate.address().into(),
+    }];
+
+    owner_swap_script
+        .main(
+            CHUNK_AMOUNT,
+            env.pools[0].0,
+            900,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_tx_policies(TxPolicies::default().with_maturity(height))
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
@@ -0,0 +1,219 @@
+use crate::utils::setup;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use fuels::prelude::{Address, TxPolicies};
+use std::time::Duration;
+use test_harness::dca::DcaPosition;
+use test_harness::setup::common::produce_blocks_until;
+
+#[tokio::test]
+async fn executes_tranches_at_block_intervals() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+
+    let chunk_amount = 1_000;
+    let block_interval = 5;
+    let start_height = env.provider.latest_block_height().await.unwrap() + 5;
+    let position = DcaPosition {
+        owner: env.wallet.address().into(),
+        asset_in: env.pools[0].0,
+        asset_out: env.pools[0].1,
+        total_amount: 3 * chunk_amount,
+        chunk_amount,
+        min_amount_out_per_chunk: 900,
+        start_height,
+        block_interval,
+        pools: vec![env.pools[0]],
+    };
+
+    let predicate = dca_keeper.create_position(position.clone(), &env.provider);
+    env.wallet
+        .transfer(
+            predicate.address(),
+            position.total_amount,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    // nothing is unlocked before the start height
+    assert!(dca_keeper
+        .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+        .await
+        .is_empty());
+
+    let balance_before = env.wallet.get_asset_balance(&env.pools[0].1).await.unwrap();
+    let mut amount_received = 0;
+    for tranche in 0..3 {
+        produce_blocks_until(&env.provider, start_height + tranche * block_interval).await;
+
+        let responses = dca_keeper
+            .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+            .await;
+        assert_eq!(responses.len(), 1);
+        let amounts_out = &responses.first().unwrap().as_ref().unwrap().value;
+        assert_eq!(
+            *amounts_out.first().unwrap(),
+            (chunk_amount, env.pools[0].0)
+        );
+        assert!(amounts_out.last().unwrap().0 >= position.min_amount_out_per_chunk);
+        amount_received += amounts_out.last().unwrap().0;
+
+        assert_eq!(
+            predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+            position.total_amount - (u64::from(tranche) + 1) * chunk_amount
+        );
+
+        // the next tranche is locked until the next interval
+        assert!(dca_keeper
+            .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+            .await
+            .is_empty());
+    }
+
+    produce_blocks_until(&env.provider, start_height + 4 * block_interval).await;
+    assert!(dca_keeper
+        .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+        .await
+        .is_empty());
+
+    let balance_after = env.wallet.get_asset_balance(&env.pools[0].1).await.unwrap();
+    assert_eq!(balance_after, balance_before + amount_received);
+}
+
+#[tokio::test]
+async fn skips_tranche_below_minimum_output() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+
+    let chunk_amount = 1_000;
+    let position = DcaPosition {
+        owner: env.wallet.address().into(),
+        asset_in: env.pools[0].0,
+        asset_out: env.pools[0].1,
+        total_amount: 2 * chunk_amount,
+        chunk_amount,
+        min_amount_out_per_chunk: 1_100,
+        start_height: env.provider.latest_block_height().await.unwrap(),
+        block_interval: 5,
+        pools: vec![env.pools[0]],
+    };
+
+    let predicate = dca_keeper.create_position(position.clone(), &env.provider);
+    env.wallet
+        .transfer(
+            predicate.address(),
+            position.total_amount,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(dca_keeper
+        .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+        .await
+        .is_empty());
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        position.total_amount
+    );
+}
+
+#[tokio::test]
+async fn withdraws_remaining_deposit() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+
+    let chunk_amount = 1_000;
+    let position = DcaPosition {
+        owner: env.wallet.address().into(),
+        asset_in: env.pools[0].0,
+        asset_out: env.pools[0].1,
+        total_amount: 3 * chunk_amount,
+        chunk_amount,
+        min_amount_out_per_chunk: 900,
+        start_height: env.provider.latest_block_height().await.unwrap(),
+        block_interval: 100,
+        pools: vec![env.pools[0]],
+    };
+
+    let predicate = dca_keeper.create_position(position.clone(), &env.provider);
+    let predicate_address = Address::from(predicate.address());
+    env.wallet
+        .transfer(
+            predicate.address(),
+            position.total_amount,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let responses = dca_keeper
+        .execute_due_tranches(&env.provider, &env.amm, &keeper_swap_script)
+        .await;
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].is_ok());
+
+    let balance_before = env.wallet.get_asset_balance(&env.pools[0].0).await.unwrap();
+    dca_keeper.withdraw(&predicate_address, &env.wallet).await;
+    let balance_after = env.wallet.get_asset_balance(&env.pools[0].0).await.unwrap();
+
+    assert_eq!(
+        balance_after,
+        balance_before + position.total_amount - chunk_amount
+    );
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        0
+    );
+    assert!(dca_keeper.position(&predicate_address).is_none());
+}
+
+#[tokio::test]
+async fn keeper_runs_until_positions_are_exhausted() {
+    let (env, keeper_swap_script, mut dca_keeper) = setup().await;
+
+    let chunk_amount = 1_000;
+    let position = DcaPosition {
+        owner: env.wallet.address().into(),
+        asset_in: env.pools[0].0,
+        asset_out: env.pools[0].1,
+        total_amount: 2 * chunk_amount,
+        chunk_amount,
+        min_amount_out_per_chunk: 900,
+        start_height: env.provider.latest_block_height().await.unwrap(),
+        block_interval: 1,
+        pools: vec![env.pools[0]],
+    };
+
+    let predicate = dca_keeper.create_position(position.clone(), &env.provider);
+    env.wallet
+        .transfer(
+            predicate.address(),
+            position.total_amount,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let balance_before = env.wallet.get_asset_balance(&env.pools[0].1).await.unwrap();
+    // every submitted tranche produces a block, unlocking the next one
+    tokio::time::timeout(
+        Duration::from_secs(60),
+        dca_keeper.run(
+            &env.provider,
+            &env.amm,
+            &keeper_swap_script,
+            Duration::from_millis(100),
+        ),
+    )
+    .await
+    .unwrap();
+    let balance_after = env.wallet.get_asset_balance(&env.pools[0].1).await.unwrap();
+
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        0
+    );
+    assert!(balance_after >= balance_before + 2 * position.min_amount_out_per_chunk);
+}
@@ -0,0 +1,49 @@
+use fuels::accounts::{wallet::WalletUnlocked, ViewOnlyAccount};
+use fuels::types::ContractId;
+use test_harness::dca::DcaKeeper;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::{SwapExactInputScript, SwapExactInputScriptConfigurables};
+use test_harness::paths::SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH;
+use test_harness::slippage::Tolerance;
+
+pub const KEEPER_BASE_ASSET_AMOUNT: u64 = 10_000_000;
+pub const POOL_LIQUIDITY: u64 = 1_000_000;
+pub const TOLERANCE: Tolerance = Tolerance::Bps(50);
+
+/// Returns an environment with a funded 1:1 volatile pool and a keeper wallet, along with the
+/// swap script instance of the keeper (plain bytecode, as required by the DCA predicates) and
+/// a `DcaKeeper` submitting through it
+pub async fn setup() -> (
+    TestEnvironment,
+    SwapExactInputScript<WalletUnlocked>,
+    DcaKeeper,
+) {
+    let environment = TestEnvironment::builder()
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(POOL_LIQUIDITY, POOL_LIQUIDITY))
+        .wallets(1, KEEPER_BASE_ASSET_AMOUNT)
+        .scripts(&[PeripheryScript::SwapExactInput])
+        .build()
+        .await;
+
+    let keeper = environment.wallets[0].clone();
+    let dca_keeper = DcaKeeper::new(environment.amm.id, keeper.address().into(), TOLERANCE);
+    let keeper_swap_exact_input_script_instance = swap_script(keeper, environment.amm.id);
+
+    (
+        environment,
+        keeper_swap_exact_input_script_instance,
+        dca_keeper,
+    )
+}
+
+/// Plain bytecode swap script instance paid for and signed by `wallet`
+pub fn swap_script(
+    wallet: WalletUnlocked,
+    amm_contract_id: ContractId,
+) -> SwapExactInputScript<WalletUnlocked> {
+    let configurables = SwapExactInputScriptConfigurables::default()
+        .with_AMM_CONTRACT_ID(amm_contract_id)
+        .unwrap();
+    SwapExactInputScript::new(wallet, SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH)
+        .with_configurables(configurables)
+}
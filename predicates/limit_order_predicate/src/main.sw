@@ -0,0 +1,63 @@
+predicate;
+
+use std::{
+    auth::predicate_address,
+    codec::decode_script_data,
+    primitive_conversions::u64::*,
+    tx::tx_script_bytecode_hash,
+};
+use utils::predicate_utils::{
+    get_final_asset,
+    is_signed_by,
+    outputs_of_asset_go_to,
+    SwapExactInputArguments,
+};
+
+configurable {
+    /// Owner of the order: receives the output asset and is the only one able to cancel the order
+    OWNER: Address = Address::zero(),
+    ASSET_IN: AssetId = AssetId::zero(),
+    ASSET_OUT: AssetId = AssetId::zero(),
+    /// Order price: at least `MIN_AMOUNT_OUT` of `ASSET_OUT` for every `AMOUNT_IN` of `ASSET_IN`
+    AMOUNT_IN: u64 = 0,
+    MIN_AMOUNT_OUT: u64 = 0,
+    /// Hash of the `swap_exact_input_script` bytecode with its configurables applied
+    SWAP_SCRIPT_BYTECODE_HASH: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+}
+
+/// Spends the order coins either when the owner signed the transaction (cancellation),
+/// or when the transaction runs the swap script at the order price or better (fill).
+/// Fills can be partial: the part of the order which is not swapped stays in the predicate
+fn main(cancel_witness_index: Option<u64>) -> bool {
+    match cancel_witness_index {
+        Some(witness_index) => is_signed_by(OWNER, witness_index),
+        None => is_valid_fill(),
+    }
+}
+
+fn is_valid_fill() -> bool {
+    match tx_script_bytecode_hash() {
+        Some(hash) => {
+            if hash != SWAP_SCRIPT_BYTECODE_HASH {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    let (amount_in, asset_in, amount_out_min, pools, recipient, _deadline) = decode_script_data::<SwapExactInputArguments>();
+    if asset_in != ASSET_IN || recipient != Identity::Address(OWNER) {
+        return false;
+    }
+    if get_final_asset(asset_in, pools) != ASSET_OUT {
+        return false;
+    }
+    // amount_out_min / amount_in >= MIN_AMOUNT_OUT / AMOUNT_IN
+    if amount_out_min.as_u256() * AMOUNT_IN.as_u256() < MIN_AMOUNT_OUT.as_u256() * amount_in.as_u256() {
+        return false;
+    }
+
+    // the part of the order which is not swapped stays resting in the predicate,
+    // sending it to the owner would cancel the rest of the order
+    outputs_of_asset_go_to(ASSET_IN, predicate_address().unwrap())
+}
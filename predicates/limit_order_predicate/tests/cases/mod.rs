@@ -0,0 +1,2 @@
+pub mod revert;
+pub mod success;
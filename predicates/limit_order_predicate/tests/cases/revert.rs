@@ -0,0 +1,199 @@
+use crate::utils::{move_price, setup};
+use fuels::accounts::{Account, ViewOnlyAccount};
+use fuels::prelude::{TxPolicies, VariableOutputPolicy};
+use fuels::types::output::Output;
+use test_harness::order_book::{LimitOrder, OrderBook};
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_filled_to_another_recipient() {
+    let (env, keeper_swap_script) = setup().await;
+
+    let amount_in = 1_000;
+
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(
+        LimitOrder {
+            owner: env.wallet.address().into(),
+            asset_in: env.pools[0].0,
+            asset_out: env.pools[0].1,
+            amount_in,
+            min_amount_out: 1_000,
+            pools: vec![env.pools[0]],
+        },
+        &env.provider,
+    );
+    env.wallet
+        .transfer(
+            predicate.address(),
+            amount_in,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    move_price(
+        env.scripts.swap_exact_input(),
+        &env.amm,
+        &env.wallet,
+        env.pools[0],
+        env.pools[0].0,
+        200_000,
+        env.deadline,
+    )
+    .await;
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, amount_in, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: predicate.address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            amount_in,
+            env.pools[0].0,
+            1_000,
+            vec![env.pools[0]],
+            env.wallets[0].address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_filled_below_order_price() {
+    let (env, keeper_swap_script) = setup().await;
+
+    let amount_in = 1_000;
+
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(
+        LimitOrder {
+            owner: env.wallet.address().into(),
+            asset_in: env.pools[0].0,
+            asset_out: env.pools[0].1,
+            amount_in,
+            min_amount_out: 1_100,
+            pools: vec![env.pools[0]],
+        },
+        &env.provider,
+    );
+    env.wallet
+        .transfer(
+            predicate.address(),
+            amount_in,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, amount_in, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: predicate.address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            amount_in,
+            env.pools[0].0,
+            0,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "PredicateVerificationFailed")]
+async fn panics_when_partial_fill_sends_remainder_to_owner() {
+    let (env, keeper_swap_script) = setup().await;
+
+    let amount_in = 1_000;
+
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(
+        LimitOrder {
+            owner: env.wallet.address().into(),
+            asset_in: env.pools[0].0,
+            asset_out: env.pools[0].1,
+            amount_in,
+            min_amount_out: 1_000,
+            pools: vec![env.pools[0]],
+        },
+        &env.provider,
+    );
+    env.wallet
+        .transfer(
+            predicate.address(),
+            amount_in,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    move_price(
+        env.scripts.swap_exact_input(),
+        &env.amm,
+        &env.wallet,
+        env.pools[0],
+        env.pools[0].0,
+        200_000,
+        env.deadline,
+    )
+    .await;
+
+    // the keeper fills a tenth of the order and closes the rest of it
+    let inputs = predicate
+        .get_asset_inputs_for_amount(env.pools[0].0, amount_in, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: env.pools[0].0,
+        amount: 0,
+        to: env.wallet.address().into(),
+    }];
+
+    keeper_swap_script
+        .main(
+            amount_in / 10,
+            env.pools[0].0,
+            amount_in / 10,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
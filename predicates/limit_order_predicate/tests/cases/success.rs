@@ -0,0 +1,250 @@
+use crate::utils::{move_price, setup};
+use fuels::accounts::{Account, ViewOnlyAccount};
+use fuels::prelude::{Address, TxPolicies};
+use test_harness::order_book::{LimitOrder, OrderBook};
+
+#[tokio::test]
+async fn fills_order_once_price_crosses() {
+    let (env, keeper_swap_script) = setup().await;
+
+    let amount_in = 1_000;
+    let min_amount_out = 1_100;
+
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(
+        LimitOrder {
+            owner: env.wallet.address().into(),
+            asset_in: env.pools[0].0,
+            asset_out: env.pools[0].1,
+            amount_in,
+            min_amount_out,
+            pools: vec![env.pools[0]],
+        },
+        &env.provider,
+    );
+    let predicate_address = Address::from(predicate.address());
+    env.wallet
+        .transfer(
+            predicate.address(),
+            amount_in,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    // 1:1 pool pays less than the order price
+    assert!(order_book
+        .fill_order(
+            &predicate_address,
+            &env.amm,
+            &keeper_swap_script,
+            env.deadline
+        )
+        .await
+        .is_none());
+
+    move_price(
+        env.scripts.swap_exact_input(),
+        &env.amm,
+        &env.wallet,
+        env.pools[0],
+        env.pools[0].0,
+        200_000,
+        env.deadline,
+    )
+    .await;
+
+    let balance_before = env.wallet.get_asset_balance(&env.pools[0].1).await.unwrap();
+    let amounts_out = order_book
+        .fill_order(
+            &predicate_address,
+            &env.amm,
+            &keeper_swap_script,
+            env.deadline,
+        )
+        .await
+        .unwrap()
+        .value;
+    let balance_after = env.wallet.get_asset_balance(&env.pools[0].1).await.unwrap();
+
+    let (amount_out, asset_out) = *amounts_out.last().unwrap();
+    assert_eq!(asset_out, env.pools[0].1);
+    assert!(amount_out >= min_amount_out);
+    assert_eq!(balance_after, balance_before + amount_out);
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn fills_partially_funded_order_at_order_price() {
+    let (env, keeper_swap_script) = setup().await;
+
+    let order = LimitOrder {
+        owner: env.wallet.address().into(),
+        asset_in: env.pools[0].0,
+        asset_out: env.pools[0].1,
+        amount_in: 10_000,
+        min_amount_out: 11_000,
+        pools: vec![env.pools[0]],
+    };
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(order.clone(), &env.provider);
+    let predicate_address = Address::from(predicate.address());
+
+    let funded_amount = 2_500;
+    env.wallet
+        .transfer(
+            predicate.address(),
+            funded_amount,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    move_price(
+        env.scripts.swap_exact_input(),
+        &env.amm,
+        &env.wallet,
+        env.pools[0],
+        env.pools[0].0,
+        200_000,
+        env.deadline,
+    )
+    .await;
+
+    let amounts_out = order_book
+        .fill_order(
+            &predicate_address,
+            &env.amm,
+            &keeper_swap_script,
+            env.deadline,
+        )
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amounts_out.first().unwrap().0, funded_amount);
+    assert!(amounts_out.last().unwrap().0 >= order.min_amount_out_for(funded_amount));
+}
+
+#[tokio::test]
+async fn partial_fill_keeps_the_rest_of_the_order_open() {
+    let (env, keeper_swap_script) = setup().await;
+
+    let order = LimitOrder {
+        owner: env.wallet.address().into(),
+        asset_in: env.pools[0].0,
+        asset_out: env.pools[0].1,
+        amount_in: 10_000,
+        min_amount_out: 11_000,
+        pools: vec![env.pools[0]],
+    };
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(order.clone(), &env.provider);
+    let predicate_address = Address::from(predicate.address());
+    env.wallet
+        .transfer(
+            predicate.address(),
+            order.amount_in,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    move_price(
+        env.scripts.swap_exact_input(),
+        &env.amm,
+        &env.wallet,
+        env.pools[0],
+        env.pools[0].0,
+        200_000,
+        env.deadline,
+    )
+    .await;
+
+    let first_fill = 4_000;
+    let amounts_out = order_book
+        .fill_order_partially(
+            &predicate_address,
+            first_fill,
+            &env.amm,
+            &keeper_swap_script,
+            env.deadline,
+        )
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(amounts_out.first().unwrap().0, first_fill);
+    assert!(amounts_out.last().unwrap().0 >= order.min_amount_out_for(first_fill));
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        order.amount_in - first_fill
+    );
+
+    // the remainder is still fillable at the order price
+    let amounts_out = order_book
+        .fill_order(
+            &predicate_address,
+            &env.amm,
+            &keeper_swap_script,
+            env.deadline,
+        )
+        .await
+        .unwrap()
+        .value;
+    let second_fill = order.amount_in - first_fill;
+    assert_eq!(amounts_out.first().unwrap().0, second_fill);
+    assert!(amounts_out.last().unwrap().0 >= order.min_amount_out_for(second_fill));
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn cancels_order_and_returns_funds_to_owner() {
+    let (env, _) = setup().await;
+
+    let amount_in = 1_000;
+
+    let mut order_book = OrderBook::new(env.amm.id);
+    let predicate = order_book.create_order(
+        LimitOrder {
+            owner: env.wallet.address().into(),
+            asset_in: env.pools[0].0,
+            asset_out: env.pools[0].1,
+            amount_in,
+            min_amount_out: 1_000,
+            pools: vec![env.pools[0]],
+        },
+        &env.provider,
+    );
+    let predicate_address = Address::from(predicate.address());
+    env.wallet
+        .transfer(
+            predicate.address(),
+            amount_in,
+            env.pools[0].0,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let balance_before = env.wallet.get_asset_balance(&env.pools[0].0).await.unwrap();
+    order_book
+        .cancel_order(&predicate_address, &env.wallet)
+        .await;
+    let balance_after = env.wallet.get_asset_balance(&env.pools[0].0).await.unwrap();
+
+    assert_eq!(balance_after, balance_before + amount_in);
+    assert_eq!(
+        predicate.get_asset_balance(&env.pools[0].0).await.unwrap(),
+        0
+    );
+    assert!(order_book.order(&predicate_address).is_none());
+}
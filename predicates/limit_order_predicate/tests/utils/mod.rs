@@ -0,0 +1,69 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::AssetId;
+use test_harness::data_structures::MiraAMMContract;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::{SwapExactInputScript, SwapExactInputScriptConfigurables};
+use test_harness::paths::SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH;
+use test_harness::types::PoolId;
+
+pub const KEEPER_BASE_ASSET_AMOUNT: u64 = 10_000_000;
+pub const POOL_LIQUIDITY: u64 = 1_000_000;
+
+/// Returns an environment with a funded 1:1 volatile pool and a keeper wallet, along with the
+/// swap script instance of the keeper (plain bytecode, as required by the order predicates)
+pub async fn setup() -> (TestEnvironment, SwapExactInputScript<WalletUnlocked>) {
+    let environment = TestEnvironment::builder()
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(POOL_LIQUIDITY, POOL_LIQUIDITY))
+        .wallets(1, KEEPER_BASE_ASSET_AMOUNT)
+        .scripts(&[PeripheryScript::SwapExactInput])
+        .build()
+        .await;
+
+    let configurables = SwapExactInputScriptConfigurables::default()
+        .with_AMM_CONTRACT_ID(environment.amm.id)
+        .unwrap();
+    let keeper_swap_exact_input_script_instance = SwapExactInputScript::new(
+        environment.wallets[0].clone(),
+        SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH,
+    )
+    .with_configurables(configurables);
+
+    (environment, keeper_swap_exact_input_script_instance)
+}
+
+/// Makes `asset` more valuable by swapping `amount` of the other pool asset into the pool
+pub async fn move_price(
+    swap_exact_input_script: &SwapExactInputScript<WalletUnlocked>,
+    amm: &MiraAMMContract,
+    wallet: &WalletUnlocked,
+    pool_id: PoolId,
+    asset: AssetId,
+    amount: u64,
+    deadline: u32,
+) {
+    let asset_in = if asset == pool_id.0 {
+        pool_id.1
+    } else {
+        pool_id.0
+    };
+    let (inputs, outputs) = get_transaction_inputs_outputs(wallet, &vec![(asset_in, amount)]).await;
+
+    swap_exact_input_script
+        .main(
+            amount,
+            asset_in,
+            0,
+            vec![pool_id],
+            wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
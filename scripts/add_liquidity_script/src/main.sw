@@ -1,7 +1,7 @@
 script;
 
 use interfaces::{data_structures::{Asset, PoolId}, mira_amm::MiraAMM};
-use math::pool_math::get_deposit_amounts;
+use math::pool_math::get_pool_deposit_amounts;
 use utils::blockchain_utils::check_deadline;
 use std::asset::transfer;
 
@@ -25,15 +25,12 @@ fn main(
     require(pool_meta_opt.is_some(), "Pool doesn't exist");
     let pool_meta = pool_meta_opt.unwrap();
 
-    let (amount_0, amount_1) = get_deposit_amounts(
+    let (amount_0, amount_1) = get_pool_deposit_amounts(
+        pool_meta,
         amount_0_desired,
         amount_1_desired,
         amount_0_min,
         amount_1_min,
-        pool_meta
-            .reserve_0,
-        pool_meta
-            .reserve_1,
     );
 
     transfer(Identity::ContractId(AMM_CONTRACT_ID), pool_id.0, amount_0);
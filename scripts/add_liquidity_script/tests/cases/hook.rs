@@ -0,0 +1,53 @@
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::hook::{calls, last_call};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::utils::common::MINIMUM_LIQUIDITY;
+
+#[tokio::test]
+async fn adds_liquidity_with_hook_installed() {
+    let env = TestEnvironment::builder()
+        .hook()
+        .pool(PoolConfig::volatile(0, 1))
+        .scripts(&[PeripheryScript::AddLiquidity])
+        .build()
+        .await;
+    let hook = env.hook.as_ref().unwrap();
+    assert_eq!(calls(hook).await.value, 0);
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.pools[0].0, 10_000), (env.pools[0].1, 10_000)],
+    )
+    .await;
+    // only the AMM is given, the hook being found by the dependency estimation
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
+        .main(
+            env.pools[0],
+            10_000,
+            10_000,
+            0,
+            0,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .determine_missing_contracts(None)
+        .await
+        .unwrap()
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(added_liquidity.amount, 10_000 - MINIMUM_LIQUIDITY);
+    assert_eq!(calls(hook).await.value, 1);
+    let call = last_call(hook).await.value.unwrap();
+    assert_eq!(call.pool_id, env.pools[0]);
+    assert_eq!((call.asset_0_in, call.asset_1_in), (10_000, 10_000));
+}
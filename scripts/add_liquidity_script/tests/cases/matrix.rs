@@ -0,0 +1,145 @@
+use fuels::prelude::{VariableOutputPolicy, WalletUnlocked};
+use test_harness::data_structures::MiraAMMContract;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::AddLiquidityScript;
+use test_harness::math::{get_mint_liquidity, get_pool_deposit_amounts};
+use test_harness::matrix::{route, FEES};
+use test_harness::property::{deploy_route, RouteFixture};
+use test_harness::types::PoolId;
+use test_harness::utils::common::MINIMUM_LIQUIDITY;
+
+/// Deposits the desired amounts into `pool_id` and checks the liquidity minted, the amounts
+/// taken and the new reserves against the reference model
+async fn assert_deposit_matches_reference_model(
+    script: &AddLiquidityScript<WalletUnlocked>,
+    amm: &MiraAMMContract,
+    wallet: &WalletUnlocked,
+    pool_id: PoolId,
+    (amount_0_desired, amount_1_desired): (u64, u64),
+    deadline: u32,
+) {
+    let pool_before = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+    let (amount_0, amount_1) =
+        get_pool_deposit_amounts(&pool_before, amount_0_desired, amount_1_desired, 0, 0);
+    let expected_liquidity = get_mint_liquidity(
+        amount_0,
+        amount_1,
+        pool_before.reserve_0,
+        pool_before.reserve_1,
+        pool_before.liquidity.amount,
+    );
+    assert!(amount_0 > 0 && amount_1 > 0 && expected_liquidity > 0);
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        wallet,
+        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+    )
+    .await;
+
+    let added_liquidity = script
+        .main(
+            pool_id,
+            amount_0_desired,
+            amount_1_desired,
+            0,
+            0,
+            wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let pool_after = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+
+    assert_eq!(added_liquidity.amount, expected_liquidity);
+    assert_eq!(pool_after.reserve_0, pool_before.reserve_0 + amount_0);
+    assert_eq!(pool_after.reserve_1, pool_before.reserve_1 + amount_1);
+    // the first deposit locks the minimum liquidity in the pool
+    let locked_liquidity = if pool_before.liquidity.amount == 0 {
+        MINIMUM_LIQUIDITY
+    } else {
+        0
+    };
+    assert_eq!(
+        pool_after.liquidity.amount,
+        pool_before.liquidity.amount + expected_liquidity + locked_liquidity
+    );
+}
+
+async fn deposit_matches_reference_model(decimals: (u8, u8), is_stable: bool) {
+    let RouteFixture {
+        amm,
+        wallet,
+        pools,
+        scripts,
+        deadline,
+        ..
+    } = deploy_route(
+        &route(decimals, is_stable, 1),
+        FEES,
+        &[PeripheryScript::AddLiquidity],
+    )
+    .await;
+    let pool_id = pools[0];
+
+    let pool = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+    // twice as much of the second asset as the reserves call for, so that it gets refunded
+    let amounts_desired = (pool.reserve_0 / 1_000, pool.reserve_1 / 500);
+    assert_deposit_matches_reference_model(
+        scripts.add_liquidity(),
+        &amm,
+        &wallet,
+        pool_id,
+        amounts_desired,
+        deadline,
+    )
+    .await;
+}
+
+/// Deposits the reserves of the matrix pool into an empty pool, which takes them as they are
+async fn first_deposit_matches_reference_model(decimals: (u8, u8), is_stable: bool) {
+    let params = route(decimals, is_stable, 1).pools.remove(0);
+    let pool = if is_stable {
+        PoolConfig::stable(0, 1)
+    } else {
+        PoolConfig::volatile(0, 1)
+    };
+    let env = TestEnvironment::builder()
+        .tokens(&[decimals.0, decimals.1])
+        .mint_amount(params.reserve_in.max(params.reserve_out))
+        .pool(pool)
+        .scripts(&[PeripheryScript::AddLiquidity])
+        .build()
+        .await;
+    let amounts_desired = if env.pools[0].0 == env.tokens[0].id {
+        (params.reserve_in, params.reserve_out)
+    } else {
+        (params.reserve_out, params.reserve_in)
+    };
+
+    assert_deposit_matches_reference_model(
+        env.scripts.add_liquidity(),
+        &env.amm,
+        &env.wallet,
+        env.pools[0],
+        amounts_desired,
+        env.deadline,
+    )
+    .await;
+}
+
+mod seeded_pool {
+    test_harness::pool_matrix!(super::deposit_matches_reference_model);
+}
+
+mod empty_pool {
+    test_harness::pool_matrix!(super::first_deposit_matches_reference_model);
+}
@@ -1 +1,5 @@
+pub mod hook;
+pub mod matrix;
+pub mod properties;
+pub mod revert;
 pub mod success;
@@ -0,0 +1,111 @@
+use fuels::prelude::VariableOutputPolicy;
+use proptest::prelude::*;
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::{get_mint_liquidity, get_pool_deposit_amounts};
+use test_harness::property::{
+    amm_fees, block_on, deploy_case_route, route_params, RouteFixture, CASES,
+};
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn deposit_matches_reference_model(
+        route in route_params(1),
+        route_fees in amm_fees(),
+        share_0_bps in 1..=10_000u64,
+        share_1_bps in 1..=10_000u64,
+    ) {
+        block_on(async {
+            let RouteFixture { amm, wallet, pools, scripts, deadline, .. } =
+                deploy_case_route(&route, route_fees, &[PeripheryScript::AddLiquidity]).await;
+            let script = scripts.add_liquidity();
+            let pool_id = pools[0];
+
+            let pool_before = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+            let amount_0_desired = (pool_before.reserve_0 * share_0_bps / 10_000).max(1);
+            let amount_1_desired = (pool_before.reserve_1 * share_1_bps / 10_000).max(1);
+            // the AMM rejects deposits minting no liquidity
+            prop_assume!(
+                get_mint_liquidity(
+                    amount_0_desired,
+                    amount_1_desired,
+                    pool_before.reserve_0,
+                    pool_before.reserve_1,
+                    pool_before.liquidity.amount,
+                ) > 0
+            );
+            let (amount_0, amount_1) = get_pool_deposit_amounts(
+                &pool_before,
+                amount_0_desired,
+                amount_1_desired,
+                0,
+                0,
+            );
+            let expected_liquidity = get_mint_liquidity(
+                amount_0,
+                amount_1,
+                pool_before.reserve_0,
+                pool_before.reserve_1,
+                pool_before.liquidity.amount,
+            );
+            // nothing beyond rounding is donated to the pool
+            prop_assert!(
+                get_mint_liquidity(
+                    amount_0 - 1,
+                    amount_1,
+                    pool_before.reserve_0,
+                    pool_before.reserve_1,
+                    pool_before.liquidity.amount,
+                ) < expected_liquidity
+            );
+            prop_assert!(
+                get_mint_liquidity(
+                    amount_0,
+                    amount_1 - 1,
+                    pool_before.reserve_0,
+                    pool_before.reserve_1,
+                    pool_before.liquidity.amount,
+                ) < expected_liquidity
+            );
+
+            let (inputs, outputs) = get_transaction_inputs_outputs(
+                &wallet,
+                &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+            )
+            .await;
+
+            let added_liquidity = script
+                .main(
+                    pool_id,
+                    amount_0_desired,
+                    amount_1_desired,
+                    0,
+                    0,
+                    wallet.address().into(),
+                    deadline,
+                )
+                .with_contracts(&[&amm.instance])
+                .with_inputs(inputs)
+                .with_outputs(outputs)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+                .call()
+                .await
+                .unwrap()
+                .value;
+
+            let pool_after = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+
+            prop_assert_eq!(added_liquidity.amount, expected_liquidity);
+            prop_assert_eq!(pool_after.reserve_0, pool_before.reserve_0 + amount_0);
+            prop_assert_eq!(pool_after.reserve_1, pool_before.reserve_1 + amount_1);
+            prop_assert_eq!(
+                pool_after.liquidity.amount,
+                pool_before.liquidity.amount + expected_liquidity
+            );
+            Ok(())
+        })?;
+    }
+}
@@ -0,0 +1,48 @@
+use crate::utils::setup;
+use test_harness::setup::common::{execution_height, produce_blocks_until};
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_on_deadline_one_block_before_execution_height() {
+    let env = setup().await;
+    let deadline = execution_height(&env.provider).await - 1;
+
+    env.scripts
+        .add_liquidity()
+        .main(
+            env.pools[0],
+            10_000,
+            10_000,
+            0,
+            0,
+            env.wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_once_blocks_are_produced_past_deadline() {
+    let env = setup().await;
+    produce_blocks_until(&env.provider, env.deadline).await;
+
+    env.scripts
+        .add_liquidity()
+        .main(
+            env.pools[0],
+            10_000,
+            10_000,
+            0,
+            0,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
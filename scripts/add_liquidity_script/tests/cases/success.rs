@@ -3,33 +3,39 @@ use fuels::accounts::ViewOnlyAccount;
 use fuels::prelude::VariableOutputPolicy;
 use test_harness::interface::amm::pool_metadata;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::setup::common::execution_height;
 use test_harness::utils::common::MINIMUM_LIQUIDITY;
 
 #[tokio::test]
 pub async fn adds_liquidity_with_equal_deposit_amounts() {
-    let (script_instance, amm, pool_id, wallet, deadline) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired = 10000;
     let amount_1_desired = 10000;
     let expected_liquidity = 10000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
-    let added_liquidity = script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -43,29 +49,34 @@ pub async fn adds_liquidity_with_equal_deposit_amounts() {
 
 #[tokio::test]
 async fn adds_liquidity_to_make_a_more_valuable() {
-    let (script_instance, amm, pool_id, wallet, deadline) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired = 40000;
     let amount_1_desired = 10000;
     let expected_liquidity = 20000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
-    let added_liquidity = script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -79,29 +90,34 @@ async fn adds_liquidity_to_make_a_more_valuable() {
 
 #[tokio::test]
 async fn adds_liquidity_to_make_b_more_valuable() {
-    let (script_instance, amm, pool_id, wallet, deadline) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired = 10000;
     let amount_1_desired = 40000;
     let expected_liquidity = 20000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
-    let added_liquidity = script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -115,27 +131,31 @@ async fn adds_liquidity_to_make_b_more_valuable() {
 
 #[tokio::test]
 async fn adds_further_liquidity_without_extra_deposit_when_a_is_more_valuable() {
-    let (script_instance, amm, pool_id, wallet, deadline) = setup().await;
+    let env = setup().await;
     let amount_0_desired = 10000;
     let amount_1_desired = 10000;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
     // adds initial liquidity
-    script_instance
+    env.scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -146,25 +166,30 @@ async fn adds_further_liquidity_without_extra_deposit_when_a_is_more_valuable()
     let amount_0_desired = 10000;
     let amount_1_desired = 40000;
 
-    println!("{:?}", wallet.get_balances().await.unwrap());
+    println!("{:?}", env.wallet.get_balances().await.unwrap());
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
-    let added_liquidity = script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
@@ -174,7 +199,45 @@ async fn adds_further_liquidity_without_extra_deposit_when_a_is_more_valuable()
         .value;
 
     assert_eq!(added_liquidity.amount, 10000);
-    let pool_meta = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+    let pool_meta = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
     assert_eq!(pool_meta.reserve_0, 20000);
     assert_eq!(pool_meta.reserve_1, 20000);
 }
+
+#[tokio::test]
+async fn adds_liquidity_with_deadline_at_execution_height() {
+    let env = setup().await;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.pools[0].0, 10000), (env.pools[0].1, 10000)],
+    )
+    .await;
+    let deadline = execution_height(&env.provider).await;
+
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
+        .main(
+            env.pools[0],
+            10000,
+            10000,
+            0,
+            0,
+            env.wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(added_liquidity.amount, 10000 - MINIMUM_LIQUIDITY);
+}
@@ -0,0 +1,55 @@
+script;
+
+use interfaces::data_structures::PoolId;
+use math::pool_math::get_amounts_out;
+use utils::{blockchain_utils::check_deadline, swap_utils::swap_exact_input_along_route};
+
+configurable {
+    AMM_CONTRACT_ID: ContractId = ContractId::zero(),
+}
+
+/// Exact input swap executed as a part of the batch
+pub struct SwapExactInputOrder {
+    pub amount_in: u64,
+    pub asset_in: AssetId,
+    pub amount_out_min: u64,
+    pub pools: Vec<PoolId>,
+    pub recipient: Identity,
+}
+
+/// Executes the orders one after another, reverting the whole batch if any of them fails.
+/// Returns the amounts of every hop for each order.
+fn main(orders: Vec<SwapExactInputOrder>, deadline: u32) -> Vec<Vec<(u64, AssetId)>> {
+    check_deadline(deadline);
+    require(orders.len() >= 1, "Empty batch");
+
+    let mut results: Vec<Vec<(u64, AssetId)>> = Vec::new();
+    let mut i = 0;
+    while i < orders.len() {
+        results.push(swap_exact_input(orders.get(i).unwrap()));
+        i += 1;
+    }
+
+    results
+}
+
+fn swap_exact_input(order: SwapExactInputOrder) -> Vec<(u64, AssetId)> {
+    let SwapExactInputOrder {
+        amount_in,
+        asset_in,
+        amount_out_min,
+        pools,
+        recipient,
+    } = order;
+
+    let amounts_out = get_amounts_out(AMM_CONTRACT_ID, amount_in, asset_in, pools);
+    let last_amount_out = amounts_out.get(amounts_out.len() - 1).unwrap();
+    require(
+        last_amount_out.0 >= amount_out_min,
+        "Insufficient output amount",
+    );
+
+    swap_exact_input_along_route(AMM_CONTRACT_ID, amounts_out, pools, recipient);
+
+    amounts_out
+}
@@ -0,0 +1,131 @@
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::Identity;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::hook::{calls, last_call};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::SwapExactInputOrder;
+use test_harness::types::PoolId;
+
+async fn setup_with_hook() -> TestEnvironment {
+    TestEnvironment::builder()
+        .hook()
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::BatchSwap])
+        .build()
+        .await
+}
+
+fn orders(pool_id: PoolId, recipient: Identity) -> Vec<SwapExactInputOrder> {
+    vec![
+        SwapExactInputOrder {
+            amount_in: 1_000,
+            asset_in: pool_id.0,
+            amount_out_min: 0,
+            pools: vec![pool_id],
+            recipient,
+        },
+        SwapExactInputOrder {
+            amount_in: 1_000,
+            asset_in: pool_id.1,
+            amount_out_min: 0,
+            pools: vec![pool_id],
+            recipient,
+        },
+    ]
+}
+
+#[tokio::test]
+async fn calls_hook_for_every_order() {
+    let env = setup_with_hook().await;
+    let hook = env.hook.as_ref().unwrap();
+    let recipient: Identity = env.wallet.address().into();
+    let calls_before = calls(hook).await.value;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, 1_000), (env.tokens[1].id, 1_000)],
+    )
+    .await;
+    let results = env
+        .scripts
+        .batch_swap()
+        .main(orders(env.pools[0], recipient), env.deadline)
+        .with_contracts(&env.contracts())
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(results.len(), 2);
+    let (amount_in, asset_in) = results[1][0];
+    let (amount_out, asset_out) = results[1][1];
+    assert_eq!(
+        (amount_in, asset_in, asset_out),
+        (1_000, env.tokens[1].id, env.tokens[0].id)
+    );
+    assert_eq!(calls(hook).await.value, calls_before + 2);
+    let call = last_call(hook).await.value.unwrap();
+    assert_eq!(call.pool_id, env.pools[0]);
+    assert_eq!(call.to, recipient);
+    assert_eq!((call.asset_0_in, call.asset_1_in), (0, 1_000));
+    assert_eq!((call.asset_0_out, call.asset_1_out), (amount_out, 0));
+}
+
+#[tokio::test]
+async fn estimates_hook_dependency() {
+    let env = setup_with_hook().await;
+    let hook = env.hook.as_ref().unwrap();
+    let calls_before = calls(hook).await.value;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, 1_000), (env.tokens[1].id, 1_000)],
+    )
+    .await;
+    env.scripts
+        .batch_swap()
+        .main(
+            orders(env.pools[0], env.wallet.address().into()),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .determine_missing_contracts(None)
+        .await
+        .unwrap()
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(calls(hook).await.value, calls_before + 2);
+}
+
+#[tokio::test]
+#[should_panic(expected = "ContractNotInInputs")]
+async fn panics_without_hook_contract() {
+    let env = setup_with_hook().await;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, 1_000), (env.tokens[1].id, 1_000)],
+    )
+    .await;
+    env.scripts
+        .batch_swap()
+        .main(
+            orders(env.pools[0], env.wallet.address().into()),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+}
@@ -0,0 +1,91 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::SwapExactInputOrder;
+use test_harness::math::{apply_amounts, get_amounts_out};
+use test_harness::matrix::{route, FEES};
+use test_harness::property::{deploy_route, RouteFixture};
+use test_harness::route_matrix;
+use test_harness::utils::common::pools_metadata;
+
+/// Batches a swap along the route with a swap back along it, the second one trading against
+/// the reserves left by the first
+async fn batch_matches_reference_model(decimals: (u8, u8), is_stable: bool, hops: usize) {
+    let route = route(decimals, is_stable, hops);
+    let RouteFixture {
+        amm,
+        wallet,
+        assets,
+        pools,
+        scripts,
+        deadline,
+    } = deploy_route(&route, FEES, &[PeripheryScript::BatchSwap]).await;
+    let script = scripts.batch_swap();
+    let (asset_in, asset_out) = (assets[0], *assets.last().unwrap());
+    let pools_back: Vec<_> = pools.iter().rev().copied().collect();
+
+    let amount_in = route.pools[0].reserve_in / 1_000;
+    let amount_back = route.pools.last().unwrap().reserve_out / 1_000;
+    let amm_fees = fees(&amm.instance).await.value;
+    let mut pools_metadata = pools_metadata(&amm.instance, &pools).await;
+    let expected_forward = get_amounts_out(amm_fees, &pools_metadata, amount_in, asset_in);
+    apply_amounts(amm_fees, &mut pools_metadata, &expected_forward);
+    pools_metadata.reverse();
+    let expected_back = get_amounts_out(amm_fees, &pools_metadata, amount_back, asset_out);
+    assert!(expected_forward
+        .iter()
+        .chain(&expected_back)
+        .all(|(amount, _)| *amount > 0));
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &wallet,
+        &vec![(asset_in, amount_in), (asset_out, amount_back)],
+    )
+    .await;
+    let balance_in_before = wallet.get_asset_balance(&asset_in).await.unwrap();
+    let balance_out_before = wallet.get_asset_balance(&asset_out).await.unwrap();
+
+    let results = script
+        .main(
+            vec![
+                SwapExactInputOrder {
+                    amount_in,
+                    asset_in,
+                    amount_out_min: 0,
+                    pools: pools.clone(),
+                    recipient: wallet.address().into(),
+                },
+                SwapExactInputOrder {
+                    amount_in: amount_back,
+                    asset_in: asset_out,
+                    amount_out_min: 0,
+                    pools: pools_back,
+                    recipient: wallet.address().into(),
+                },
+            ],
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let amount_out = expected_forward.last().unwrap().0;
+    let amount_back_out = expected_back.last().unwrap().0;
+    assert_eq!(results, vec![expected_forward, expected_back]);
+    assert_eq!(
+        wallet.get_asset_balance(&asset_in).await.unwrap(),
+        balance_in_before - amount_in + amount_back_out
+    );
+    assert_eq!(
+        wallet.get_asset_balance(&asset_out).await.unwrap(),
+        balance_out_before - amount_back + amount_out
+    );
+}
+
+route_matrix!(batch_matches_reference_model);
@@ -0,0 +1,199 @@
+use crate::utils::setup;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::SwapExactInputOrder;
+use test_harness::setup::common::{execution_height, produce_blocks_until};
+
+#[tokio::test]
+#[should_panic(expected = "Empty batch")]
+async fn panics_on_empty_batch() {
+    let env = setup().await;
+
+    env.scripts
+        .batch_swap()
+        .main(vec![], env.deadline)
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Insufficient output amount")]
+async fn panics_when_an_order_output_is_insufficient() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+    env.add_liquidity(env.pools[1], 1_000_000, 1_000_000).await;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, 1_000), (env.tokens[2].id, 1_000)],
+    )
+    .await;
+
+    env.scripts
+        .batch_swap()
+        .main(
+            vec![
+                SwapExactInputOrder {
+                    amount_in: 1_000,
+                    asset_in: env.tokens[0].id,
+                    amount_out_min: 0,
+                    pools: vec![env.pools[0]],
+                    recipient: env.wallet.address().into(),
+                },
+                SwapExactInputOrder {
+                    amount_in: 1_000,
+                    asset_in: env.tokens[2].id,
+                    amount_out_min: 997,
+                    pools: vec![env.pools[1]],
+                    recipient: env.wallet.address().into(),
+                },
+            ],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn reverts_all_orders_when_an_order_fails() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+    env.add_liquidity(env.pools[1], 1_000_000, 1_000_000).await;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, 1_000), (env.tokens[2].id, 1_000)],
+    )
+    .await;
+
+    let wallet_token_0_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[0].id)
+        .await
+        .unwrap();
+    let wallet_token_1_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[1].id)
+        .await
+        .unwrap();
+    let pool_metadata_0_before = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+
+    let result = env
+        .scripts
+        .batch_swap()
+        .main(
+            vec![
+                SwapExactInputOrder {
+                    amount_in: 1_000,
+                    asset_in: env.tokens[0].id,
+                    amount_out_min: 0,
+                    pools: vec![env.pools[0]],
+                    recipient: env.wallet.address().into(),
+                },
+                SwapExactInputOrder {
+                    amount_in: 1_000,
+                    asset_in: env.tokens[2].id,
+                    amount_out_min: 997,
+                    pools: vec![env.pools[1]],
+                    recipient: env.wallet.address().into(),
+                },
+            ],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await;
+    assert!(result.is_err());
+
+    let pool_metadata_0_after = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[0].id)
+            .await
+            .unwrap(),
+        wallet_token_0_before
+    );
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[1].id)
+            .await
+            .unwrap(),
+        wallet_token_1_before
+    );
+    assert_eq!(
+        pool_metadata_0_after.reserve_0,
+        pool_metadata_0_before.reserve_0
+    );
+    assert_eq!(
+        pool_metadata_0_after.reserve_1,
+        pool_metadata_0_before.reserve_1
+    );
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_on_deadline_one_block_before_execution_height() {
+    let env = setup().await;
+    let deadline = execution_height(&env.provider).await - 1;
+
+    env.scripts
+        .batch_swap()
+        .main(
+            vec![SwapExactInputOrder {
+                amount_in: 1_000,
+                asset_in: env.tokens[0].id,
+                amount_out_min: 0,
+                pools: vec![env.pools[0]],
+                recipient: env.wallet.address().into(),
+            }],
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_once_blocks_are_produced_past_deadline() {
+    let env = setup().await;
+    produce_blocks_until(&env.provider, env.deadline).await;
+
+    env.scripts
+        .batch_swap()
+        .main(
+            vec![SwapExactInputOrder {
+                amount_in: 1_000,
+                asset_in: env.tokens[0].id,
+                amount_out_min: 0,
+                pools: vec![env.pools[0]],
+                recipient: env.wallet.address().into(),
+            }],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
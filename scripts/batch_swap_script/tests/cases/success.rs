@@ -0,0 +1,206 @@
+use crate::utils::setup;
+use fuels::accounts::{wallet::WalletUnlocked, ViewOnlyAccount};
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::SwapExactInputOrder;
+use test_harness::setup::common::execution_height;
+
+#[tokio::test]
+async fn executes_independent_swaps_in_one_transaction() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+    env.add_liquidity(env.pools[1], 1_000_000, 1_000_000).await;
+
+    let other_recipient = WalletUnlocked::new_random(None);
+    let amount_in = 1_000;
+    let amount_out_expected = 996;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, amount_in), (env.tokens[2].id, amount_in)],
+    )
+    .await;
+
+    let wallet_token_0_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[0].id)
+        .await
+        .unwrap();
+    let wallet_token_1_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[1].id)
+        .await
+        .unwrap();
+    let results = env
+        .scripts
+        .batch_swap()
+        .main(
+            vec![
+                SwapExactInputOrder {
+                    amount_in,
+                    asset_in: env.tokens[0].id,
+                    amount_out_min: amount_out_expected,
+                    pools: vec![env.pools[0]],
+                    recipient: env.wallet.address().into(),
+                },
+                SwapExactInputOrder {
+                    amount_in,
+                    asset_in: env.tokens[2].id,
+                    amount_out_min: amount_out_expected,
+                    pools: vec![env.pools[1]],
+                    recipient: other_recipient.address().into(),
+                },
+            ],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        results,
+        vec![
+            vec![
+                (amount_in, env.tokens[0].id),
+                (amount_out_expected, env.tokens[1].id)
+            ],
+            vec![
+                (amount_in, env.tokens[2].id),
+                (amount_out_expected, env.tokens[1].id)
+            ],
+        ]
+    );
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[0].id)
+            .await
+            .unwrap(),
+        wallet_token_0_before - amount_in
+    );
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[1].id)
+            .await
+            .unwrap(),
+        wallet_token_1_before + amount_out_expected
+    );
+    assert_eq!(
+        env.provider
+            .get_asset_balance(other_recipient.address(), env.tokens[1].id)
+            .await
+            .unwrap(),
+        amount_out_expected
+    );
+}
+
+#[tokio::test]
+async fn executes_orders_sequentially_against_updated_reserves() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+    env.add_liquidity(env.pools[1], 1_000_000, 1_000_000).await;
+
+    let amount_in = 1_000;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 2 * amount_in)]).await;
+
+    let pool_metadata_0_before = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let results = env
+        .scripts
+        .batch_swap()
+        .main(
+            vec![
+                SwapExactInputOrder {
+                    amount_in,
+                    asset_in: env.tokens[0].id,
+                    amount_out_min: 0,
+                    pools: vec![env.pools[0]],
+                    recipient: env.wallet.address().into(),
+                },
+                SwapExactInputOrder {
+                    amount_in,
+                    asset_in: env.tokens[0].id,
+                    amount_out_min: 0,
+                    pools: vec![env.pools[0], env.pools[1]],
+                    recipient: env.wallet.address().into(),
+                },
+            ],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+    let pool_metadata_0_after = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+
+    let (first_amount_out, first_asset_out) = results[0][1];
+    let (second_hop_amount_out, second_hop_asset_out) = results[1][1];
+    assert_eq!(first_asset_out, env.tokens[1].id);
+    assert_eq!(second_hop_asset_out, env.tokens[1].id);
+    assert_eq!(results[1][2].1, env.tokens[2].id);
+    // the second order trades against the reserves moved by the first one
+    assert!(second_hop_amount_out < first_amount_out);
+    assert_eq!(
+        pool_metadata_0_after.reserve_0,
+        pool_metadata_0_before.reserve_0 + 2 * amount_in
+    );
+    assert_eq!(
+        pool_metadata_0_after.reserve_1,
+        pool_metadata_0_before.reserve_1 - first_amount_out - second_hop_amount_out
+    );
+}
+
+#[tokio::test]
+async fn executes_batch_with_deadline_at_execution_height() {
+    let env = setup().await;
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    let deadline = execution_height(&env.provider).await;
+
+    let results = env
+        .scripts
+        .batch_swap()
+        .main(
+            vec![SwapExactInputOrder {
+                amount_in: 1_000,
+                asset_in: env.tokens[0].id,
+                amount_out_min: 0,
+                pools: vec![env.pools[0]],
+                recipient: env.wallet.address().into(),
+            }],
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        results,
+        vec![vec![(1_000, env.tokens[0].id), (996, env.tokens[1].id)]]
+    );
+}
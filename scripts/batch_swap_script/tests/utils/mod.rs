@@ -0,0 +1,11 @@
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+
+pub async fn setup() -> TestEnvironment {
+    TestEnvironment::builder()
+        .tokens(&[9, 9, 9])
+        .pool(PoolConfig::volatile(0, 1))
+        .pool(PoolConfig::volatile(1, 2))
+        .scripts(&[PeripheryScript::AddLiquidity, PeripheryScript::BatchSwap])
+        .build()
+        .await
+}
@@ -0,0 +1,79 @@
+use crate::utils::ordered_sub_ids;
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::environment::{PeripheryScript, TestEnvironment};
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::get_mint_liquidity;
+use test_harness::matrix::{route, FEES};
+use test_harness::pool_matrix;
+use test_harness::utils::common::{get_lp_asset_id, MINIMUM_LIQUIDITY};
+
+/// Creates a pool holding the reserves of the matrix pool, which the first deposit takes as
+/// they are
+async fn creates_pool_matching_reference_model(decimals: (u8, u8), is_stable: bool) {
+    let params = route(decimals, is_stable, 1).pools.remove(0);
+    let env = TestEnvironment::builder()
+        .tokens(&[decimals.0, decimals.1])
+        .mint_amount(params.reserve_in.max(params.reserve_out))
+        .protocol_fees(FEES.2, FEES.3)
+        .scripts(&[PeripheryScript::CreatePoolAndAddLiquidity])
+        .build()
+        .await;
+    let (sub_id_0, sub_id_1) = ordered_sub_ids(&env);
+    let (pool_id, amounts) = if env.tokens[0].id < env.tokens[1].id {
+        (
+            (env.tokens[0].id, env.tokens[1].id, is_stable),
+            (params.reserve_in, params.reserve_out),
+        )
+    } else {
+        (
+            (env.tokens[1].id, env.tokens[0].id, is_stable),
+            (params.reserve_out, params.reserve_in),
+        )
+    };
+    let expected_liquidity = get_mint_liquidity(amounts.0, amounts.1, 0, 0, 0);
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(pool_id.0, amounts.0), (pool_id.1, amounts.1)],
+    )
+    .await;
+
+    let added_liquidity = env
+        .scripts
+        .create_pool_and_add_liquidity()
+        .main(
+            env.token_contract_id,
+            sub_id_0,
+            env.token_contract_id,
+            sub_id_1,
+            is_stable,
+            amounts.0,
+            amounts.1,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance, &env.token_contract])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let pool = pool_metadata(&env.amm.instance, pool_id)
+        .await
+        .value
+        .unwrap();
+
+    assert_eq!(added_liquidity.id, get_lp_asset_id(env.amm.id, &pool_id));
+    assert_eq!(added_liquidity.amount, expected_liquidity);
+    assert_eq!((pool.reserve_0, pool.reserve_1), amounts);
+    assert_eq!(
+        pool.liquidity.amount,
+        expected_liquidity + MINIMUM_LIQUIDITY
+    );
+}
+
+pool_matrix!(creates_pool_matching_reference_model);
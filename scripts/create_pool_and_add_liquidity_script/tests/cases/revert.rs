@@ -0,0 +1,54 @@
+use crate::utils::{ordered_sub_ids, setup};
+use test_harness::setup::common::{execution_height, produce_blocks_until};
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_on_deadline_one_block_before_execution_height() {
+    let env = setup().await;
+    let (sub_id_0, sub_id_1) = ordered_sub_ids(&env);
+    let deadline = execution_height(&env.provider).await - 1;
+
+    env.scripts
+        .create_pool_and_add_liquidity()
+        .main(
+            env.token_contract_id,
+            sub_id_0,
+            env.token_contract_id,
+            sub_id_1,
+            false,
+            10_000,
+            10_000,
+            env.wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance, &env.token_contract])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_once_blocks_are_produced_past_deadline() {
+    let env = setup().await;
+    let (sub_id_0, sub_id_1) = ordered_sub_ids(&env);
+    produce_blocks_until(&env.provider, env.deadline).await;
+
+    env.scripts
+        .create_pool_and_add_liquidity()
+        .main(
+            env.token_contract_id,
+            sub_id_0,
+            env.token_contract_id,
+            sub_id_1,
+            false,
+            10_000,
+            10_000,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance, &env.token_contract])
+        .call()
+        .await
+        .unwrap();
+}
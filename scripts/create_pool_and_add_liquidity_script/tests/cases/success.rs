@@ -0,0 +1,55 @@
+use crate::utils::{ordered_sub_ids, setup};
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::setup::common::execution_height;
+use test_harness::utils::common::{get_lp_asset_id, MINIMUM_LIQUIDITY};
+
+#[tokio::test]
+async fn creates_pool_and_adds_liquidity_with_deadline_at_execution_height() {
+    let env = setup().await;
+    let (sub_id_0, sub_id_1) = ordered_sub_ids(&env);
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, 10_000), (env.tokens[1].id, 10_000)],
+    )
+    .await;
+    let deadline = execution_height(&env.provider).await;
+
+    let added_liquidity = env
+        .scripts
+        .create_pool_and_add_liquidity()
+        .main(
+            env.token_contract_id,
+            sub_id_0,
+            env.token_contract_id,
+            sub_id_1,
+            false,
+            10_000,
+            10_000,
+            env.wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance, &env.token_contract])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let pool_id = if env.tokens[0].id < env.tokens[1].id {
+        (env.tokens[0].id, env.tokens[1].id, false)
+    } else {
+        (env.tokens[1].id, env.tokens[0].id, false)
+    };
+    assert_eq!(added_liquidity.id, get_lp_asset_id(env.amm.id, &pool_id));
+    assert_eq!(added_liquidity.amount, 10_000 - MINIMUM_LIQUIDITY);
+    let pool = pool_metadata(&env.amm.instance, pool_id)
+        .await
+        .value
+        .unwrap();
+    assert_eq!((pool.reserve_0, pool.reserve_1), (10_000, 10_000));
+}
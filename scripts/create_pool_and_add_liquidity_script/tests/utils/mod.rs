@@ -0,0 +1,18 @@
+use fuels::types::Bits256;
+use test_harness::environment::{PeripheryScript, TestEnvironment};
+use test_harness::utils::common::order_sub_ids;
+
+pub async fn setup() -> TestEnvironment {
+    TestEnvironment::builder()
+        .scripts(&[PeripheryScript::CreatePoolAndAddLiquidity])
+        .build()
+        .await
+}
+
+/// Sub ids of the two tokens of the environment, in the order the AMM expects them
+pub fn ordered_sub_ids(env: &TestEnvironment) -> (Bits256, Bits256) {
+    order_sub_ids(
+        (env.tokens[0].id, env.tokens[1].id),
+        (env.tokens[0].sub_id, env.tokens[1].sub_id),
+    )
+}
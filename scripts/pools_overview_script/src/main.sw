@@ -0,0 +1,48 @@
+script;
+
+use interfaces::{data_structures::{PoolId, PoolMetadata}, mira_amm::MiraAMM};
+use utils::blockchain_utils::get_lp_asset;
+
+configurable {
+    AMM_CONTRACT_ID: ContractId = ContractId::zero(),
+}
+
+pub struct PoolOverview {
+    pub pool_id: PoolId,
+    pub lp_asset: AssetId,
+    /// `None` if the pool doesn't exist
+    pub metadata: Option<PoolMetadata>,
+}
+
+pub struct AmmOverview {
+    /// (lp_fee_volatile, lp_fee_stable, protocol_fee_volatile, protocol_fee_stable)
+    pub fees: (u64, u64, u64, u64),
+    pub hook: Option<ContractId>,
+    /// In the order of the requested pool ids
+    pub pools: Vec<PoolOverview>,
+}
+
+/// Read-only: collects the AMM state needed to quote the given pools in a single call.
+/// Pools which don't exist are returned without metadata rather than reverting the call.
+fn main(pool_ids: Vec<PoolId>) -> AmmOverview {
+    let amm = abi(MiraAMM, AMM_CONTRACT_ID.into());
+
+    let mut pools: Vec<PoolOverview> = Vec::new();
+    let mut i = 0;
+    while i < pool_ids.len() {
+        let pool_id = pool_ids.get(i).unwrap();
+        let (_, lp_asset) = get_lp_asset(AMM_CONTRACT_ID, pool_id);
+        pools.push(PoolOverview {
+            pool_id,
+            lp_asset,
+            metadata: amm.pool_metadata(pool_id),
+        });
+        i += 1;
+    }
+
+    AmmOverview {
+        fees: amm.fees(),
+        hook: amm.hook(),
+        pools,
+    }
+}
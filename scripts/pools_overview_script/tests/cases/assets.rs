@@ -0,0 +1,95 @@
+use fuels::types::AssetId;
+use test_harness::assets::{AssetOverride, AssetRegistry};
+use test_harness::discovery::PoolRegistry;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::mock::add_token;
+use test_harness::utils::common::get_lp_asset_id;
+
+#[tokio::test]
+async fn resolves_token_and_lp_metadata_with_overrides() {
+    let env = TestEnvironment::builder()
+        .tokens(&[9, 6])
+        .pool(PoolConfig::stable(0, 1).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::PoolsOverview])
+        .build()
+        .await;
+    let (token_0, token_1) = (*env.token(env.pools[0].0), *env.token(env.pools[0].1));
+    let lp_asset = get_lp_asset_id(env.amm.id, &env.pools[0]);
+
+    let mut assets = AssetRegistry::new(env.amm.id)
+        .with_contracts(&[env.token_contract_id])
+        .with_overrides(vec![AssetOverride {
+            asset_id: token_1.id,
+            name: None,
+            symbol: Some("USDC".to_string()),
+            decimals: None,
+        }]);
+    assets.add_pools(&env.pools);
+
+    let metadata_0 = assets.resolve(&env.wallet, token_0.id).await.unwrap();
+    assert_eq!(metadata_0.contract_id, Some(env.token_contract_id));
+    assert_eq!(metadata_0.decimals, token_0.decimals);
+    assert!(metadata_0.name.unwrap().starts_with("TOKEN_"));
+    let symbol_0 = metadata_0.symbol.unwrap();
+    assert!(symbol_0.starts_with("TK"));
+
+    // on-chain decimals and name, overridden symbol
+    let metadata_1 = assets.resolve(&env.wallet, token_1.id).await.unwrap();
+    assert_eq!(metadata_1.decimals, token_1.decimals);
+    assert!(metadata_1.name.unwrap().starts_with("TOKEN_"));
+    assert_eq!(metadata_1.symbol.as_deref(), Some("USDC"));
+
+    // LP assets resolve back to their pool and are described by the AMM
+    assert_eq!(assets.pool_of_lp_asset(lp_asset), Some(env.pools[0]));
+    let lp_metadata = assets.resolve(&env.wallet, lp_asset).await.unwrap();
+    assert_eq!(lp_metadata.contract_id, Some(env.amm.id));
+    assert!(lp_metadata.symbol.is_some());
+    assert_eq!(assets.label(lp_asset), format!("{symbol_0}/USDC stable LP"));
+
+    // unknown assets are cached as misses
+    let unknown = AssetId::new([1; 32]);
+    assert_eq!(assets.resolve(&env.wallet, unknown).await, None);
+    assert_eq!(assets.label(unknown), unknown.to_string());
+    assert_eq!(assets.get(token_0.id).unwrap().decimals, token_0.decimals);
+}
+
+#[tokio::test]
+async fn seeds_from_pool_discovery() {
+    let env = TestEnvironment::builder()
+        .tokens(&[9, 6, 9])
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 500_000))
+        .pool(PoolConfig::volatile(1, 2).with_liquidity(500_000, 1_000_000))
+        .scripts(&[PeripheryScript::PoolsOverview])
+        .build()
+        .await;
+    let height = env.provider.latest_block_height().await.unwrap();
+    let pools = PoolRegistry::discover(&env.amm.instance, 0, height).await;
+
+    let mut assets = AssetRegistry::new(env.amm.id);
+    assets.add_discovered(&pools);
+
+    // no call is needed to label a discovered route
+    let route = &pools.routes(env.tokens[0].id, env.tokens[2].id, 2)[0];
+    let symbols: Vec<String> = [env.tokens[0].id, env.tokens[1].id, env.tokens[2].id]
+        .iter()
+        .map(|asset| assets.get(*asset).unwrap().symbol.unwrap())
+        .collect();
+    assert_eq!(
+        assets.route_label(env.tokens[0].id, route),
+        symbols.join(" -> ")
+    );
+
+    // and the token contract of the discovered pools is asked for new assets
+    let new_token = add_token(
+        &env.token_contract,
+        "TOKEN_NEW".to_string(),
+        "NEW".to_string(),
+        8,
+    )
+    .await
+    .value;
+    let metadata = assets.resolve(&env.wallet, new_token).await.unwrap();
+    assert_eq!(metadata.contract_id, Some(env.token_contract_id));
+    assert_eq!(metadata.symbol.as_deref(), Some("NEW"));
+    assert_eq!(metadata.decimals, 8);
+}
@@ -0,0 +1,47 @@
+use test_harness::discovery::PoolRegistry;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::utils::common::{get_lp_asset_id, pools_metadata};
+
+#[tokio::test]
+async fn discovers_pools_and_their_token_metadata() {
+    let env = TestEnvironment::builder()
+        .tokens(&[9, 6, 9])
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 500_000))
+        .pool(PoolConfig::stable(1, 2).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::PoolsOverview])
+        .build()
+        .await;
+    let height = env.provider.latest_block_height().await.unwrap();
+    let decimals: Vec<u8> = env.tokens.iter().map(|token| token.decimals).collect();
+    assert_eq!(decimals, vec![9, 6, 9]);
+
+    let registry = PoolRegistry::discover(&env.amm.instance, 0, height).await;
+
+    assert_eq!(registry.pool_ids(), env.pools);
+    for pool in registry.pools() {
+        let (asset_0, asset_1, _) = pool.pool_id;
+        assert!(pool.created_at <= height);
+        for (token, asset) in [(&pool.token_0, asset_0), (&pool.token_1, asset_1)] {
+            assert_eq!(token.asset_id, asset);
+            assert_eq!(token.contract_id, Some(env.token_contract_id));
+            assert_eq!(token.decimals, env.token(asset).decimals);
+            assert!(token.name.as_ref().unwrap().starts_with("TOKEN_"));
+            assert!(token.symbol.as_ref().unwrap().starts_with("TK"));
+        }
+        let lp_token = pool.lp_token.as_ref().unwrap();
+        assert_eq!(
+            lp_token.asset_id,
+            get_lp_asset_id(env.amm.id, &pool.pool_id)
+        );
+        assert_eq!(lp_token.contract_id, Some(env.amm.id));
+    }
+
+    // the only route between the outer tokens goes through both pools
+    let (asset_in, asset_out) = (env.tokens[0].id, env.tokens[2].id);
+    let routes = registry.routes(asset_in, asset_out, 3);
+    assert_eq!(routes, vec![env.pools.clone()]);
+
+    // and can be quoted like any other
+    let metadata = pools_metadata(&env.amm.instance, &routes[0]).await;
+    assert!(metadata.iter().all(|(_, metadata)| metadata.reserve_0 > 0));
+}
@@ -0,0 +1,98 @@
+use crate::utils::setup;
+use test_harness::interface::amm::{fees, hook, pool_metadata, set_hook, set_protocol_fees};
+use test_harness::interface::scripts::pools_overview;
+use test_harness::interface::PoolOverview;
+use test_harness::math::initial_liquidity;
+use test_harness::utils::common::get_lp_asset_id;
+
+#[tokio::test]
+async fn returns_pool_metadata_fees_and_hook() {
+    let env = setup().await;
+    // 1_000_000 of the first token were deposited for 500_000 of the second one
+    let (reserve_0, reserve_1) = if env.pools[0].0 == env.tokens[0].id {
+        (1_000_000, 500_000)
+    } else {
+        (500_000, 1_000_000)
+    };
+
+    set_protocol_fees(&env.amm.instance, 3, 1).await;
+    set_hook(&env.amm.instance, Some(env.token_contract_id)).await;
+
+    let overview = pools_overview(
+        env.scripts.pools_overview(),
+        &env.amm.instance,
+        vec![env.pools[0]],
+    )
+    .await
+    .value;
+
+    assert_eq!(overview.fees, fees(&env.amm.instance).await.value);
+    assert_eq!(overview.hook, Some(env.token_contract_id));
+    assert_eq!(
+        overview.pools,
+        vec![PoolOverview {
+            pool_id: env.pools[0],
+            lp_asset: get_lp_asset_id(env.amm.id, &env.pools[0]),
+            metadata: pool_metadata(&env.amm.instance, env.pools[0]).await.value,
+        }]
+    );
+
+    let metadata = overview.pools[0].metadata.clone().unwrap();
+    assert_eq!(metadata.reserve_0, reserve_0);
+    assert_eq!(metadata.reserve_1, reserve_1);
+    let decimals = |asset| {
+        env.tokens
+            .iter()
+            .find(|token| token.id == asset)
+            .unwrap()
+            .decimals
+    };
+    assert_eq!(metadata.decimals_0, decimals(env.pools[0].0));
+    assert_eq!(metadata.decimals_1, decimals(env.pools[0].1));
+    assert_eq!(
+        metadata.liquidity.amount,
+        initial_liquidity(1_000_000, 500_000)
+    );
+}
+
+#[tokio::test]
+async fn returns_none_for_missing_pools() {
+    let env = setup().await;
+
+    let stable_pool_id = (env.pools[0].0, env.pools[0].1, true);
+    let reversed_pool_id = (env.pools[0].1, env.pools[0].0, false);
+
+    let overview = pools_overview(
+        env.scripts.pools_overview(),
+        &env.amm.instance,
+        vec![stable_pool_id, env.pools[0], reversed_pool_id],
+    )
+    .await
+    .value;
+
+    assert_eq!(overview.hook, hook(&env.amm.instance).await.value);
+    assert_eq!(overview.pools.len(), 3);
+    assert_eq!(overview.pools[0].pool_id, stable_pool_id);
+    assert_eq!(
+        overview.pools[0].lp_asset,
+        get_lp_asset_id(env.amm.id, &stable_pool_id)
+    );
+    assert_eq!(overview.pools[0].metadata, None);
+    assert_eq!(overview.pools[1].pool_id, env.pools[0]);
+    assert!(overview.pools[1].metadata.is_some());
+    assert_eq!(overview.pools[2].pool_id, reversed_pool_id);
+    assert_eq!(overview.pools[2].metadata, None);
+}
+
+#[tokio::test]
+async fn returns_no_pools_when_none_requested() {
+    let env = setup().await;
+
+    let overview = pools_overview(env.scripts.pools_overview(), &env.amm.instance, vec![])
+        .await
+        .value;
+
+    assert_eq!(overview.fees, fees(&env.amm.instance).await.value);
+    assert_eq!(overview.hook, None);
+    assert!(overview.pools.is_empty());
+}
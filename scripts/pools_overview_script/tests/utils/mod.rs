@@ -0,0 +1,12 @@
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+
+/// Seeds a volatile pool between a 9 and a 6 decimals token with a 2:1 price
+pub async fn setup() -> TestEnvironment {
+    TestEnvironment::builder()
+        .tokens(&[9, 6])
+        .mint_amount(1_000_000)
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 500_000))
+        .scripts(&[PeripheryScript::PoolsOverview])
+        .build()
+        .await
+}
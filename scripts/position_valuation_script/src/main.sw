@@ -0,0 +1,55 @@
+script;
+
+use interfaces::{data_structures::PoolId, mira_amm::MiraAMM};
+use math::pool_math::get_burn_amounts;
+use utils::blockchain_utils::get_lp_asset;
+
+configurable {
+    AMM_CONTRACT_ID: ContractId = ContractId::zero(),
+}
+
+/// Pool assets redeemable for an amount of LP asset at the current reserves
+pub struct PositionValue {
+    pub pool_id: PoolId,
+    pub lp_asset: AssetId,
+    pub liquidity: u64,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+/// Read-only: values every (pool id, LP amount) position as if it was burnt
+fn main(positions: Vec<(PoolId, u64)>) -> Vec<PositionValue> {
+    let amm = abi(MiraAMM, AMM_CONTRACT_ID.into());
+
+    let mut values: Vec<PositionValue> = Vec::new();
+    let mut i = 0;
+    while i < positions.len() {
+        let (pool_id, liquidity) = positions.get(i).unwrap();
+        let (_, lp_asset) = get_lp_asset(AMM_CONTRACT_ID, pool_id);
+
+        let pool_meta_opt = amm.pool_metadata(pool_id);
+        require(pool_meta_opt.is_some(), "Pool doesn't exist");
+        let pool_meta = pool_meta_opt.unwrap();
+
+        let (amount_0, amount_1) = get_burn_amounts(
+            liquidity,
+            pool_meta
+                .reserve_0,
+            pool_meta
+                .reserve_1,
+            pool_meta
+                .liquidity
+                .amount,
+        );
+        values.push(PositionValue {
+            pool_id,
+            lp_asset,
+            liquidity,
+            amount_0,
+            amount_1,
+        });
+        i += 1;
+    }
+
+    values
+}
@@ -0,0 +1,47 @@
+use fuels::prelude::ViewOnlyAccount;
+use test_harness::environment::PeripheryScript;
+use test_harness::matrix::{route, FEES};
+use test_harness::property::{deploy_route, RouteFixture};
+use test_harness::route_matrix;
+use test_harness::utils::common::pools_metadata;
+use test_harness::valuation::value_positions;
+
+/// Values a third of the wallet's position in every pool of the route
+async fn valuation_matches_reference_model(decimals: (u8, u8), is_stable: bool, hops: usize) {
+    let RouteFixture {
+        amm,
+        wallet,
+        pools,
+        scripts,
+        ..
+    } = deploy_route(
+        &route(decimals, is_stable, hops),
+        FEES,
+        &[PeripheryScript::PositionValuation],
+    )
+    .await;
+    let script = scripts.position_valuation();
+
+    let snapshots = pools_metadata(&amm.instance, &pools).await;
+    let mut positions = Vec::with_capacity(snapshots.len());
+    for (pool_id, pool) in &snapshots {
+        let lp_balance = wallet.get_asset_balance(&pool.liquidity.id).await.unwrap();
+        positions.push((*pool_id, lp_balance / 3));
+    }
+
+    let values = script
+        .main(positions.clone())
+        .with_contracts(&[&amm.instance])
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let expected = value_positions(amm.id, &snapshots, &positions);
+    assert!(expected
+        .iter()
+        .all(|value| value.amount_0 > 0 && value.amount_1 > 0));
+    assert_eq!(values, expected);
+}
+
+route_matrix!(valuation_matches_reference_model);
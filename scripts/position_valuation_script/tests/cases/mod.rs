@@ -0,0 +1,4 @@
+pub mod matrix;
+pub mod pnl;
+pub mod revert;
+pub mod success;
@@ -0,0 +1,108 @@
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::AssetId;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::pnl::{position_pnl, LpEntry};
+use test_harness::utils::common::MINIMUM_LIQUIDITY;
+
+const DEPOSIT: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn pnl_tracks_script_valuation_and_swap_fees() {
+    let env = TestEnvironment::builder()
+        .mint_amount(2 * DEPOSIT)
+        .pool(PoolConfig::volatile(0, 1))
+        .scripts(&[
+            PeripheryScript::AddLiquidity,
+            PeripheryScript::SwapExactInput,
+            PeripheryScript::PositionValuation,
+        ])
+        .build()
+        .await;
+
+    let added_liquidity = env.add_liquidity(env.pools[0], DEPOSIT, DEPOSIT).await;
+
+    // the first deposit only donates the locked minimum, worth as much of both assets
+    let pool = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let entry = LpEntry::new(
+        env.pools[0],
+        (DEPOSIT, DEPOSIT),
+        added_liquidity.amount,
+        &pool,
+    );
+    let at_entry = position_pnl(&entry, &pool).unwrap();
+    assert_eq!((at_entry.impermanent_loss, at_entry.fees_earned), (0, 0));
+    assert_eq!(at_entry.donated_value, 2 * u128::from(MINIMUM_LIQUIDITY));
+
+    // swaps both ways leave their fees in the pool
+    swap(&env, env.tokens[0].id, DEPOSIT / 10).await;
+    swap(&env, env.tokens[1].id, DEPOSIT / 10).await;
+
+    let pool = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let pnl = position_pnl(&entry, &pool).unwrap();
+    let values = env
+        .scripts
+        .position_valuation()
+        .main(vec![(env.pools[0], entry.liquidity)])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        (pnl.amount_0, pnl.amount_1),
+        (values[0].amount_0, values[0].amount_1)
+    );
+    assert!(pnl.fees_earned > 0);
+    assert_eq!(
+        pnl.pnl,
+        pnl.impermanent_loss + pnl.fees_earned as i128 - pnl.donated_value as i128
+    );
+
+    // a swap one way moves the price away from the entry, by about 2.25x for half the reserves
+    swap(&env, env.tokens[0].id, DEPOSIT / 2).await;
+    let pool = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let moved = position_pnl(&entry, &pool).unwrap();
+
+    // about 7.7% of the hold value, its fees not making up for it
+    assert!(moved.impermanent_loss < -(moved.hold_value as i128) / 20);
+    assert!(moved.fees_earned > 0);
+    assert!(moved.pnl < 0);
+    assert_eq!(
+        moved.pnl,
+        moved.impermanent_loss + moved.fees_earned as i128 - moved.donated_value as i128
+    );
+}
+
+async fn swap(env: &TestEnvironment, asset_in: AssetId, amount_in: u64) {
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(asset_in, amount_in)]).await;
+    env.scripts
+        .swap_exact_input()
+        .main(
+            amount_in,
+            asset_in,
+            0,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
@@ -0,0 +1,51 @@
+use crate::utils::setup;
+use test_harness::utils::common::MINIMUM_LIQUIDITY;
+
+#[tokio::test]
+#[should_panic(expected = "Pool doesn't exist")]
+async fn panics_on_missing_pool() {
+    let env = setup().await;
+
+    env.scripts
+        .position_valuation()
+        .main(vec![((env.pools[0].0, env.pools[0].1, true), 1)])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Insufficient liquidity")]
+async fn panics_on_pool_without_liquidity() {
+    let env = setup().await;
+
+    env.scripts
+        .position_valuation()
+        .main(vec![(env.pools[0], 0)])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Liquidity exceeds supply")]
+async fn panics_on_liquidity_exceeding_supply() {
+    let env = setup().await;
+
+    let added_liquidity = env
+        .add_liquidity(env.pools[0], 1_000_000_000, 1_000_000_000)
+        .await;
+
+    env.scripts
+        .position_valuation()
+        .main(vec![(
+            env.pools[0],
+            added_liquidity.amount + MINIMUM_LIQUIDITY + 1,
+        )])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
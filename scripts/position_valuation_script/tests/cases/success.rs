@@ -0,0 +1,141 @@
+use crate::utils::setup;
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::PositionValue;
+use test_harness::utils::common::{get_lp_asset_id, pools_metadata, MINIMUM_LIQUIDITY};
+use test_harness::valuation::value_positions;
+
+#[tokio::test]
+async fn values_position_as_the_assets_it_burns_into() {
+    let env = setup().await;
+
+    let added_liquidity = env
+        .add_liquidity(env.pools[0], 1_000_000_000, 500_000_000)
+        .await;
+    let liquidity_to_value = added_liquidity.amount / 3;
+
+    let values = env
+        .scripts
+        .position_valuation()
+        .main(vec![(env.pools[0], liquidity_to_value)])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let snapshots = pools_metadata(&env.amm.instance, &[env.pools[0]]).await;
+    assert_eq!(
+        values,
+        value_positions(
+            env.amm.id,
+            &snapshots,
+            &[(env.pools[0], liquidity_to_value)]
+        )
+    );
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(added_liquidity.id, liquidity_to_value)],
+    )
+    .await;
+    let removed_liquidity = env
+        .scripts
+        .remove_liquidity()
+        .main(
+            env.pools[0],
+            liquidity_to_value,
+            0,
+            0,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        values,
+        vec![PositionValue {
+            pool_id: env.pools[0],
+            lp_asset: added_liquidity.id,
+            liquidity: liquidity_to_value,
+            amount_0: removed_liquidity.0,
+            amount_1: removed_liquidity.1,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn values_multiple_positions_in_order() {
+    let env = setup().await;
+
+    let added_liquidity = env
+        .add_liquidity(env.pools[0], 1_000_000_000, 1_000_000_000)
+        .await;
+    let total_liquidity = added_liquidity.amount + MINIMUM_LIQUIDITY;
+
+    let values = env
+        .scripts
+        .position_valuation()
+        .main(vec![
+            (env.pools[0], added_liquidity.amount),
+            (env.pools[0], 0),
+            (env.pools[0], total_liquidity),
+        ])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let lp_asset = get_lp_asset_id(env.amm.id, &env.pools[0]);
+    assert_eq!(
+        values,
+        vec![
+            PositionValue {
+                pool_id: env.pools[0],
+                lp_asset,
+                liquidity: added_liquidity.amount,
+                amount_0: 1_000_000_000 - MINIMUM_LIQUIDITY,
+                amount_1: 1_000_000_000 - MINIMUM_LIQUIDITY,
+            },
+            PositionValue {
+                pool_id: env.pools[0],
+                lp_asset,
+                liquidity: 0,
+                amount_0: 0,
+                amount_1: 0,
+            },
+            PositionValue {
+                pool_id: env.pools[0],
+                lp_asset,
+                liquidity: total_liquidity,
+                amount_0: 1_000_000_000,
+                amount_1: 1_000_000_000,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn values_no_positions() {
+    let env = setup().await;
+
+    let values = env
+        .scripts
+        .position_valuation()
+        .main(vec![])
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert!(values.is_empty());
+}
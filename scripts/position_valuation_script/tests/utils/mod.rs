@@ -0,0 +1,13 @@
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+
+pub async fn setup() -> TestEnvironment {
+    TestEnvironment::builder()
+        .pool(PoolConfig::volatile(0, 1))
+        .scripts(&[
+            PeripheryScript::AddLiquidity,
+            PeripheryScript::RemoveLiquidity,
+            PeripheryScript::PositionValuation,
+        ])
+        .build()
+        .await
+}
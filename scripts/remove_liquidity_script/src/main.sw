@@ -1,7 +1,6 @@
 script;
 
 use interfaces::{data_structures::{Asset, PoolId}, mira_amm::MiraAMM};
-use math::pool_math::get_deposit_amounts;
 use utils::blockchain_utils::{check_deadline, get_lp_asset};
 use std::asset::transfer;
 
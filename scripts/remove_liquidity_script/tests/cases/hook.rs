@@ -0,0 +1,54 @@
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::hook::{calls, last_call};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::utils::common::MINIMUM_LIQUIDITY;
+
+#[tokio::test]
+async fn removes_liquidity_with_hook_installed() {
+    let env = TestEnvironment::builder()
+        .hook()
+        .pool(PoolConfig::volatile(0, 1))
+        .scripts(&[
+            PeripheryScript::AddLiquidity,
+            PeripheryScript::RemoveLiquidity,
+        ])
+        .build()
+        .await;
+    let hook = env.hook.as_ref().unwrap();
+    let added_liquidity = env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+    let calls_before = calls(hook).await.value;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(added_liquidity.id, added_liquidity.amount)],
+    )
+    .await;
+    let (amount_0, amount_1) = env
+        .scripts
+        .remove_liquidity()
+        .main(
+            env.pools[0],
+            added_liquidity.amount,
+            0,
+            0,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&env.contracts())
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amount_0, 1_000_000 - MINIMUM_LIQUIDITY);
+    assert_eq!(amount_1, 1_000_000 - MINIMUM_LIQUIDITY);
+    assert_eq!(calls(hook).await.value, calls_before + 1);
+    let call = last_call(hook).await.value.unwrap();
+    assert_eq!(call.pool_id, env.pools[0]);
+    assert_eq!(call.lp_token_in, added_liquidity.amount);
+    assert_eq!((call.asset_0_out, call.asset_1_out), (amount_0, amount_1));
+}
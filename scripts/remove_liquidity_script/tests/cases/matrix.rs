@@ -0,0 +1,64 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::get_burn_amounts;
+use test_harness::matrix::{route, FEES};
+use test_harness::pool_matrix;
+use test_harness::property::{deploy_route, RouteFixture};
+
+async fn withdrawal_matches_reference_model(decimals: (u8, u8), is_stable: bool) {
+    let RouteFixture {
+        amm,
+        wallet,
+        pools,
+        scripts,
+        deadline,
+        ..
+    } = deploy_route(
+        &route(decimals, is_stable, 1),
+        FEES,
+        &[PeripheryScript::RemoveLiquidity],
+    )
+    .await;
+    let script = scripts.remove_liquidity();
+    let pool_id = pools[0];
+
+    let pool_before = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+    let lp_asset = pool_before.liquidity.id;
+    let lp_balance = wallet.get_asset_balance(&lp_asset).await.unwrap();
+    let liquidity = lp_balance / 3;
+    let (amount_0, amount_1) = get_burn_amounts(
+        liquidity,
+        pool_before.reserve_0,
+        pool_before.reserve_1,
+        pool_before.liquidity.amount,
+    );
+    assert!(amount_0 > 0 && amount_1 > 0);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(lp_asset, liquidity)]).await;
+
+    let removed_liquidity = script
+        .main(pool_id, liquidity, 0, 0, wallet.address().into(), deadline)
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let pool_after = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+
+    assert_eq!(removed_liquidity, (amount_0, amount_1));
+    assert_eq!(pool_after.reserve_0, pool_before.reserve_0 - amount_0);
+    assert_eq!(pool_after.reserve_1, pool_before.reserve_1 - amount_1);
+    assert_eq!(
+        wallet.get_asset_balance(&lp_asset).await.unwrap(),
+        lp_balance - liquidity
+    );
+}
+
+pool_matrix!(withdrawal_matches_reference_model);
@@ -0,0 +1,65 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use proptest::prelude::*;
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::get_burn_amounts;
+use test_harness::property::{
+    amm_fees, block_on, deploy_case_route, route_params, RouteFixture, CASES,
+};
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn withdrawal_matches_reference_model(
+        route in route_params(1),
+        route_fees in amm_fees(),
+        share_bps in 1..=10_000u64,
+    ) {
+        block_on(async {
+            let RouteFixture { amm, wallet, pools, scripts, deadline, .. } =
+                deploy_case_route(&route, route_fees, &[PeripheryScript::RemoveLiquidity]).await;
+            let script = scripts.remove_liquidity();
+            let pool_id = pools[0];
+
+            let pool_before = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+            let lp_asset = pool_before.liquidity.id;
+            let lp_balance = wallet.get_asset_balance(&lp_asset).await.unwrap();
+            let liquidity = (lp_balance * share_bps / 10_000).max(1);
+            let (amount_0, amount_1) = get_burn_amounts(
+                liquidity,
+                pool_before.reserve_0,
+                pool_before.reserve_1,
+                pool_before.liquidity.amount,
+            );
+            // the AMM rejects burns redeeming nothing
+            prop_assume!(amount_0 > 0 && amount_1 > 0);
+
+            let (inputs, outputs) =
+                get_transaction_inputs_outputs(&wallet, &vec![(lp_asset, liquidity)]).await;
+
+            let removed_liquidity = script
+                .main(pool_id, liquidity, 0, 0, wallet.address().into(), deadline)
+                .with_contracts(&[&amm.instance])
+                .with_inputs(inputs)
+                .with_outputs(outputs)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+                .call()
+                .await
+                .unwrap()
+                .value;
+
+            let pool_after = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+
+            prop_assert_eq!(removed_liquidity, (amount_0, amount_1));
+            prop_assert_eq!(pool_after.reserve_0, pool_before.reserve_0 - amount_0);
+            prop_assert_eq!(pool_after.reserve_1, pool_before.reserve_1 - amount_1);
+            prop_assert_eq!(
+                wallet.get_asset_balance(&lp_asset).await.unwrap(),
+                lp_balance - liquidity
+            );
+            Ok(())
+        })?;
+    }
+}
@@ -1,42 +1,41 @@
 use crate::utils::setup;
 use fuels::prelude::VariableOutputPolicy;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::setup::common::{execution_height, produce_blocks_until};
 use test_harness::utils::common::MINIMUM_LIQUIDITY;
 
 #[tokio::test]
 #[should_panic(expected = "ZeroInputAmount")]
 async fn panics_on_removing_zero_liquidity() {
-    let (
-        add_liquidity_script_instance,
-        remove_liquidity_script_instance,
-        amm,
-        pool_id,
-        wallet,
-        deadline,
-    ) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired: u64 = 1_000_000_000;
     let amount_1_desired: u64 = 1_000_000_000;
     let expected_liquidity: u64 = 1_000_000_000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
     // adds initial liquidity
-    let added_liquidity = add_liquidity_script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -47,9 +46,54 @@ async fn panics_on_removing_zero_liquidity() {
 
     assert_eq!(added_liquidity.amount, expected_liquidity);
 
-    remove_liquidity_script_instance
-        .main(pool_id, 0, 0, 0, wallet.address().into(), deadline)
-        .with_contracts(&[&amm.instance])
+    env.scripts
+        .remove_liquidity()
+        .main(
+            env.pools[0],
+            0,
+            0,
+            0,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_on_deadline_one_block_before_execution_height() {
+    let env = setup().await;
+    let deadline = execution_height(&env.provider).await - 1;
+
+    env.scripts
+        .remove_liquidity()
+        .main(env.pools[0], 1, 0, 0, env.wallet.address().into(), deadline)
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_once_blocks_are_produced_past_deadline() {
+    let env = setup().await;
+    produce_blocks_until(&env.provider, env.deadline).await;
+
+    env.scripts
+        .remove_liquidity()
+        .main(
+            env.pools[0],
+            1,
+            0,
+            0,
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
         .call()
         .await
         .unwrap();
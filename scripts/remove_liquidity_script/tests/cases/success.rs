@@ -3,41 +3,40 @@ use fuels::prelude::VariableOutputPolicy;
 use test_harness::interface::amm::pool_metadata;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
 use test_harness::interface::{Asset, BurnEvent};
+use test_harness::setup::common::execution_height;
 use test_harness::utils::common::{pool_assets_balance, MINIMUM_LIQUIDITY};
 
 #[tokio::test]
 async fn removes_all_liquidity_passing_exact_a_and_b_values() {
-    let (
-        add_liquidity_script_instance,
-        remove_liquidity_script_instance,
-        amm,
-        pool_id,
-        wallet,
-        deadline,
-    ) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired: u64 = 1_000_000_000;
     let amount_1_desired: u64 = 1_000_000_000;
     let expected_liquidity: u64 = 1_000_000_000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
     // adds initial liquidity
-    let added_liquidity = add_liquidity_script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -49,21 +48,23 @@ async fn removes_all_liquidity_passing_exact_a_and_b_values() {
     assert_eq!(added_liquidity.amount, expected_liquidity);
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
+        &env.wallet,
         &vec![(added_liquidity.id, added_liquidity.amount)],
     )
     .await;
 
-    let removed_liquidity = remove_liquidity_script_instance
+    let removed_liquidity = env
+        .scripts
+        .remove_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             added_liquidity.amount,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -76,14 +77,17 @@ async fn removes_all_liquidity_passing_exact_a_and_b_values() {
         .unwrap();
     let event = log.first().unwrap();
 
-    let final_pool_metadata = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
-    let final_wallet_balances = pool_assets_balance(&wallet, &pool_id, amm.id).await;
+    let final_pool_metadata = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let final_wallet_balances = pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
 
     assert_eq!(
         *event,
         BurnEvent {
-            pool_id,
-            recipient: wallet.address().into(),
+            pool_id: env.pools[0],
+            recipient: env.wallet.address().into(),
             liquidity: added_liquidity,
             asset_0_out: removed_liquidity.value.0,
             asset_1_out: removed_liquidity.value.1,
@@ -97,37 +101,35 @@ async fn removes_all_liquidity_passing_exact_a_and_b_values() {
 
 #[tokio::test]
 async fn removes_partial_liquidity() {
-    let (
-        add_liquidity_script_instance,
-        remove_liquidity_script_instance,
-        amm,
-        pool_id,
-        wallet,
-        deadline,
-    ) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired: u64 = 1_000_000_000;
     let amount_1_desired: u64 = 1_000_000_000;
     let expected_liquidity: u64 = 1_000_000_000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
-        &vec![(pool_id.0, amount_0_desired), (pool_id.1, amount_1_desired)],
+        &env.wallet,
+        &vec![
+            (env.pools[0].0, amount_0_desired),
+            (env.pools[0].1, amount_1_desired),
+        ],
     )
     .await;
 
     // adds initial liquidity
-    let added_liquidity = add_liquidity_script_instance
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -138,23 +140,27 @@ async fn removes_partial_liquidity() {
 
     assert_eq!(added_liquidity.amount, expected_liquidity);
 
-    let initial_wallet_balances = pool_assets_balance(&wallet, &pool_id, amm.id).await;
+    let initial_wallet_balances = pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
     let liquidity_to_remove = added_liquidity.amount / 2;
 
-    let (inputs, outputs) =
-        get_transaction_inputs_outputs(&wallet, &vec![(added_liquidity.id, liquidity_to_remove)])
-            .await;
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(added_liquidity.id, liquidity_to_remove)],
+    )
+    .await;
 
-    let removed_liquidity = remove_liquidity_script_instance
+    let removed_liquidity = env
+        .scripts
+        .remove_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             liquidity_to_remove,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -167,13 +173,13 @@ async fn removes_partial_liquidity() {
         .unwrap();
     let event = log.first().unwrap();
 
-    let final_wallet_balances = pool_assets_balance(&wallet, &pool_id, amm.id).await;
+    let final_wallet_balances = pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
 
     assert_eq!(
         *event,
         BurnEvent {
-            pool_id,
-            recipient: wallet.address().into(),
+            pool_id: env.pools[0],
+            recipient: env.wallet.address().into(),
             liquidity: Asset {
                 amount: liquidity_to_remove,
                 id: added_liquidity.id
@@ -191,3 +197,39 @@ async fn removes_partial_liquidity() {
         initial_wallet_balances.asset_b + ((amount_1_desired - MINIMUM_LIQUIDITY) / 2)
     );
 }
+
+#[tokio::test]
+async fn removes_liquidity_with_deadline_at_execution_height() {
+    let env = setup().await;
+    let added_liquidity = env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(added_liquidity.id, added_liquidity.amount)],
+    )
+    .await;
+    let deadline = execution_height(&env.provider).await;
+
+    let (amount_0, amount_1) = env
+        .scripts
+        .remove_liquidity()
+        .main(
+            env.pools[0],
+            added_liquidity.amount,
+            0,
+            0,
+            env.wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amount_0, 1_000_000 - MINIMUM_LIQUIDITY);
+    assert_eq!(amount_1, 1_000_000 - MINIMUM_LIQUIDITY);
+}
@@ -1,9 +1,8 @@
 script;
 
-use interfaces::{data_structures::PoolId, mira_amm::MiraAMM};
+use interfaces::data_structures::PoolId;
 use math::pool_math::get_amounts_out;
-use utils::blockchain_utils::check_deadline;
-use std::{asset::transfer, bytes::Bytes};
+use utils::{blockchain_utils::check_deadline, swap_utils::swap_exact_input_along_route};
 
 configurable {
     AMM_CONTRACT_ID: ContractId = ContractId::zero(),
@@ -26,26 +25,7 @@ fn main(
         "Insufficient output amount",
     );
 
-    transfer(Identity::ContractId(AMM_CONTRACT_ID), asset_in, amount_in);
-    let amm = abi(MiraAMM, AMM_CONTRACT_ID.into());
-
-    let mut i = 0;
-    while i < pools.len() {
-        let pool_id = pools.get(i).unwrap();
-        let (amount_out, asset_out) = amounts_out.get(i + 1).unwrap();
-        let to = if i == pools.len() - 1 {
-            recipient
-        } else {
-            Identity::ContractId(AMM_CONTRACT_ID)
-        };
-        let (amount_0_out, amount_1_out) = if asset_out == pool_id.0 {
-            (amount_out, 0)
-        } else {
-            (0, amount_out)
-        };
-        amm.swap(pool_id, amount_0_out, amount_1_out, to, Bytes::new());
-        i += 1;
-    }
+    swap_exact_input_along_route(AMM_CONTRACT_ID, amounts_out, pools, recipient);
 
     amounts_out
 }
@@ -0,0 +1,94 @@
+use fuels::prelude::VariableOutputPolicy;
+use test_harness::analytics::{decode_events, PoolAnalytics, TransactionReceipts};
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::{fees, pool_metadata};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::utils::common::pools_metadata;
+
+const HOUR: u64 = 3_600;
+
+#[tokio::test]
+async fn analytics_follow_swaps_under_protocol_fees() {
+    let env = TestEnvironment::builder()
+        .protocol_fees(10, 0)
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::SwapExactInput])
+        .build()
+        .await;
+    let snapshots = pools_metadata(&env.amm.instance, &[env.pools[0]]).await;
+
+    let mut transactions = vec![];
+    let mut amounts_out = vec![];
+    for amount_in in [10_000, 20_000] {
+        let (inputs, outputs) =
+            get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, amount_in)]).await;
+        let response = env
+            .scripts
+            .swap_exact_input()
+            .main(
+                amount_in,
+                env.tokens[0].id,
+                0,
+                vec![env.pools[0]],
+                env.wallet.address().into(),
+                env.deadline,
+            )
+            .with_contracts(&[&env.amm.instance])
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .unwrap();
+        amounts_out.push(response.value[1].0);
+        transactions.push(TransactionReceipts {
+            block_height: env.provider.latest_block_height().await.unwrap(),
+            timestamp: env
+                .provider
+                .latest_block_time()
+                .await
+                .unwrap()
+                .unwrap()
+                .timestamp() as u64,
+            receipts: response.receipts,
+        });
+    }
+
+    let events = decode_events(env.amm.id, &transactions);
+    let mut analytics =
+        PoolAnalytics::new(fees(&env.amm.instance).await.value, HOUR).with_snapshots(&snapshots);
+    analytics.record_all(&events);
+    let series = analytics.series();
+    let pool_after = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    let last = series.last().unwrap();
+    assert_eq!(series.iter().map(|bucket| bucket.swaps).sum::<u64>(), 2);
+    assert_eq!(
+        series.iter().map(|bucket| bucket.volume_0).sum::<u128>(),
+        30_000
+    );
+    assert_eq!(
+        series.iter().map(|bucket| bucket.volume_1).sum::<u128>(),
+        u128::from(amounts_out[0] + amounts_out[1])
+    );
+    // 10 bps of each input go to the protocol, rounded up
+    assert_eq!(
+        series
+            .iter()
+            .map(|bucket| bucket.protocol_fees_0)
+            .sum::<u128>(),
+        30
+    );
+    assert_eq!(
+        (last.reserve_0, last.reserve_1, last.liquidity),
+        (
+            pool_after.reserve_0,
+            pool_after.reserve_1,
+            pool_after.liquidity.amount
+        )
+    );
+}
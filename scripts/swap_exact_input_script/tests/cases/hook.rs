@@ -0,0 +1,113 @@
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::Identity;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::hook::{calls, last_call};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+
+async fn setup_with_hook() -> TestEnvironment {
+    TestEnvironment::builder()
+        .hook()
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::SwapExactInput])
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn swaps_with_hook_installed() {
+    let env = setup_with_hook().await;
+    let hook = env.hook.as_ref().unwrap();
+    let recipient: Identity = env.wallet.address().into();
+    let calls_before = calls(hook).await.value;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    let amounts_out = env
+        .scripts
+        .swap_exact_input()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            recipient,
+            env.deadline,
+        )
+        .with_contracts(&env.contracts())
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        amounts_out,
+        vec![(1_000, env.tokens[0].id), (996, env.tokens[1].id)]
+    );
+    assert_eq!(calls(hook).await.value, calls_before + 1);
+    let call = last_call(hook).await.value.unwrap();
+    assert_eq!(call.pool_id, env.pools[0]);
+    assert_eq!(call.to, recipient);
+    assert_eq!((call.asset_0_in, call.asset_1_in), (1_000, 0));
+    assert_eq!((call.asset_0_out, call.asset_1_out), (0, 996));
+}
+
+#[tokio::test]
+async fn estimates_hook_dependency() {
+    let env = setup_with_hook().await;
+    let hook = env.hook.as_ref().unwrap();
+    let calls_before = calls(hook).await.value;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    env.scripts
+        .swap_exact_input()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .determine_missing_contracts(None)
+        .await
+        .unwrap()
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(calls(hook).await.value, calls_before + 1);
+}
+
+#[tokio::test]
+#[should_panic(expected = "ContractNotInInputs")]
+async fn panics_without_hook_contract() {
+    let env = setup_with_hook().await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    env.scripts
+        .swap_exact_input()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+}
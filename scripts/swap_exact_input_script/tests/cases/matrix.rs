@@ -0,0 +1,68 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::get_amounts_out;
+use test_harness::matrix::{route, FEES};
+use test_harness::property::{deploy_route, RouteFixture};
+use test_harness::route_matrix;
+use test_harness::utils::common::pools_metadata;
+
+async fn swap_matches_reference_model(decimals: (u8, u8), is_stable: bool, hops: usize) {
+    let route = route(decimals, is_stable, hops);
+    let RouteFixture {
+        amm,
+        wallet,
+        assets,
+        pools,
+        scripts,
+        deadline,
+    } = deploy_route(&route, FEES, &[PeripheryScript::SwapExactInput]).await;
+    let script = scripts.swap_exact_input();
+    let (asset_in, asset_out) = (assets[0], *assets.last().unwrap());
+
+    let amount_in = route.pools[0].reserve_in / 1_000;
+    let amm_fees = fees(&amm.instance).await.value;
+    let expected = get_amounts_out(
+        amm_fees,
+        &pools_metadata(&amm.instance, &pools).await,
+        amount_in,
+        asset_in,
+    );
+    assert!(expected.iter().all(|(amount, _)| *amount > 0));
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(asset_in, amount_in)]).await;
+    let balance_in_before = wallet.get_asset_balance(&asset_in).await.unwrap();
+    let balance_out_before = wallet.get_asset_balance(&asset_out).await.unwrap();
+
+    let amounts_out = script
+        .main(
+            amount_in,
+            asset_in,
+            0,
+            pools.clone(),
+            wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amounts_out, expected);
+    assert_eq!(
+        wallet.get_asset_balance(&asset_in).await.unwrap(),
+        balance_in_before - amount_in
+    );
+    assert_eq!(
+        wallet.get_asset_balance(&asset_out).await.unwrap(),
+        balance_out_before + expected.last().unwrap().0
+    );
+}
+
+route_matrix!(swap_matches_reference_model);
@@ -0,0 +1,67 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use proptest::prelude::*;
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::get_amounts_out;
+use test_harness::property::{
+    amm_fees, block_on, deploy_case_route, route_params, RouteFixture, CASES,
+};
+use test_harness::utils::common::pools_metadata;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn swap_matches_reference_model(
+        route in route_params(3),
+        route_fees in amm_fees(),
+        share_bps in 1..=1_000u64,
+    ) {
+        block_on(async {
+            let RouteFixture { amm, wallet, assets, pools, scripts, deadline } =
+                deploy_case_route(&route, route_fees, &[PeripheryScript::SwapExactInput]).await;
+            let script = scripts.swap_exact_input();
+            let (asset_in, asset_out) = (assets[0], *assets.last().unwrap());
+
+            let amount_in = (route.pools[0].reserve_in * share_bps / 10_000).max(1);
+            let amm_fees = fees(&amm.instance).await.value;
+            prop_assert_eq!(amm_fees, route_fees);
+            let expected = get_amounts_out(
+                amm_fees,
+                &pools_metadata(&amm.instance, &pools).await,
+                amount_in,
+                asset_in,
+            );
+            // the AMM rejects swaps without output
+            prop_assume!(expected.iter().all(|(amount, _)| *amount > 0));
+
+            let (inputs, outputs) =
+                get_transaction_inputs_outputs(&wallet, &vec![(asset_in, amount_in)]).await;
+            let balance_in_before = wallet.get_asset_balance(&asset_in).await.unwrap();
+            let balance_out_before = wallet.get_asset_balance(&asset_out).await.unwrap();
+
+            let amounts_out = script
+                .main(amount_in, asset_in, 0, pools.clone(), wallet.address().into(), deadline)
+                .with_contracts(&[&amm.instance])
+                .with_inputs(inputs)
+                .with_outputs(outputs)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await
+                .unwrap()
+                .value;
+
+            prop_assert_eq!(&amounts_out, &expected);
+            prop_assert_eq!(
+                wallet.get_asset_balance(&asset_in).await.unwrap(),
+                balance_in_before - amount_in
+            );
+            prop_assert_eq!(
+                wallet.get_asset_balance(&asset_out).await.unwrap(),
+                balance_out_before + expected.last().unwrap().0
+            );
+            Ok(())
+        })?;
+    }
+}
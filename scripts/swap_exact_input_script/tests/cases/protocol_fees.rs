@@ -0,0 +1,142 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use fuels::types::{AssetId, U256};
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::PoolMetadata;
+use test_harness::math::{
+    get_amounts_out, marginal_price_after_trade, pow_decimals, spot_price, swap_fees,
+};
+use test_harness::types::PoolId;
+use test_harness::utils::common::pools_metadata;
+
+/// `(volatile_fee, stable_fee)` protocol fees every case is run with, in basis points
+const PROTOCOL_FEES: [(u64, u64); 3] = [(1, 1), (10, 5), (30, 30)];
+const POOL_LIQUIDITY: u64 = 100_000_000;
+const AMOUNT_IN: u64 = 1_000_000;
+
+/// Swaps from the first to the last token of `pools`, seeded with equal reserves, and checks
+/// that the script pays what the model predicts once protocol fees are taken, leaving every
+/// pool at the predicted marginal price
+async fn swaps_under_protocol_fees(pools: &[PoolConfig], protocol_fees: (u64, u64)) {
+    let mut builder = TestEnvironment::builder()
+        .tokens(&[9; 3])
+        .protocol_fees(protocol_fees.0, protocol_fees.1)
+        .scripts(&[PeripheryScript::SwapExactInput]);
+    for pool in pools {
+        builder = builder.pool(pool.with_liquidity(POOL_LIQUIDITY, POOL_LIQUIDITY));
+    }
+    let env = builder.build().await;
+    let (asset_in, asset_out) = (env.tokens[0].id, env.tokens[pools.len()].id);
+
+    let amm_fees = fees(&env.amm.instance).await.value;
+    assert_eq!((amm_fees.2, amm_fees.3), protocol_fees);
+    let metadata = pools_metadata(&env.amm.instance, &env.pools).await;
+    let amounts_out_expected = get_amounts_out(amm_fees, &metadata, AMOUNT_IN, asset_in);
+    let amount_out_expected = amounts_out_expected.last().unwrap().0;
+    let without_protocol_fees = get_amounts_out(
+        (amm_fees.0, amm_fees.1, 0, 0),
+        &metadata,
+        AMOUNT_IN,
+        asset_in,
+    );
+    assert!(amount_out_expected < without_protocol_fees.last().unwrap().0);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(asset_in, AMOUNT_IN)]).await;
+    let balance_before = env.wallet.get_asset_balance(&asset_out).await.unwrap();
+    let amounts_out = env
+        .scripts
+        .swap_exact_input()
+        .main(
+            AMOUNT_IN,
+            asset_in,
+            amount_out_expected,
+            env.pools.clone(),
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amounts_out, amounts_out_expected);
+    assert_eq!(
+        env.wallet.get_asset_balance(&asset_out).await.unwrap(),
+        balance_before + amount_out_expected
+    );
+
+    // the price the model predicts after each hop is the one of the reserves the swap left
+    let metadata_after = pools_metadata(&env.amm.instance, &env.pools).await;
+    for (i, ((pool_id, pool), (_, pool_after))) in metadata.iter().zip(&metadata_after).enumerate()
+    {
+        let (amount_in, asset_in) = amounts_out[i];
+        let (fee, protocol_fee) = swap_fees(amm_fees, pool_id.2);
+        let (reserve_in, reserve_out, pow_decimals_in, pow_decimals_out) =
+            swap_side(pool_id, pool, asset_in);
+        let predicted = marginal_price_after_trade(
+            pool_id.2,
+            reserve_in,
+            reserve_out,
+            pow_decimals_in,
+            pow_decimals_out,
+            amount_in,
+            fee,
+            protocol_fee,
+        );
+        let (reserve_in_after, reserve_out_after, _, _) = swap_side(pool_id, pool_after, asset_in);
+        assert_eq!(
+            predicted,
+            spot_price(
+                pool_id.2,
+                reserve_in_after,
+                reserve_out_after,
+                pow_decimals_in,
+                pow_decimals_out,
+            )
+        );
+    }
+}
+
+/// Reserves and decimals of `pool` ordered in the direction of a swap from `asset_in`
+fn swap_side(pool_id: &PoolId, pool: &PoolMetadata, asset_in: AssetId) -> (U256, U256, U256, U256) {
+    let side_0 = (U256::from(pool.reserve_0), pow_decimals(pool.decimals_0));
+    let side_1 = (U256::from(pool.reserve_1), pow_decimals(pool.decimals_1));
+    let ((reserve_in, pow_decimals_in), (reserve_out, pow_decimals_out)) = if asset_in == pool_id.0
+    {
+        (side_0, side_1)
+    } else {
+        (side_1, side_0)
+    };
+    (reserve_in, reserve_out, pow_decimals_in, pow_decimals_out)
+}
+
+#[tokio::test]
+async fn volatile_pool_takes_protocol_fees() {
+    for protocol_fees in PROTOCOL_FEES {
+        swaps_under_protocol_fees(&[PoolConfig::volatile(0, 1)], protocol_fees).await;
+    }
+}
+
+#[tokio::test]
+async fn stable_pool_takes_protocol_fees() {
+    for protocol_fees in PROTOCOL_FEES {
+        swaps_under_protocol_fees(&[PoolConfig::stable(0, 1)], protocol_fees).await;
+    }
+}
+
+#[tokio::test]
+async fn route_through_both_pool_types_takes_protocol_fees() {
+    for protocol_fees in PROTOCOL_FEES {
+        swaps_under_protocol_fees(
+            &[PoolConfig::volatile(0, 1), PoolConfig::stable(1, 2)],
+            protocol_fees,
+        )
+        .await;
+    }
+}
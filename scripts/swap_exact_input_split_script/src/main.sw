@@ -0,0 +1,139 @@
+script;
+
+use interfaces::{data_structures::PoolId, mira_amm::MiraAMM};
+use math::pool_math::{get_amounts_in, get_amounts_out};
+use utils::blockchain_utils::check_deadline;
+use std::{asset::transfer, bytes::Bytes, primitive_conversions::{u16::*, u64::*}};
+
+configurable {
+    AMM_CONTRACT_ID: ContractId = ContractId::zero(),
+}
+
+/// Divides the output of an exact input swap between several recipients by weight.
+///
+/// The output of swapping the whole `amount_in` is quoted once and allocated by weight, the
+/// allocations being rounded down and the last recipient getting the remainder. The AMM pays
+/// every swap out to a single identity, so each allocation is then bought with an exact output
+/// swap along the path. Rounding can leave the input a few units short of the last allocation,
+/// which is then lowered to what the rest of the input buys. The input left unspent goes back
+/// to the sender through the change output of the transaction.
+///
+/// Every recipient is guaranteed its weighted share of `amount_out_min`, rounded down: the split
+/// reverts rather than letting the rounding shortfall of the last allocation go below it.
+///
+/// Returns (recipient, input spent, output amount) for every recipient.
+fn main(
+    amount_in: u64,
+    asset_in: AssetId,
+    amount_out_min: u64,
+    pools: Vec<PoolId>,
+    recipients: Vec<(Identity, u16)>,
+    deadline: u32,
+) -> Vec<(Identity, u64, u64)> {
+    check_deadline(deadline);
+    require(recipients.len() >= 1, "No recipients");
+    let total_weight = get_total_weight(recipients);
+    require(total_weight > 0, "Zero total weight");
+
+    let amounts_out = get_amounts_out(AMM_CONTRACT_ID, amount_in, asset_in, pools);
+    let (total_amount_out, asset_out) = amounts_out.get(amounts_out.len() - 1).unwrap();
+    require(
+        total_amount_out >= amount_out_min,
+        "Insufficient output amount",
+    );
+
+    let mut results: Vec<(Identity, u64, u64)> = Vec::new();
+    let mut amount_in_left = amount_in;
+    let mut amount_out_left = total_amount_out;
+    let mut i = 0;
+    while i < recipients.len() {
+        let (recipient, weight) = recipients.get(i).unwrap();
+        let allocation = if i == recipients.len() - 1 {
+            min(amount_out_left, get_amount_out(amount_in_left, asset_in, pools))
+        } else {
+            u64::try_from(total_amount_out.as_u256() * weight.as_u64().as_u256() / total_weight.as_u256()).unwrap()
+        };
+        amount_out_left -= allocation;
+        let min_allocation = u64::try_from(amount_out_min.as_u256() * weight.as_u64().as_u256() / total_weight.as_u256()).unwrap();
+        require(allocation >= min_allocation, "Insufficient output amount");
+
+        let spent = if allocation > 0 {
+            swap_exact_output(allocation, asset_out, amount_in_left, pools, recipient)
+        } else {
+            0
+        };
+        amount_in_left -= spent;
+        results.push((recipient, spent, allocation));
+        i += 1;
+    }
+
+    require(
+        total_amount_out - amount_out_left >= amount_out_min,
+        "Insufficient output amount",
+    );
+    results
+}
+
+fn get_total_weight(recipients: Vec<(Identity, u16)>) -> u64 {
+    let mut total_weight = 0;
+    let mut i = 0;
+    while i < recipients.len() {
+        let (_, weight) = recipients.get(i).unwrap();
+        total_weight += weight.as_u64();
+        i += 1;
+    }
+    total_weight
+}
+
+fn min(a: u64, b: u64) -> u64 {
+    if a < b { a } else { b }
+}
+
+/// Output of swapping `amount_in` along `pools` at the current reserves
+fn get_amount_out(amount_in: u64, asset_in: AssetId, pools: Vec<PoolId>) -> u64 {
+    if amount_in == 0 {
+        return 0;
+    }
+    let amounts_out = get_amounts_out(AMM_CONTRACT_ID, amount_in, asset_in, pools);
+    amounts_out.get(amounts_out.len() - 1).unwrap().0
+}
+
+/// Swaps for exactly `amount_out` of `asset_out` paid to `recipient`, returning the input spent
+fn swap_exact_output(
+    amount_out: u64,
+    asset_out: AssetId,
+    amount_in_max: u64,
+    pools: Vec<PoolId>,
+    recipient: Identity,
+) -> u64 {
+    let amounts_in = get_amounts_in(AMM_CONTRACT_ID, amount_out, asset_out, pools);
+    let (first_amount_in, first_asset) = amounts_in.get(amounts_in.len() - 1).unwrap();
+    require(first_amount_in <= amount_in_max, "Exceeding input amount");
+
+    transfer(
+        Identity::ContractId(AMM_CONTRACT_ID),
+        first_asset,
+        first_amount_in,
+    );
+    let amm = abi(MiraAMM, AMM_CONTRACT_ID.into());
+
+    let mut i = 0;
+    while i < pools.len() {
+        let pool_id = pools.get(i).unwrap();
+        let (amount_out, asset_out) = amounts_in.get(amounts_in.len() - i - 2).unwrap();
+        let to = if i == pools.len() - 1 {
+            recipient
+        } else {
+            Identity::ContractId(AMM_CONTRACT_ID)
+        };
+        let (amount_0_out, amount_1_out) = if asset_out == pool_id.0 {
+            (amount_out, 0)
+        } else {
+            (0, amount_out)
+        };
+        amm.swap(pool_id, amount_0_out, amount_1_out, to, Bytes::new());
+        i += 1;
+    }
+
+    first_amount_in
+}
@@ -0,0 +1,122 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::Identity;
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::fees;
+use test_harness::interface::hook::{calls, last_call};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::split_exact_input;
+use test_harness::utils::common::pools_metadata;
+
+async fn setup_with_hook() -> TestEnvironment {
+    TestEnvironment::builder()
+        .hook()
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::SwapExactInputSplit])
+        .build()
+        .await
+}
+
+fn recipients(env: &TestEnvironment) -> Vec<(Identity, u16)> {
+    vec![
+        (env.wallet.address().into(), 1),
+        (WalletUnlocked::new_random(None).address().into(), 3),
+    ]
+}
+
+#[tokio::test]
+async fn calls_hook_for_every_recipient() {
+    let env = setup_with_hook().await;
+    let hook = env.hook.as_ref().unwrap();
+    let recipients = recipients(&env);
+    let calls_before = calls(hook).await.value;
+
+    let amm_fees = fees(&env.amm.instance).await.value;
+    let pools = pools_metadata(&env.amm.instance, &[env.pools[0]]).await;
+    let legs = split_exact_input(amm_fees, &pools, 1_000, env.tokens[0].id, &[1, 3]);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            recipients.clone(),
+            env.deadline,
+        )
+        .with_contracts(&env.contracts())
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(calls(hook).await.value, calls_before + 2);
+    let call = last_call(hook).await.value.unwrap();
+    assert_eq!(call.pool_id, env.pools[0]);
+    assert_eq!(call.to, recipients[1].0);
+    assert_eq!((call.asset_0_in, call.asset_1_in), (legs[1].0, 0));
+    assert_eq!((call.asset_0_out, call.asset_1_out), (0, legs[1].1));
+}
+
+#[tokio::test]
+async fn estimates_hook_dependency() {
+    let env = setup_with_hook().await;
+    let hook = env.hook.as_ref().unwrap();
+    let calls_before = calls(hook).await.value;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            recipients(&env),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .determine_missing_contracts(None)
+        .await
+        .unwrap()
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(calls(hook).await.value, calls_before + 2);
+}
+
+#[tokio::test]
+#[should_panic(expected = "ContractNotInInputs")]
+async fn panics_without_hook_contract() {
+    let env = setup_with_hook().await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            recipients(&env),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+}
@@ -0,0 +1,86 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount, WalletUnlocked};
+use fuels::types::Identity;
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::{get_amounts_out, split_by_weight, split_exact_input};
+use test_harness::matrix::{route, FEES};
+use test_harness::property::{deploy_route, RouteFixture};
+use test_harness::route_matrix;
+use test_harness::utils::common::pools_metadata;
+
+async fn split_matches_reference_model(decimals: (u8, u8), is_stable: bool, hops: usize) {
+    let route = route(decimals, is_stable, hops);
+    let RouteFixture {
+        amm,
+        wallet,
+        assets,
+        pools,
+        scripts,
+        deadline,
+    } = deploy_route(&route, FEES, &[PeripheryScript::SwapExactInputSplit]).await;
+    let script = scripts.swap_exact_input_split();
+    let (asset_in, asset_out) = (assets[0], *assets.last().unwrap());
+
+    let other_recipient = WalletUnlocked::new_random(None);
+    let recipients: Vec<(Identity, u16)> = vec![
+        (wallet.address().into(), 1),
+        (other_recipient.address().into(), 2),
+    ];
+    let amount_in = route.pools[0].reserve_in / 1_000;
+
+    let amm_fees = fees(&amm.instance).await.value;
+    let pools_metadata = pools_metadata(&amm.instance, &pools).await;
+    let total_amount_out = get_amounts_out(amm_fees, &pools_metadata, amount_in, asset_in)
+        .last()
+        .unwrap()
+        .0;
+    let legs = split_exact_input(amm_fees, &pools_metadata, amount_in, asset_in, &[1, 2]);
+    // the output is divided by weight, whatever the price impact of each leg, the last
+    // allocation being lowered only when rounding leaves the input short of it
+    let allocations = split_by_weight(total_amount_out, &[1, 2]);
+    assert_eq!(legs[0].1, allocations[0]);
+    assert!(legs[1].1 <= allocations[1]);
+    let expected: Vec<(Identity, u64, u64)> = legs
+        .iter()
+        .zip(&recipients)
+        .map(|((spent, amount_out), (recipient, _))| (*recipient, *spent, *amount_out))
+        .collect();
+    let amount_spent: u64 = legs.iter().map(|(spent, _)| *spent).sum();
+    assert!(amount_spent <= amount_in);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(asset_in, amount_in)]).await;
+    let balance_in_before = wallet.get_asset_balance(&asset_in).await.unwrap();
+    let balance_out_before = wallet.get_asset_balance(&asset_out).await.unwrap();
+
+    let results = script
+        .main(
+            amount_in,
+            asset_in,
+            total_amount_out,
+            pools.clone(),
+            recipients.clone(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(results, expected);
+    assert_eq!(
+        wallet.get_asset_balance(&asset_in).await.unwrap(),
+        balance_in_before - amount_spent
+    );
+    assert_eq!(
+        wallet.get_asset_balance(&asset_out).await.unwrap(),
+        balance_out_before + expected[0].2
+    );
+}
+
+route_matrix!(split_matches_reference_model);
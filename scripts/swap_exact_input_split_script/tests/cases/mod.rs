@@ -0,0 +1,4 @@
+pub mod hook;
+pub mod matrix;
+pub mod revert;
+pub mod success;
@@ -0,0 +1,173 @@
+use crate::utils::{setup, setup_with_liquidity};
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::Identity;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::{get_amounts_out, split_by_weight, split_exact_input};
+use test_harness::setup::common::{execution_height, produce_blocks_until};
+use test_harness::utils::common::pools_metadata;
+
+#[tokio::test]
+#[should_panic(expected = "No recipients")]
+async fn panics_without_recipients() {
+    let env = setup().await;
+
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            vec![],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Zero total weight")]
+async fn panics_on_zero_total_weight() {
+    let env = setup().await;
+
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            vec![(env.wallet.address().into(), 0)],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Insufficient output amount")]
+async fn panics_when_total_output_is_insufficient() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let other_recipient = WalletUnlocked::new_random(None);
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            997,
+            vec![env.pools[0]],
+            vec![
+                (env.wallet.address().into(), 1),
+                (other_recipient.address().into(), 1),
+            ],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Insufficient output amount")]
+async fn panics_when_rounding_leaves_last_recipient_below_its_share() {
+    let env = setup_with_liquidity().await;
+    let recipients: Vec<(Identity, u16)> = vec![
+        (env.wallet.address().into(), 1),
+        (WalletUnlocked::new_random(None).address().into(), 1),
+    ];
+    let amount_in = 1_000;
+
+    let amm_fees = fees(&env.amm.instance).await.value;
+    let pools = pools_metadata(&env.amm.instance, &[env.pools[0]]).await;
+    let total_amount_out = get_amounts_out(amm_fees, &pools, amount_in, env.tokens[0].id)
+        .last()
+        .unwrap()
+        .0;
+    let allocations = split_by_weight(total_amount_out, &[1, 1]);
+    let legs = split_exact_input(amm_fees, &pools, amount_in, env.tokens[0].id, &[1, 1]);
+    // the input left after the first exact output swap can't buy the whole last allocation
+    assert!(legs[1].1 < allocations[1]);
+    // so the total output covers a minimum whose half the last recipient doesn't get
+    let amount_out_min = legs[0].1 + legs[1].1;
+    assert!(legs[1].1 < amount_out_min / 2);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, amount_in)]).await;
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            amount_in,
+            env.tokens[0].id,
+            amount_out_min,
+            vec![env.pools[0]],
+            recipients,
+            env.deadline,
+        )
+        .with_contracts(&env.contracts())
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_on_deadline_one_block_before_execution_height() {
+    let env = setup().await;
+    let deadline = execution_height(&env.provider).await - 1;
+
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            vec![(env.wallet.address().into(), 1)],
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic(expected = "Deadline passed")]
+async fn panics_once_blocks_are_produced_past_deadline() {
+    let env = setup().await;
+    produce_blocks_until(&env.provider, env.deadline).await;
+
+    env.scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            vec![(env.wallet.address().into(), 1)],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .call()
+        .await
+        .unwrap();
+}
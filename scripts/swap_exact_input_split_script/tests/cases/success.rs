@@ -0,0 +1,289 @@
+use crate::utils::{setup, setup_with_liquidity};
+use fuels::accounts::{wallet::WalletUnlocked, ViewOnlyAccount};
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::Identity;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::{get_amounts_out, proportional_value, split_by_weight, split_exact_input};
+use test_harness::setup::common::execution_height;
+use test_harness::utils::common::pools_metadata;
+
+#[tokio::test]
+async fn splits_output_between_recipients_by_weight() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+    env.add_liquidity(env.pools[1], 1_000_000, 1_000_000).await;
+
+    let recipient_1 = WalletUnlocked::new_random(None);
+    let recipient_2 = WalletUnlocked::new_random(None);
+    let recipients: Vec<(Identity, u16)> = vec![
+        (env.wallet.address().into(), 1),
+        (recipient_1.address().into(), 1),
+        (recipient_2.address().into(), 2),
+    ];
+    let amount_in = 1_001;
+
+    // the output of the whole input is divided by weight, not the input
+    let amm_fees = fees(&env.amm.instance).await.value;
+    let metadata = pools_metadata(&env.amm.instance, &[env.pools[0], env.pools[1]]).await;
+    let total_amount_out = get_amounts_out(amm_fees, &metadata, amount_in, env.tokens[0].id)
+        .last()
+        .unwrap()
+        .0;
+    let allocations = split_by_weight(total_amount_out, &[1, 1, 2]);
+    let legs = split_exact_input(amm_fees, &metadata, amount_in, env.tokens[0].id, &[1, 1, 2]);
+    assert_eq!(
+        legs.iter()
+            .map(|(_, amount_out)| *amount_out)
+            .collect::<Vec<_>>(),
+        allocations
+    );
+    assert_eq!(allocations[0], allocations[1]);
+    assert!(allocations[2] >= 2 * allocations[0]);
+    let amount_spent: u64 = legs.iter().map(|(spent, _)| *spent).sum();
+    assert!(amount_spent <= amount_in);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, amount_in)]).await;
+
+    let wallet_token_0_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[0].id)
+        .await
+        .unwrap();
+    let wallet_token_2_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[2].id)
+        .await
+        .unwrap();
+    let results = env
+        .scripts
+        .swap_exact_input_split()
+        .main(
+            amount_in,
+            env.tokens[0].id,
+            total_amount_out,
+            vec![env.pools[0], env.pools[1]],
+            recipients.clone(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        results,
+        vec![
+            (recipients[0].0, legs[0].0, legs[0].1),
+            (recipients[1].0, legs[1].0, legs[1].1),
+            (recipients[2].0, legs[2].0, legs[2].1),
+        ]
+    );
+    // the unspent input is refunded
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[0].id)
+            .await
+            .unwrap(),
+        wallet_token_0_before - amount_spent
+    );
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[2].id)
+            .await
+            .unwrap(),
+        wallet_token_2_before + allocations[0]
+    );
+    assert_eq!(
+        env.provider
+            .get_asset_balance(recipient_1.address(), env.tokens[2].id)
+            .await
+            .unwrap(),
+        allocations[1]
+    );
+    assert_eq!(
+        env.provider
+            .get_asset_balance(recipient_2.address(), env.tokens[2].id)
+            .await
+            .unwrap(),
+        allocations[2]
+    );
+}
+
+#[tokio::test]
+async fn last_recipient_gets_its_share_of_the_minimum_despite_rounding() {
+    let env = setup_with_liquidity().await;
+    let recipient = WalletUnlocked::new_random(None);
+    let recipients: Vec<(Identity, u16)> = vec![
+        (env.wallet.address().into(), 1),
+        (recipient.address().into(), 1),
+    ];
+    let amount_in = 1_000;
+
+    let amm_fees = fees(&env.amm.instance).await.value;
+    let metadata = pools_metadata(&env.amm.instance, &[env.pools[0]]).await;
+    let total_amount_out = get_amounts_out(amm_fees, &metadata, amount_in, env.tokens[0].id)
+        .last()
+        .unwrap()
+        .0;
+    let allocations = split_by_weight(total_amount_out, &[1, 1]);
+    let legs = split_exact_input(amm_fees, &metadata, amount_in, env.tokens[0].id, &[1, 1]);
+    assert!(legs[1].1 < allocations[1]);
+    // the largest minimum whose half the last recipient still gets
+    let amount_out_min = 2 * legs[1].1;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, amount_in)]).await;
+    let results = env
+        .scripts
+        .swap_exact_input_split()
+        .main(
+            amount_in,
+            env.tokens[0].id,
+            amount_out_min,
+            vec![env.pools[0]],
+            recipients.clone(),
+            env.deadline,
+        )
+        .with_contracts(&env.contracts())
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        results,
+        vec![
+            (recipients[0].0, legs[0].0, legs[0].1),
+            (recipients[1].0, legs[1].0, legs[1].1),
+        ]
+    );
+    for (_, _, amount_out) in &results {
+        assert!(*amount_out >= proportional_value(amount_out_min, 1, 2));
+    }
+    assert_eq!(
+        env.provider
+            .get_asset_balance(recipient.address(), env.tokens[1].id)
+            .await
+            .unwrap(),
+        legs[1].1
+    );
+}
+
+#[tokio::test]
+async fn single_recipient_receives_the_whole_output() {
+    let env = setup().await;
+
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let token_0_to_swap = 1_000;
+    let token_1_expected = 996;
+
+    let amm_fees = fees(&env.amm.instance).await.value;
+    let metadata = pools_metadata(&env.amm.instance, &[env.pools[0]]).await;
+    let legs = split_exact_input(
+        amm_fees,
+        &metadata,
+        token_0_to_swap,
+        env.tokens[0].id,
+        &[10],
+    );
+    assert_eq!(legs[0].1, token_1_expected);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, token_0_to_swap)])
+            .await;
+
+    let wallet_token_0_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[0].id)
+        .await
+        .unwrap();
+    let wallet_token_1_before = env
+        .wallet
+        .get_asset_balance(&env.tokens[1].id)
+        .await
+        .unwrap();
+    let results = env
+        .scripts
+        .swap_exact_input_split()
+        .main(
+            token_0_to_swap,
+            env.tokens[0].id,
+            token_1_expected,
+            vec![env.pools[0]],
+            vec![(env.wallet.address().into(), 10)],
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        results,
+        vec![(env.wallet.address().into(), legs[0].0, token_1_expected)]
+    );
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[0].id)
+            .await
+            .unwrap(),
+        wallet_token_0_before - legs[0].0
+    );
+    assert_eq!(
+        env.wallet
+            .get_asset_balance(&env.tokens[1].id)
+            .await
+            .unwrap(),
+        wallet_token_1_before + token_1_expected
+    );
+}
+
+#[tokio::test]
+async fn splits_with_deadline_at_execution_height() {
+    let env = setup().await;
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let recipient: Identity = env.wallet.address().into();
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_000)]).await;
+    let deadline = execution_height(&env.provider).await;
+
+    let results = env
+        .scripts
+        .swap_exact_input_split()
+        .main(
+            1_000,
+            env.tokens[0].id,
+            0,
+            vec![env.pools[0]],
+            vec![(recipient, 1)],
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!((results[0].0, results[0].2), (recipient, 996));
+    assert!(results[0].1 <= 1_000);
+}
@@ -0,0 +1,24 @@
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+
+pub async fn setup() -> TestEnvironment {
+    TestEnvironment::builder()
+        .tokens(&[9, 9, 9])
+        .pool(PoolConfig::volatile(0, 1))
+        .pool(PoolConfig::volatile(1, 2))
+        .scripts(&[
+            PeripheryScript::AddLiquidity,
+            PeripheryScript::SwapExactInputSplit,
+        ])
+        .build()
+        .await
+}
+
+/// Returns an environment with a single 1:1 volatile pool, where an input unit buys about one
+/// output unit and the rounding of every exact output swap costs the last recipient
+pub async fn setup_with_liquidity() -> TestEnvironment {
+    TestEnvironment::builder()
+        .pool(PoolConfig::volatile(0, 1).with_liquidity(1_000_000, 1_000_000))
+        .scripts(&[PeripheryScript::SwapExactInputSplit])
+        .build()
+        .await
+}
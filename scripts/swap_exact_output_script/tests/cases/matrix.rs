@@ -0,0 +1,74 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use test_harness::environment::PeripheryScript;
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::{get_amounts_in, get_amounts_out};
+use test_harness::matrix::{route, FEES};
+use test_harness::property::{deploy_route, RouteFixture};
+use test_harness::route_matrix;
+use test_harness::utils::common::pools_metadata;
+
+async fn swap_matches_reference_model(decimals: (u8, u8), is_stable: bool, hops: usize) {
+    let route = route(decimals, is_stable, hops);
+    let RouteFixture {
+        amm,
+        wallet,
+        assets,
+        pools,
+        scripts,
+        deadline,
+    } = deploy_route(&route, FEES, &[PeripheryScript::SwapExactOutput]).await;
+    let script = scripts.swap_exact_output();
+    let (asset_in, asset_out) = (assets[0], *assets.last().unwrap());
+
+    let amm_fees = fees(&amm.instance).await.value;
+    let pools_metadata = pools_metadata(&amm.instance, &pools).await;
+    // the output of a thousandth of the first reserve, so that every hop can pay it
+    let amount_out = get_amounts_out(
+        amm_fees,
+        &pools_metadata,
+        route.pools[0].reserve_in / 1_000,
+        asset_in,
+    )
+    .last()
+    .unwrap()
+    .0;
+    assert!(amount_out > 0);
+    let expected = get_amounts_in(amm_fees, &pools_metadata, amount_out, asset_out);
+    let amount_in_max = expected.last().unwrap().0;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(asset_in, amount_in_max)]).await;
+    let balance_in_before = wallet.get_asset_balance(&asset_in).await.unwrap();
+    let balance_out_before = wallet.get_asset_balance(&asset_out).await.unwrap();
+
+    let amounts_in = script
+        .main(
+            amount_out,
+            asset_out,
+            amount_in_max,
+            pools.clone(),
+            wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amounts_in, expected);
+    assert_eq!(
+        wallet.get_asset_balance(&asset_in).await.unwrap(),
+        balance_in_before - amount_in_max
+    );
+    assert_eq!(
+        wallet.get_asset_balance(&asset_out).await.unwrap(),
+        balance_out_before + amount_out
+    );
+}
+
+route_matrix!(swap_matches_reference_model);
@@ -1 +1,6 @@
+pub mod hook;
+pub mod matrix;
+pub mod properties;
+pub mod protocol_fees;
+pub mod revert;
 pub mod success;
@@ -0,0 +1,97 @@
+use fuels::prelude::{VariableOutputPolicy, ViewOnlyAccount};
+use test_harness::environment::{PeripheryScript, PoolConfig, TestEnvironment};
+use test_harness::interface::amm::fees;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::math::get_amounts_in;
+use test_harness::utils::common::pools_metadata;
+
+/// `(volatile_fee, stable_fee)` protocol fees every case is run with, in basis points
+const PROTOCOL_FEES: [(u64, u64); 3] = [(1, 1), (10, 5), (30, 30)];
+const POOL_LIQUIDITY: u64 = 100_000_000;
+const AMOUNT_OUT: u64 = 1_000_000;
+
+/// Swaps from the first to the last token of `pools`, seeded with equal reserves, and checks
+/// that the script charges what the model predicts once protocol fees are taken
+async fn swaps_under_protocol_fees(pools: &[PoolConfig], protocol_fees: (u64, u64)) {
+    let mut builder = TestEnvironment::builder()
+        .tokens(&[9; 3])
+        .protocol_fees(protocol_fees.0, protocol_fees.1)
+        .scripts(&[PeripheryScript::SwapExactOutput]);
+    for pool in pools {
+        builder = builder.pool(pool.with_liquidity(POOL_LIQUIDITY, POOL_LIQUIDITY));
+    }
+    let env = builder.build().await;
+    let (asset_in, asset_out) = (env.tokens[0].id, env.tokens[pools.len()].id);
+
+    let amm_fees = fees(&env.amm.instance).await.value;
+    assert_eq!((amm_fees.2, amm_fees.3), protocol_fees);
+    let metadata = pools_metadata(&env.amm.instance, &env.pools).await;
+    let amounts_in_expected = get_amounts_in(amm_fees, &metadata, AMOUNT_OUT, asset_out);
+    let amount_in_expected = amounts_in_expected.last().unwrap().0;
+    let without_protocol_fees = get_amounts_in(
+        (amm_fees.0, amm_fees.1, 0, 0),
+        &metadata,
+        AMOUNT_OUT,
+        asset_out,
+    );
+    assert!(amount_in_expected > without_protocol_fees.last().unwrap().0);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(asset_in, amount_in_expected)]).await;
+    let balance_in_before = env.wallet.get_asset_balance(&asset_in).await.unwrap();
+    let balance_out_before = env.wallet.get_asset_balance(&asset_out).await.unwrap();
+    let amounts_in = env
+        .scripts
+        .swap_exact_output()
+        .main(
+            AMOUNT_OUT,
+            asset_out,
+            amount_in_expected,
+            env.pools.clone(),
+            env.wallet.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(amounts_in, amounts_in_expected);
+    assert_eq!(
+        env.wallet.get_asset_balance(&asset_in).await.unwrap(),
+        balance_in_before - amount_in_expected
+    );
+    assert_eq!(
+        env.wallet.get_asset_balance(&asset_out).await.unwrap(),
+        balance_out_before + AMOUNT_OUT
+    );
+}
+
+#[tokio::test]
+async fn volatile_pool_takes_protocol_fees() {
+    for protocol_fees in PROTOCOL_FEES {
+        swaps_under_protocol_fees(&[PoolConfig::volatile(0, 1)], protocol_fees).await;
+    }
+}
+
+#[tokio::test]
+async fn stable_pool_takes_protocol_fees() {
+    for protocol_fees in PROTOCOL_FEES {
+        swaps_under_protocol_fees(&[PoolConfig::stable(0, 1)], protocol_fees).await;
+    }
+}
+
+#[tokio::test]
+async fn route_through_both_pool_types_takes_protocol_fees() {
+    for protocol_fees in PROTOCOL_FEES {
+        swaps_under_protocol_fees(
+            &[PoolConfig::volatile(0, 1), PoolConfig::stable(1, 2)],
+            protocol_fees,
+        )
+        .await;
+    }
+}
@@ -1,47 +1,44 @@
 use crate::utils::setup;
-use fuels::prelude::VariableOutputPolicy;
+use fuels::prelude::{TxPolicies, VariableOutputPolicy, ViewOnlyAccount};
+use test_harness::environment::PeripheryScript;
 use test_harness::interface::amm::pool_metadata;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::setup::common::{execution_height, predicate_account};
 use test_harness::utils::common::{pool_assets_balance, MINIMUM_LIQUIDITY};
 
+const PREDICATE_BASE_ASSET_AMOUNT: u64 = 100_000;
+
 #[tokio::test]
 async fn swap_between_two_volatile_tokens() {
-    let (
-        add_liquidity_script,
-        swap_exact_output_script,
-        amm,
-        pool_id,
-        _,
-        wallet,
-        deadline,
-        (token_0_id, token_1_id, _),
-    ) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired: u64 = 1_000_000;
     let amount_1_desired: u64 = 1_000_000;
     let expected_liquidity: u64 = 1_000_000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
+        &env.wallet,
         &vec![
-            (token_0_id, amount_0_desired),
-            (token_1_id, amount_1_desired),
+            (env.tokens[0].id, amount_0_desired),
+            (env.tokens[1].id, amount_1_desired),
         ],
     )
     .await;
 
     // adds initial liquidity
-    let added_liquidity = add_liquidity_script
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -55,21 +52,29 @@ async fn swap_between_two_volatile_tokens() {
     let token_1_output = 1_000;
     let token_0_input_expected = 1006;
 
-    let (inputs, outputs) =
-        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_input_expected)]).await;
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, token_0_input_expected)],
+    )
+    .await;
 
-    let wallet_balances_before = pool_assets_balance(&wallet, &pool_id, amm.id).await;
-    let pool_metadata_before = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
-    let amounts_in = swap_exact_output_script
+    let wallet_balances_before = pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
+    let pool_metadata_before = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let amounts_in = env
+        .scripts
+        .swap_exact_output()
         .main(
             token_1_output,
-            token_1_id,
+            env.tokens[1].id,
             token_0_input_expected * 2,
-            vec![pool_id],
-            wallet.address().into(),
-            deadline,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
@@ -81,12 +86,15 @@ async fn swap_between_two_volatile_tokens() {
     assert_eq!(
         amounts_in,
         vec![
-            (token_1_output, token_1_id),
-            (token_0_input_expected, token_0_id),
+            (token_1_output, env.tokens[1].id),
+            (token_0_input_expected, env.tokens[0].id),
         ]
     );
-    let wallet_balances_after = pool_assets_balance(&wallet, &pool_id, amm.id).await;
-    let pool_metadata_after = pool_metadata(&amm.instance, pool_id).await.value.unwrap();
+    let wallet_balances_after = pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
+    let pool_metadata_after = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
 
     assert_eq!(
         wallet_balances_after.asset_a,
@@ -108,42 +116,35 @@ async fn swap_between_two_volatile_tokens() {
 
 #[tokio::test]
 async fn swap_between_three_volatile_tokens() {
-    let (
-        add_liquidity_script,
-        swap_exact_output_script,
-        amm,
-        pool_id_0,
-        pool_id_1,
-        wallet,
-        deadline,
-        (token_0_id, token_1_id, token_2_id),
-    ) = setup().await;
+    let env = setup().await;
 
     let amount_0_desired: u64 = 1_000_000;
     let amount_1_desired: u64 = 1_000_000;
     let expected_liquidity: u64 = 1_000_000 - MINIMUM_LIQUIDITY;
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
+        &env.wallet,
         &vec![
-            (token_0_id, amount_0_desired),
-            (token_1_id, amount_1_desired),
+            (env.tokens[0].id, amount_0_desired),
+            (env.tokens[1].id, amount_1_desired),
         ],
     )
     .await;
 
     // adds initial liquidity
-    let added_liquidity = add_liquidity_script
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id_0,
+            env.pools[0],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -155,26 +156,28 @@ async fn swap_between_three_volatile_tokens() {
     assert_eq!(added_liquidity.amount, expected_liquidity);
 
     let (inputs, outputs) = get_transaction_inputs_outputs(
-        &wallet,
+        &env.wallet,
         &vec![
-            (token_1_id, amount_0_desired),
-            (token_2_id, amount_1_desired),
+            (env.tokens[1].id, amount_0_desired),
+            (env.tokens[2].id, amount_1_desired),
         ],
     )
     .await;
 
     // adds initial liquidity
-    let added_liquidity = add_liquidity_script
+    let added_liquidity = env
+        .scripts
+        .add_liquidity()
         .main(
-            pool_id_1,
+            env.pools[1],
             amount_0_desired,
             amount_1_desired,
             0,
             0,
-            wallet.address().into(),
-            deadline,
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
@@ -189,23 +192,36 @@ async fn swap_between_three_volatile_tokens() {
     let token_1_input_expected = 1006;
     let token_2_output = 1000;
 
-    let (inputs, outputs) =
-        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_input_expected)]).await;
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &env.wallet,
+        &vec![(env.tokens[0].id, token_0_input_expected)],
+    )
+    .await;
 
-    let wallet_balances_0_before = pool_assets_balance(&wallet, &pool_id_0, amm.id).await;
-    let wallet_balances_1_before = pool_assets_balance(&wallet, &pool_id_1, amm.id).await;
-    let pool_metadata_0_before = pool_metadata(&amm.instance, pool_id_0).await.value.unwrap();
-    let pool_metadata_1_before = pool_metadata(&amm.instance, pool_id_1).await.value.unwrap();
-    let amounts_in = swap_exact_output_script
+    let wallet_balances_0_before =
+        pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
+    let wallet_balances_1_before =
+        pool_assets_balance(&env.wallet, &env.pools[1], env.amm.id).await;
+    let pool_metadata_0_before = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let pool_metadata_1_before = pool_metadata(&env.amm.instance, env.pools[1])
+        .await
+        .value
+        .unwrap();
+    let amounts_in = env
+        .scripts
+        .swap_exact_output()
         .main(
             token_2_output,
-            token_2_id,
+            env.tokens[2].id,
             token_0_input_expected * 2,
-            vec![pool_id_0, pool_id_1],
-            wallet.address().into(),
-            deadline,
+            vec![env.pools[0], env.pools[1]],
+            env.wallet.address().into(),
+            env.deadline,
         )
-        .with_contracts(&[&amm.instance])
+        .with_contracts(&[&env.amm.instance])
         .with_inputs(inputs)
         .with_outputs(outputs)
         .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
@@ -213,17 +229,23 @@ async fn swap_between_three_volatile_tokens() {
         .await
         .unwrap()
         .value;
-    let pool_metadata_0_after = pool_metadata(&amm.instance, pool_id_0).await.value.unwrap();
-    let pool_metadata_1_after = pool_metadata(&amm.instance, pool_id_1).await.value.unwrap();
-    let wallet_balances_0_after = pool_assets_balance(&wallet, &pool_id_0, amm.id).await;
-    let wallet_balances_1_after = pool_assets_balance(&wallet, &pool_id_1, amm.id).await;
+    let pool_metadata_0_after = pool_metadata(&env.amm.instance, env.pools[0])
+        .await
+        .value
+        .unwrap();
+    let pool_metadata_1_after = pool_metadata(&env.amm.instance, env.pools[1])
+        .await
+        .value
+        .unwrap();
+    let wallet_balances_0_after = pool_assets_balance(&env.wallet, &env.pools[0], env.amm.id).await;
+    let wallet_balances_1_after = pool_assets_balance(&env.wallet, &env.pools[1], env.amm.id).await;
 
     assert_eq!(
         amounts_in,
         vec![
-            (token_2_output, token_2_id),
-            (token_1_input_expected, pool_id_0.1),
-            (token_0_input_expected, pool_id_0.0),
+            (token_2_output, env.tokens[2].id),
+            (token_1_input_expected, env.pools[0].1),
+            (token_0_input_expected, env.pools[0].0),
         ]
     );
 
@@ -257,3 +279,98 @@ async fn swap_between_three_volatile_tokens() {
         pool_metadata_1_before.reserve_1 - token_2_output
     );
 }
+
+#[tokio::test]
+async fn swaps_with_deadline_at_execution_height() {
+    let env = setup().await;
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&env.wallet, &vec![(env.tokens[0].id, 1_006)]).await;
+    let deadline = execution_height(&env.provider).await;
+
+    let amounts_in = env
+        .scripts
+        .swap_exact_output()
+        .main(
+            1_000,
+            env.tokens[1].id,
+            2_000,
+            vec![env.pools[0]],
+            env.wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        amounts_in,
+        vec![(1_000, env.tokens[1].id), (1_006, env.tokens[0].id)]
+    );
+}
+
+#[tokio::test]
+async fn swaps_from_predicate_account() {
+    let env = setup().await;
+    env.add_liquidity(env.pools[0], 1_000_000, 1_000_000).await;
+
+    let predicate = predicate_account(&env.provider);
+    for (asset_id, amount) in [
+        (*env.provider.base_asset_id(), PREDICATE_BASE_ASSET_AMOUNT),
+        (env.tokens[0].id, 1_006),
+    ] {
+        env.wallet
+            .transfer(predicate.address(), amount, asset_id, TxPolicies::default())
+            .await
+            .unwrap();
+    }
+    let predicate_scripts = env
+        .scripts_for(&predicate, &[PeripheryScript::SwapExactOutput])
+        .await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&predicate, &vec![(env.tokens[0].id, 1_006)]).await;
+    let amounts_in = predicate_scripts
+        .swap_exact_output()
+        .main(
+            1_000,
+            env.tokens[1].id,
+            1_006,
+            vec![env.pools[0]],
+            predicate.address().into(),
+            env.deadline,
+        )
+        .with_contracts(&[&env.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(
+        amounts_in,
+        vec![(1_000, env.tokens[1].id), (1_006, env.tokens[0].id)]
+    );
+    assert_eq!(
+        predicate
+            .get_asset_balance(&env.tokens[0].id)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        predicate
+            .get_asset_balance(&env.tokens[1].id)
+            .await
+            .unwrap(),
+        1_000
+    );
+}
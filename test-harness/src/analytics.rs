@@ -0,0 +1,755 @@
+use std::{
+    fmt::Write as _,
+    io::{Read, Write},
+};
+
+use fuels::{
+    core::{
+        codec::ABIDecoder,
+        traits::{Parameterize, Tokenizable},
+    },
+    prelude::Provider,
+    tx::{Receipt, ScriptExecutionResult},
+    types::{tx_status::TxStatus, ContractId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::AssetRegistry,
+    fixtures::AMM,
+    interface::{BurnEvent, CreatePoolEvent, MintEvent, PoolMetadata, SwapEvent},
+    math::{subtract_fee, swap_fees, value_in_asset_0},
+    types::PoolId,
+    utils::common::MINIMUM_LIQUIDITY,
+};
+
+/// Receipts of one transaction, as saved for offline analysis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReceipts {
+    pub block_height: u32,
+    /// Unix time of the block, in seconds
+    pub timestamp: u64,
+    pub receipts: Vec<Receipt>,
+}
+
+/// Reads a receipts file, a JSON array of `TransactionReceipts`
+pub fn read_receipts(reader: impl Read) -> serde_json::Result<Vec<TransactionReceipts>> {
+    serde_json::from_reader(reader)
+}
+
+pub fn write_receipts(
+    writer: impl Write,
+    transactions: &[TransactionReceipts],
+) -> serde_json::Result<()> {
+    serde_json::to_writer(writer, transactions)
+}
+
+/// Receipts of the executed transactions of blocks `from_height` to `to_height`, to be saved
+/// with `write_receipts`
+pub async fn fetch_receipts(
+    provider: &Provider,
+    from_height: u32,
+    to_height: u32,
+) -> Vec<TransactionReceipts> {
+    let mut transactions = vec![];
+    for height in from_height..=to_height {
+        let Some(block) = provider.block_by_height(height.into()).await.unwrap() else {
+            break;
+        };
+        let timestamp = block
+            .header
+            .time
+            .map(|time| time.timestamp() as u64)
+            .unwrap_or_default();
+        for tx_id in &block.transactions {
+            let receipts = match provider.tx_status(tx_id).await.unwrap() {
+                TxStatus::Success { receipts, .. } | TxStatus::Revert { receipts, .. } => receipts,
+                _ => continue,
+            };
+            transactions.push(TransactionReceipts {
+                block_height: height,
+                timestamp,
+                receipts,
+            });
+        }
+    }
+    transactions
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolEvent {
+    CreatePool(CreatePoolEvent),
+    Mint(MintEvent),
+    Burn(BurnEvent),
+    Swap(SwapEvent),
+}
+
+impl PoolEvent {
+    pub fn pool_id(&self) -> PoolId {
+        match self {
+            PoolEvent::CreatePool(event) => event.pool_id,
+            PoolEvent::Mint(event) => event.pool_id,
+            PoolEvent::Burn(event) => event.pool_id,
+            PoolEvent::Swap(event) => event.pool_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedEvent {
+    pub block_height: u32,
+    pub timestamp: u64,
+    pub event: PoolEvent,
+}
+
+/// Decodes the pool events logged by the AMM in the successful transactions, in order
+pub fn decode_events(
+    amm_contract_id: ContractId,
+    transactions: &[TransactionReceipts],
+) -> Vec<TimedEvent> {
+    let log_id = |event: &str| {
+        AMM.log_id(&format!("struct interfaces::events::{event}"))
+            .unwrap()
+    };
+    let (create_pool, mint, burn, swap) = (
+        log_id("CreatePoolEvent"),
+        log_id("MintEvent"),
+        log_id("BurnEvent"),
+        log_id("SwapEvent"),
+    );
+
+    let mut events = vec![];
+    for transaction in transactions.iter().filter(|tx| succeeded(&tx.receipts)) {
+        for receipt in &transaction.receipts {
+            let Receipt::LogData {
+                id,
+                rb,
+                data: Some(data),
+                ..
+            } = receipt
+            else {
+                continue;
+            };
+            if *id != amm_contract_id {
+                continue;
+            }
+            let event = match *rb {
+                rb if rb == create_pool => PoolEvent::CreatePool(decode(data)),
+                rb if rb == mint => PoolEvent::Mint(decode(data)),
+                rb if rb == burn => PoolEvent::Burn(decode(data)),
+                rb if rb == swap => PoolEvent::Swap(decode(data)),
+                _ => continue,
+            };
+            events.push(TimedEvent {
+                block_height: transaction.block_height,
+                timestamp: transaction.timestamp,
+                event,
+            });
+        }
+    }
+    events
+}
+
+fn succeeded(receipts: &[Receipt]) -> bool {
+    receipts.iter().any(|receipt| {
+        matches!(
+            receipt,
+            Receipt::ScriptResult {
+                result: ScriptExecutionResult::Success,
+                ..
+            }
+        )
+    })
+}
+
+fn decode<T: Parameterize + Tokenizable>(data: &[u8]) -> T {
+    let token = ABIDecoder::default()
+        .decode(&T::param_type(), data)
+        .unwrap();
+    T::from_token(token).unwrap()
+}
+
+/// Activity of a pool over a time bucket, and its state once the bucket is over.
+/// Amounts summed over the bucket are `u128`, as they can exceed the reserves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolBucket {
+    pub pool_id: PoolId,
+    /// Unix time the bucket starts at, in seconds
+    pub start: u64,
+    pub swaps: u64,
+    /// Amounts of the assets swapped in and out of the pool
+    pub volume_0: u128,
+    pub volume_1: u128,
+    /// Share of the swap fees left to the liquidity providers
+    pub lp_fees_0: u128,
+    pub lp_fees_1: u128,
+    pub protocol_fees_0: u128,
+    pub protocol_fees_1: u128,
+    pub reserve_0: u64,
+    pub reserve_1: u64,
+    /// LP supply, including the locked minimum
+    pub liquidity: u64,
+    /// Value of both reserves in `asset_0` at the spot price of the pool
+    pub tvl_0: u128,
+}
+
+struct PoolState {
+    pool_id: PoolId,
+    decimals: (u8, u8),
+    reserve_0: u64,
+    reserve_1: u64,
+    liquidity: u64,
+    buckets: Vec<PoolBucket>,
+}
+
+impl PoolState {
+    fn tvl_0(&self) -> u128 {
+        value_in_asset_0(
+            self.pool_id.2,
+            (self.reserve_0, self.reserve_1),
+            self.decimals,
+            (self.reserve_0, self.reserve_1),
+        )
+        .expect("TVL doesn't fit in a u128")
+    }
+
+    /// Bucket starting at `start`, carrying the state over the buckets without activity
+    fn bucket(&mut self, start: u64, bucket_seconds: u64) -> &mut PoolBucket {
+        let next = match self.buckets.last() {
+            Some(last) if last.start == start => None,
+            Some(last) => {
+                assert!(last.start < start, "Events must be in time order");
+                Some(last.start + bucket_seconds)
+            }
+            None => Some(start),
+        };
+        if let Some(mut next) = next {
+            let tvl_0 = self.tvl_0();
+            while next <= start {
+                self.buckets.push(PoolBucket {
+                    pool_id: self.pool_id,
+                    start: next,
+                    swaps: 0,
+                    volume_0: 0,
+                    volume_1: 0,
+                    lp_fees_0: 0,
+                    lp_fees_1: 0,
+                    protocol_fees_0: 0,
+                    protocol_fees_1: 0,
+                    reserve_0: self.reserve_0,
+                    reserve_1: self.reserve_1,
+                    liquidity: self.liquidity,
+                    tvl_0,
+                });
+                next += bucket_seconds;
+            }
+        }
+        self.buckets.last_mut().unwrap()
+    }
+
+    /// Records the current state as the closing state of the last bucket
+    fn close(&mut self) {
+        let (reserve_0, reserve_1, liquidity, tvl_0) =
+            (self.reserve_0, self.reserve_1, self.liquidity, self.tvl_0());
+        let bucket = self.buckets.last_mut().unwrap();
+        bucket.reserve_0 = reserve_0;
+        bucket.reserve_1 = reserve_1;
+        bucket.liquidity = liquidity;
+        bucket.tvl_0 = tvl_0;
+    }
+}
+
+/// Per pool volume, fees and TVL series replayed from the AMM events.
+///
+/// Reserves and LP supply follow the events from the pool creation, or from a `pool_metadata`
+/// snapshot taken before the first of them. Swap fees are charged on the input with the
+/// `fees()` rates the engine is given, the protocol share leaving the reserves.
+pub struct PoolAnalytics {
+    fees: (u64, u64, u64, u64),
+    bucket_seconds: u64,
+    pools: Vec<PoolState>,
+}
+
+impl PoolAnalytics {
+    pub fn new(fees: (u64, u64, u64, u64), bucket_seconds: u64) -> Self {
+        assert!(bucket_seconds > 0, "Empty buckets");
+        Self {
+            fees,
+            bucket_seconds,
+            pools: vec![],
+        }
+    }
+
+    /// Starts the pools of `snapshots` from their metadata, for receipts that don't reach
+    /// back to their creation
+    pub fn with_snapshots(mut self, snapshots: &[(PoolId, PoolMetadata)]) -> Self {
+        for (pool_id, pool) in snapshots {
+            self.pools.push(PoolState {
+                pool_id: *pool_id,
+                decimals: (pool.decimals_0, pool.decimals_1),
+                reserve_0: pool.reserve_0,
+                reserve_1: pool.reserve_1,
+                liquidity: pool.liquidity.amount,
+                buckets: vec![],
+            });
+        }
+        self
+    }
+
+    pub fn record_all(&mut self, events: &[TimedEvent]) {
+        for event in events {
+            self.record(event);
+        }
+    }
+
+    /// Applies an event, later than any recorded before. The creation of a snapshotted pool
+    /// keeps the snapshot, which was taken before it.
+    /// Panics if its pool was neither created nor snapshotted.
+    pub fn record(&mut self, event: &TimedEvent) {
+        let start = event.timestamp - event.timestamp % self.bucket_seconds;
+        let pool_id = event.event.pool_id();
+        let is_known = self.pools.iter().any(|pool| pool.pool_id == pool_id);
+        if let PoolEvent::CreatePool(create_pool) = &event.event {
+            if !is_known {
+                self.pools.push(PoolState {
+                    pool_id,
+                    decimals: (create_pool.decimals_0, create_pool.decimals_1),
+                    reserve_0: 0,
+                    reserve_1: 0,
+                    liquidity: 0,
+                    buckets: vec![],
+                });
+            }
+        }
+
+        let (fee, protocol_fee) = swap_fees(self.fees, pool_id.2);
+        let pool = self
+            .pools
+            .iter_mut()
+            .find(|pool| pool.pool_id == pool_id)
+            .expect("Pool doesn't exist");
+        // opened before applying the event, so that skipped buckets carry the previous state
+        pool.bucket(start, self.bucket_seconds);
+
+        match &event.event {
+            PoolEvent::CreatePool(_) => {}
+            PoolEvent::Mint(mint) => {
+                pool.reserve_0 += mint.asset_0_in;
+                pool.reserve_1 += mint.asset_1_in;
+                if pool.liquidity == 0 {
+                    pool.liquidity = MINIMUM_LIQUIDITY;
+                }
+                pool.liquidity += mint.liquidity.amount;
+            }
+            PoolEvent::Burn(burn) => {
+                pool.reserve_0 -= burn.asset_0_out;
+                pool.reserve_1 -= burn.asset_1_out;
+                pool.liquidity -= burn.liquidity.amount;
+            }
+            PoolEvent::Swap(swap) => {
+                let (lp_fee_0, protocol_fee_0) = split_fee(swap.asset_0_in, fee, protocol_fee);
+                let (lp_fee_1, protocol_fee_1) = split_fee(swap.asset_1_in, fee, protocol_fee);
+                pool.reserve_0 =
+                    pool.reserve_0 + swap.asset_0_in - protocol_fee_0 - swap.asset_0_out;
+                pool.reserve_1 =
+                    pool.reserve_1 + swap.asset_1_in - protocol_fee_1 - swap.asset_1_out;
+
+                let bucket = pool.buckets.last_mut().unwrap();
+                bucket.swaps += 1;
+                bucket.volume_0 += u128::from(swap.asset_0_in) + u128::from(swap.asset_0_out);
+                bucket.volume_1 += u128::from(swap.asset_1_in) + u128::from(swap.asset_1_out);
+                bucket.lp_fees_0 += u128::from(lp_fee_0);
+                bucket.lp_fees_1 += u128::from(lp_fee_1);
+                bucket.protocol_fees_0 += u128::from(protocol_fee_0);
+                bucket.protocol_fees_1 += u128::from(protocol_fee_1);
+            }
+        }
+        pool.close();
+    }
+
+    /// Buckets of every pool, without gaps between the first and last event of each,
+    /// ordered by pool then time
+    pub fn series(&self) -> Vec<PoolBucket> {
+        self.pools
+            .iter()
+            .flat_map(|pool| pool.buckets.iter().cloned())
+            .collect()
+    }
+}
+
+/// `(lp_fee, protocol_fee)` charged on `amount_in`, both rounded up as the AMM does
+fn split_fee(amount_in: u64, fee: u64, protocol_fee: u64) -> (u64, u64) {
+    let total = amount_in - subtract_fee(amount_in, fee);
+    let protocol = (amount_in - subtract_fee(amount_in, protocol_fee)).min(total);
+    (total - protocol, protocol)
+}
+
+pub fn series_to_json(series: &[PoolBucket]) -> String {
+    serde_json::to_string_pretty(series).unwrap()
+}
+
+/// Series as CSV, with the symbols of the pool assets when `assets` knows them
+pub fn series_to_csv(series: &[PoolBucket], assets: &AssetRegistry) -> String {
+    let mut csv = String::from(
+        "asset_0,asset_1,symbol_0,symbol_1,is_stable,start,swaps,volume_0,volume_1,lp_fees_0,lp_fees_1,\
+         protocol_fees_0,protocol_fees_1,reserve_0,reserve_1,liquidity,tvl_0\n",
+    );
+    for bucket in series {
+        let (asset_0, asset_1, is_stable) = bucket.pool_id;
+        let symbol = |asset| assets.symbol(asset).unwrap_or_default();
+        writeln!(
+            csv,
+            "{asset_0},{asset_1},{},{},{is_stable},{},{},{},{},{},{},{},{},{},{},{},{}",
+            symbol(asset_0),
+            symbol(asset_1),
+            bucket.start,
+            bucket.swaps,
+            bucket.volume_0,
+            bucket.volume_1,
+            bucket.lp_fees_0,
+            bucket.lp_fees_1,
+            bucket.protocol_fees_0,
+            bucket.protocol_fees_1,
+            bucket.reserve_0,
+            bucket.reserve_1,
+            bucket.liquidity,
+            bucket.tvl_0,
+        )
+        .unwrap();
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::{
+        core::codec::ABIEncoder,
+        types::{AssetId, Bytes32, Identity},
+    };
+
+    use super::*;
+    use crate::interface::Asset;
+
+    const FEES: (u64, u64, u64, u64) = (30, 5, 10, 0);
+    const HOUR: u64 = 3_600;
+
+    fn volatile_pool() -> PoolId {
+        (AssetId::new([1; 32]), AssetId::new([2; 32]), false)
+    }
+
+    fn lp_asset(amount: u64) -> Asset {
+        Asset {
+            id: AssetId::new([3; 32]),
+            amount,
+        }
+    }
+
+    fn log(amm: ContractId, event: &str, token: fuels::types::Token) -> Receipt {
+        let rb = AMM
+            .log_id(&format!("struct interfaces::events::{event}"))
+            .unwrap();
+        let data = ABIEncoder::default().encode(&[token]).unwrap();
+        Receipt::LogData {
+            id: amm,
+            ra: 0,
+            rb,
+            ptr: 0,
+            len: data.len() as u64,
+            digest: Bytes32::zeroed(),
+            pc: 0,
+            is: 0,
+            data: Some(data),
+        }
+    }
+
+    fn transaction(
+        timestamp: u64,
+        receipts: Vec<Receipt>,
+        result: ScriptExecutionResult,
+    ) -> TransactionReceipts {
+        let mut receipts = receipts;
+        receipts.push(Receipt::ScriptResult {
+            result,
+            gas_used: 0,
+        });
+        TransactionReceipts {
+            block_height: (timestamp / HOUR) as u32,
+            timestamp,
+            receipts,
+        }
+    }
+
+    fn history(amm: ContractId) -> Vec<TransactionReceipts> {
+        let pool_id = volatile_pool();
+        let recipient = Identity::Address(Default::default());
+        vec![
+            transaction(
+                100,
+                vec![
+                    log(
+                        amm,
+                        "CreatePoolEvent",
+                        CreatePoolEvent {
+                            pool_id,
+                            decimals_0: 9,
+                            decimals_1: 9,
+                        }
+                        .into_token(),
+                    ),
+                    log(
+                        amm,
+                        "MintEvent",
+                        MintEvent {
+                            pool_id,
+                            recipient,
+                            liquidity: lp_asset(999_000),
+                            asset_0_in: 1_000_000,
+                            asset_1_in: 1_000_000,
+                        }
+                        .into_token(),
+                    ),
+                ],
+                ScriptExecutionResult::Success,
+            ),
+            transaction(
+                200,
+                vec![log(
+                    amm,
+                    "SwapEvent",
+                    SwapEvent {
+                        pool_id,
+                        recipient,
+                        asset_0_in: 10_000,
+                        asset_1_in: 0,
+                        asset_0_out: 0,
+                        asset_1_out: 9_000,
+                    }
+                    .into_token(),
+                )],
+                ScriptExecutionResult::Success,
+            ),
+            // reverted, so its logs never happened
+            transaction(
+                300,
+                vec![log(
+                    amm,
+                    "SwapEvent",
+                    SwapEvent {
+                        pool_id,
+                        recipient,
+                        asset_0_in: 50_000,
+                        asset_1_in: 0,
+                        asset_0_out: 0,
+                        asset_1_out: 40_000,
+                    }
+                    .into_token(),
+                )],
+                ScriptExecutionResult::Revert,
+            ),
+            transaction(
+                2 * HOUR + 10,
+                vec![log(
+                    amm,
+                    "BurnEvent",
+                    BurnEvent {
+                        pool_id,
+                        recipient,
+                        liquidity: lp_asset(500_000),
+                        asset_0_out: 500_000,
+                        asset_1_out: 400_000,
+                    }
+                    .into_token(),
+                )],
+                ScriptExecutionResult::Success,
+            ),
+        ]
+    }
+
+    #[test]
+    fn decodes_events_of_successful_transactions() {
+        let amm = ContractId::new([7; 32]);
+        let mut transactions = history(amm);
+        // logs of other contracts are ignored
+        transactions[1]
+            .receipts
+            .extend(history(ContractId::new([8; 32]))[1].receipts.clone());
+
+        let events = decode_events(amm, &transactions);
+
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0].event, PoolEvent::CreatePool(_)));
+        assert!(matches!(events[1].event, PoolEvent::Mint(_)));
+        assert!(matches!(events[2].event, PoolEvent::Swap(_)));
+        assert!(matches!(events[3].event, PoolEvent::Burn(_)));
+        assert_eq!(events[3].timestamp, 2 * HOUR + 10);
+    }
+
+    #[test]
+    fn receipts_survive_a_round_trip_through_a_file() {
+        let transactions = history(ContractId::new([7; 32]));
+        let mut file = vec![];
+        write_receipts(&mut file, &transactions).unwrap();
+
+        assert_eq!(read_receipts(file.as_slice()).unwrap(), transactions);
+    }
+
+    #[test]
+    fn buckets_volume_fees_and_reserves() {
+        let amm = ContractId::new([7; 32]);
+        let mut analytics = PoolAnalytics::new(FEES, HOUR);
+        analytics.record_all(&decode_events(amm, &history(amm)));
+
+        let series = analytics.series();
+
+        assert_eq!(series.len(), 3);
+        let (first, idle, last) = (&series[0], &series[1], &series[2]);
+        assert_eq!(first.start, 0);
+        assert_eq!(first.swaps, 1);
+        assert_eq!((first.volume_0, first.volume_1), (10_000, 9_000));
+        // 40 bps on 10_000, of which 10 bps go to the protocol
+        assert_eq!((first.lp_fees_0, first.protocol_fees_0), (30, 10));
+        assert_eq!((first.lp_fees_1, first.protocol_fees_1), (0, 0));
+        assert_eq!((first.reserve_0, first.reserve_1), (1_009_990, 991_000));
+        assert_eq!(first.liquidity, 1_000_000);
+        assert_eq!(first.tvl_0, 2 * 1_009_990);
+
+        assert_eq!(idle.start, HOUR);
+        assert_eq!(idle.swaps, 0);
+        assert_eq!(
+            (idle.reserve_0, idle.reserve_1, idle.liquidity),
+            (first.reserve_0, first.reserve_1, first.liquidity)
+        );
+
+        assert_eq!(last.start, 2 * HOUR);
+        assert_eq!((last.reserve_0, last.reserve_1), (509_990, 591_000));
+        assert_eq!(last.liquidity, 500_000);
+    }
+
+    #[test]
+    fn starts_from_snapshots() {
+        let volatile_pool_id = volatile_pool();
+        let stable_pool_id = (volatile_pool_id.0, volatile_pool_id.1, true);
+        let snapshot = |reserve_0, reserve_1, liquidity| PoolMetadata {
+            reserve_0,
+            reserve_1,
+            liquidity: lp_asset(liquidity),
+            decimals_0: 6,
+            decimals_1: 6,
+        };
+        let recipient = Identity::Address(Default::default());
+        let mut analytics = PoolAnalytics::new(FEES, HOUR).with_snapshots(&[
+            (volatile_pool_id, snapshot(2_000, 1_000, 1_414)),
+            (stable_pool_id, snapshot(1_000, 1_000, 2_000)),
+        ]);
+        analytics.record_all(&[
+            TimedEvent {
+                block_height: 1,
+                timestamp: 10,
+                event: PoolEvent::Burn(BurnEvent {
+                    pool_id: volatile_pool_id,
+                    recipient,
+                    liquidity: lp_asset(414),
+                    asset_0_out: 585,
+                    asset_1_out: 292,
+                }),
+            },
+            TimedEvent {
+                block_height: 1,
+                timestamp: 10,
+                event: PoolEvent::Mint(MintEvent {
+                    pool_id: stable_pool_id,
+                    recipient,
+                    liquidity: lp_asset(2_000),
+                    asset_0_in: 1_000,
+                    asset_1_in: 1_000,
+                }),
+            },
+        ]);
+
+        let series = analytics.series();
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(
+            (
+                series[0].reserve_0,
+                series[0].reserve_1,
+                series[0].liquidity
+            ),
+            (1_415, 708, 1_000)
+        );
+        assert_eq!(series[0].tvl_0, 2 * 1_415);
+        // a balanced stable pool trades at par
+        assert_eq!(
+            (
+                series[1].reserve_0,
+                series[1].reserve_1,
+                series[1].liquidity
+            ),
+            (2_000, 2_000, 4_000)
+        );
+        assert_eq!(series[1].tvl_0, 4_000);
+    }
+
+    #[test]
+    fn keeps_snapshot_of_created_pool() {
+        let amm = ContractId::new([7; 32]);
+        let empty_pool = PoolMetadata {
+            reserve_0: 0,
+            reserve_1: 0,
+            liquidity: lp_asset(0),
+            decimals_0: 9,
+            decimals_1: 9,
+        };
+        let mut analytics =
+            PoolAnalytics::new(FEES, HOUR).with_snapshots(&[(volatile_pool(), empty_pool)]);
+        analytics.record_all(&decode_events(amm, &history(amm)));
+
+        let series = analytics.series();
+
+        assert_eq!(series.len(), 3);
+        assert!(series
+            .iter()
+            .all(|bucket| bucket.pool_id == volatile_pool()));
+        assert_eq!(
+            (series[2].reserve_0, series[2].reserve_1),
+            (509_990, 591_000)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Pool doesn't exist")]
+    fn panics_on_unknown_pool() {
+        let mut analytics = PoolAnalytics::new(FEES, HOUR);
+        analytics.record(&TimedEvent {
+            block_height: 1,
+            timestamp: 10,
+            event: PoolEvent::Swap(SwapEvent {
+                pool_id: volatile_pool(),
+                recipient: Identity::Address(Default::default()),
+                asset_0_in: 1,
+                asset_1_in: 0,
+                asset_0_out: 0,
+                asset_1_out: 1,
+            }),
+        });
+    }
+
+    #[test]
+    fn exports_one_csv_row_per_bucket() {
+        let amm = ContractId::new([7; 32]);
+        let mut analytics = PoolAnalytics::new(FEES, HOUR);
+        analytics.record_all(&decode_events(amm, &history(amm)));
+        let series = analytics.series();
+
+        let csv = series_to_csv(&series, &AssetRegistry::new(amm));
+        let json: Vec<PoolBucket> = serde_json::from_str(&series_to_json(&series)).unwrap();
+
+        assert_eq!(csv.lines().count(), 1 + series.len());
+        assert!(csv
+            .lines()
+            .nth(1)
+            .unwrap()
+            .ends_with(",false,0,1,10000,9000,30,0,10,0,1009990,991000,1000000,2019980"));
+        assert_eq!(json, series);
+    }
+}
@@ -0,0 +1,342 @@
+use std::{collections::HashMap, io::Read};
+
+use fuels::{
+    prelude::{Account, AssetId},
+    types::ContractId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    discovery::PoolRegistry,
+    interface::{amm, src20, MiraAMM, MockToken},
+    types::PoolId,
+    utils::common::get_lp_asset_id,
+};
+
+/// SRC-20 metadata of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub asset_id: AssetId,
+    /// Contract minting the asset, when it could be found
+    pub contract_id: Option<ContractId>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: u8,
+}
+
+/// Entry of an override file, taking precedence over the on-chain metadata field by field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetOverride {
+    pub asset_id: AssetId,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub decimals: Option<u8>,
+}
+
+impl AssetOverride {
+    fn is_complete(&self) -> bool {
+        self.name.is_some() && self.symbol.is_some() && self.decimals.is_some()
+    }
+
+    fn apply(&self, metadata: Option<TokenMetadata>) -> Option<TokenMetadata> {
+        let decimals = self
+            .decimals
+            .or(metadata.as_ref().map(|metadata| metadata.decimals))?;
+        let metadata = metadata.unwrap_or(TokenMetadata {
+            asset_id: self.asset_id,
+            contract_id: None,
+            name: None,
+            symbol: None,
+            decimals,
+        });
+        Some(TokenMetadata {
+            name: self.name.clone().or(metadata.name),
+            symbol: self.symbol.clone().or(metadata.symbol),
+            decimals,
+            ..metadata
+        })
+    }
+}
+
+/// Reads an override file, a JSON array of `AssetOverride`
+pub fn read_overrides(reader: impl Read) -> serde_json::Result<Vec<AssetOverride>> {
+    serde_json::from_reader(reader)
+}
+
+/// Metadata of `asset` from a contract implementing SRC-20, if it knows the asset. Any other
+/// contract, or a failing call, is a miss.
+pub async fn fetch_token_metadata<A: Account>(
+    contract: &MockToken<A>,
+    asset: AssetId,
+) -> Option<TokenMetadata> {
+    let decimals = src20::try_decimals(contract, asset).await?;
+    Some(TokenMetadata {
+        asset_id: asset,
+        contract_id: Some(contract.contract_id().into()),
+        name: src20::try_name(contract, asset).await,
+        symbol: src20::try_symbol(contract, asset).await,
+        decimals,
+    })
+}
+
+/// Metadata of an LP asset, which the AMM mints and describes itself. A failing call is a miss.
+pub async fn fetch_lp_metadata<A: Account>(
+    amm: &MiraAMM<A>,
+    lp_asset: AssetId,
+) -> Option<TokenMetadata> {
+    let decimals = amm::try_decimals(amm, lp_asset).await?;
+    Some(TokenMetadata {
+        asset_id: lp_asset,
+        contract_id: Some(amm.contract_id().into()),
+        name: amm::try_name(amm, lp_asset).await,
+        symbol: amm::try_symbol(amm, lp_asset).await,
+        decimals,
+    })
+}
+
+/// Names, symbols and decimals of the assets traded on an AMM, for display.
+///
+/// Metadata is fetched once per asset and cached, misses included: from the AMM for the LP
+/// assets of registered pools, and from the registered token contracts otherwise. Overrides,
+/// e.g. read from a local file, take precedence and spare the calls once complete.
+#[derive(Debug, Clone)]
+pub struct AssetRegistry {
+    amm_contract_id: ContractId,
+    contracts: Vec<ContractId>,
+    overrides: HashMap<AssetId, AssetOverride>,
+    cache: HashMap<AssetId, Option<TokenMetadata>>,
+    lp_assets: HashMap<AssetId, PoolId>,
+}
+
+impl AssetRegistry {
+    pub fn new(amm_contract_id: ContractId) -> Self {
+        Self {
+            amm_contract_id,
+            contracts: vec![],
+            overrides: HashMap::new(),
+            cache: HashMap::new(),
+            lp_assets: HashMap::new(),
+        }
+    }
+
+    /// SRC-20 contracts asked, in order, for the metadata of the assets which aren't LP assets
+    pub fn with_contracts(mut self, contracts: &[ContractId]) -> Self {
+        for contract_id in contracts {
+            if !self.contracts.contains(contract_id) {
+                self.contracts.push(*contract_id);
+            }
+        }
+        self
+    }
+
+    pub fn with_overrides(mut self, overrides: Vec<AssetOverride>) -> Self {
+        self.overrides.extend(
+            overrides
+                .into_iter()
+                .map(|asset_override| (asset_override.asset_id, asset_override)),
+        );
+        self
+    }
+
+    /// Registers the LP assets of `pools`, for them to resolve back to their pool
+    pub fn add_pools(&mut self, pools: &[PoolId]) {
+        for pool_id in pools {
+            self.lp_assets
+                .insert(get_lp_asset_id(self.amm_contract_id, pool_id), *pool_id);
+        }
+    }
+
+    /// Registers the pools of a discovery, caching the metadata fetched for their assets
+    pub fn add_discovered(&mut self, registry: &PoolRegistry) {
+        self.add_pools(&registry.pool_ids());
+        for pool in registry.pools() {
+            let tokens = [
+                Some(&pool.token_0),
+                Some(&pool.token_1),
+                pool.lp_token.as_ref(),
+            ];
+            for token in tokens.into_iter().flatten() {
+                if let Some(contract_id) = token.contract_id {
+                    if contract_id != self.amm_contract_id && !self.contracts.contains(&contract_id)
+                    {
+                        self.contracts.push(contract_id);
+                    }
+                }
+                self.cache.insert(token.asset_id, Some(token.clone()));
+            }
+        }
+    }
+
+    /// Pool of which `asset` is the LP asset, among the registered pools
+    pub fn pool_of_lp_asset(&self, asset: AssetId) -> Option<PoolId> {
+        self.lp_assets.get(&asset).copied()
+    }
+
+    /// Metadata of `asset` as resolved so far, overrides applied, without making any call
+    pub fn get(&self, asset: AssetId) -> Option<TokenMetadata> {
+        let metadata = self.cache.get(&asset).cloned().flatten();
+        match self.overrides.get(&asset) {
+            Some(asset_override) => asset_override.apply(metadata),
+            None => metadata,
+        }
+    }
+
+    /// Metadata of `asset`, fetched on first use
+    pub async fn resolve<A: Account>(
+        &mut self,
+        account: &A,
+        asset: AssetId,
+    ) -> Option<TokenMetadata> {
+        let overridden = self
+            .overrides
+            .get(&asset)
+            .is_some_and(AssetOverride::is_complete);
+        if !overridden && !self.cache.contains_key(&asset) {
+            let metadata = self.fetch(account, asset).await;
+            self.cache.insert(asset, metadata);
+        }
+        self.get(asset)
+    }
+
+    async fn fetch<A: Account>(&self, account: &A, asset: AssetId) -> Option<TokenMetadata> {
+        if self.lp_assets.contains_key(&asset) {
+            let amm = MiraAMM::new(self.amm_contract_id, account.clone());
+            return fetch_lp_metadata(&amm, asset).await;
+        }
+        for contract_id in &self.contracts {
+            let contract = MockToken::new(*contract_id, account.clone());
+            if let Some(metadata) = fetch_token_metadata(&contract, asset).await {
+                return Some(metadata);
+            }
+        }
+        None
+    }
+
+    /// Symbol of `asset`, overridden even when its decimals aren't known
+    pub fn symbol(&self, asset: AssetId) -> Option<String> {
+        self.overrides
+            .get(&asset)
+            .and_then(|asset_override| asset_override.symbol.clone())
+            .or_else(|| self.get(asset).and_then(|metadata| metadata.symbol))
+    }
+
+    /// Symbol of `asset`, falling back to its id. LP assets are named after their pool
+    /// unless overridden, as the AMM gives all of them the same symbol.
+    pub fn label(&self, asset: AssetId) -> String {
+        let overridden = self
+            .overrides
+            .get(&asset)
+            .is_some_and(|asset_override| asset_override.symbol.is_some());
+        if let (Some(pool_id), false) = (self.pool_of_lp_asset(asset), overridden) {
+            return format!("{} LP", self.pool_label(&pool_id));
+        }
+        self.symbol(asset).unwrap_or_else(|| asset.to_string())
+    }
+
+    /// e.g. `TKA/TKB volatile`
+    pub fn pool_label(&self, pool_id: &PoolId) -> String {
+        let (asset_0, asset_1, is_stable) = *pool_id;
+        let curve = if is_stable { "stable" } else { "volatile" };
+        format!("{}/{} {curve}", self.label(asset_0), self.label(asset_1))
+    }
+
+    /// e.g. `TKA -> TKB -> TKC`, for a route of `pools` starting with `asset_in`
+    pub fn route_label(&self, asset_in: AssetId, pools: &[PoolId]) -> String {
+        let mut labels = vec![self.label(asset_in)];
+        let mut asset = asset_in;
+        for (asset_0, asset_1, _) in pools {
+            asset = if *asset_0 == asset {
+                *asset_1
+            } else {
+                *asset_0
+            };
+            labels.push(self.label(asset));
+        }
+        labels.join(" -> ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMM: ContractId = ContractId::new([7; 32]);
+
+    fn asset(byte: u8) -> AssetId {
+        AssetId::new([byte; 32])
+    }
+
+    fn token(asset_id: AssetId, symbol: &str) -> TokenMetadata {
+        TokenMetadata {
+            asset_id,
+            contract_id: Some(ContractId::new([8; 32])),
+            name: Some(format!("Token {symbol}")),
+            symbol: Some(symbol.to_string()),
+            decimals: 9,
+        }
+    }
+
+    fn registry() -> AssetRegistry {
+        let mut registry = AssetRegistry::new(AMM);
+        registry
+            .cache
+            .insert(asset(1), Some(token(asset(1), "TKA")));
+        registry
+            .cache
+            .insert(asset(2), Some(token(asset(2), "TKB")));
+        registry.cache.insert(asset(3), None);
+        registry
+    }
+
+    #[test]
+    fn overrides_take_precedence_field_by_field() {
+        let overrides = r#"[
+            {"asset_id": "0101010101010101010101010101010101010101010101010101010101010101", "symbol": "USDC"},
+            {"asset_id": "0303030303030303030303030303030303030303030303030303030303030303", "symbol": "ETH"},
+            {"asset_id": "0404040404040404040404040404040404040404040404040404040404040404", "decimals": 18}
+        ]"#;
+        let registry = registry().with_overrides(read_overrides(overrides.as_bytes()).unwrap());
+
+        let usdc = registry.get(asset(1)).unwrap();
+        assert_eq!(usdc.symbol.as_deref(), Some("USDC"));
+        assert_eq!(usdc.name.as_deref(), Some("Token TKA"));
+        assert_eq!(usdc.decimals, 9);
+        // no decimals known, so no metadata
+        assert_eq!(registry.get(asset(3)), None);
+        assert_eq!(registry.label(asset(3)), "ETH");
+        assert_eq!(registry.get(asset(4)).unwrap().decimals, 18);
+        assert_eq!(registry.get(asset(4)).unwrap().contract_id, None);
+        assert_eq!(registry.get(asset(2)), Some(token(asset(2), "TKB")));
+    }
+
+    #[test]
+    fn resolves_lp_assets_to_their_pool() {
+        let mut registry = registry();
+        let pool_id = (asset(1), asset(2), true);
+        registry.add_pools(&[pool_id]);
+        let lp_asset = get_lp_asset_id(AMM, &pool_id);
+
+        assert_eq!(registry.pool_of_lp_asset(lp_asset), Some(pool_id));
+        assert_eq!(registry.pool_of_lp_asset(asset(1)), None);
+        assert_eq!(registry.label(lp_asset), "TKA/TKB stable LP");
+    }
+
+    #[test]
+    fn labels_routes_with_symbols_or_ids() {
+        let registry = registry();
+        let pools = [(asset(1), asset(2), false), (asset(2), asset(5), false)];
+
+        assert_eq!(registry.pool_label(&pools[0]), "TKA/TKB volatile");
+        assert_eq!(
+            registry.route_label(asset(1), &pools),
+            format!("TKA -> TKB -> {}", asset(5))
+        );
+        assert_eq!(
+            registry.route_label(asset(5), &[pools[1]]),
+            format!("{} -> TKB", asset(5))
+        );
+    }
+}
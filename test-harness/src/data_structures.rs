@@ -1,4 +1,7 @@
-use fuels::prelude::{AssetId, ContractId, WalletUnlocked};
+use fuels::{
+    prelude::{Account, AssetId, ContractId, WalletUnlocked},
+    types::{Identity, Salt},
+};
 
 use super::interface::MiraAMM;
 
@@ -8,9 +11,9 @@ const AMOUNT_PER_COIN: u64 = 1_000_000;
 const COINS_PER_ASSET: u64 = 100;
 pub const NUMBER_OF_ASSETS: u64 = 5;
 
-pub struct MiraAMMContract {
+pub struct MiraAMMContract<A: Account = WalletUnlocked> {
     pub id: ContractId,
-    pub instance: MiraAMM<WalletUnlocked>,
+    pub instance: MiraAMM<A>,
 }
 
 #[derive(Debug)]
@@ -35,3 +38,18 @@ impl Default for WalletAssetConfiguration {
         }
     }
 }
+
+/// Settings applied to the AMM right after it is deployed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AmmConfiguration {
+    /// Defaults to the deploying account
+    pub owner: Option<Identity>,
+    /// `(volatile_fee, stable_fee)` LP fees in basis points, set through the configurables of
+    /// the binary. The AMM charges the fees it was built with, 30 and 5, when unset.
+    pub lp_fees: Option<(u64, u64)>,
+    /// `(volatile_fee, stable_fee)` protocol fees in basis points, set by the owner once
+    /// ownership is initialized. The AMM charges no protocol fee when unset.
+    pub protocol_fees: Option<(u64, u64)>,
+    /// Salt the AMM is deployed under, to deploy more than one AMM on a node
+    pub salt: Salt,
+}
@@ -0,0 +1,302 @@
+use std::{collections::HashMap, time::Duration};
+
+use fuels::{
+    prelude::*,
+    programs::responses::CallResponse,
+    types::{errors::Error, output::Output, Bits256, Identity},
+};
+
+use crate::{
+    data_structures::MiraAMMContract,
+    interface::{
+        amm::fees, DcaPredicateConfigurables, DcaPredicateEncoder, DcaSigner, SwapExactInputScript,
+    },
+    math::get_amounts_out,
+    order_book::{swap_script_bytecode_hash, withdraw_signed_by_owner},
+    paths::DCA_PREDICATE_BINARY_PATH,
+    slippage::{swap_exact_input_amount_out_min, Tolerance},
+    types::PoolId,
+    utils::common::pools_metadata,
+};
+
+/// Number of blocks a submitted tranche stays valid for
+const TRANCHE_DEADLINE_BLOCKS: u32 = 10;
+
+/// Outcome of a submitted tranche, which fails e.g. when the price moves under the minimum
+/// between the quote and the execution
+pub type TrancheResult = std::result::Result<CallResponse<Vec<(u64, AssetId)>>, Error>;
+
+/// Recurring buy of `asset_out`, swapping `chunk_amount` of the deposit every `block_interval`
+#[derive(Debug, Clone)]
+pub struct DcaPosition {
+    pub owner: Address,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    pub total_amount: u64,
+    pub chunk_amount: u64,
+    pub min_amount_out_per_chunk: u64,
+    pub start_height: u32,
+    pub block_interval: u32,
+    /// Route used by the keeper to swap the tranches, has to end with `asset_out`
+    pub pools: Vec<PoolId>,
+}
+
+impl DcaPosition {
+    /// Height from which the next tranche can be swapped, given the deposit left in the predicate.
+    /// As in the predicate, the tranches executed are rounded up, so that less than a chunk sent
+    /// to the predicate doesn't unlock the next tranche early.
+    /// Returns `None` once less than a chunk is left, or when the height overflows.
+    pub fn next_tranche_height(&self, remaining: u64) -> Option<u32> {
+        if remaining < self.chunk_amount {
+            return None;
+        }
+        let spent = self.total_amount.saturating_sub(remaining);
+        let executed_tranches = u32::try_from(spent.div_ceil(self.chunk_amount)).ok()?;
+        executed_tranches
+            .checked_mul(self.block_interval)?
+            .checked_add(self.start_height)
+    }
+}
+
+/// Tracks DCA predicates and submits their tranches through the `swap_exact_input_script`.
+///
+/// The predicates only accept tranches signed by the keeper, which asks for the quoted output
+/// less `tolerance` rather than the per-chunk minimum alone, so that tranches can't be
+/// sandwiched down to it. As for the limit orders, tranches have to be submitted with a script
+/// instance which was not converted into a loader, and whose account is the keeper.
+pub struct DcaKeeper {
+    swap_script_bytecode_hash: Bits256,
+    keeper: Address,
+    tolerance: Tolerance,
+    positions: HashMap<Address, (DcaPosition, Predicate)>,
+}
+
+impl DcaKeeper {
+    pub fn new(amm_contract_id: ContractId, keeper: Address, tolerance: Tolerance) -> Self {
+        Self {
+            swap_script_bytecode_hash: swap_script_bytecode_hash(amm_contract_id),
+            keeper,
+            tolerance,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Builds the predicate for the position and starts tracking it.
+    /// The position becomes active once the owner deposits `total_amount` into the predicate.
+    pub fn create_position(&mut self, position: DcaPosition, provider: &Provider) -> Predicate {
+        assert!(position.chunk_amount > 0, "Chunk amount can't be zero");
+        assert!(
+            position.total_amount >= position.chunk_amount,
+            "Total amount is less than a chunk"
+        );
+        assert!(!position.pools.is_empty(), "Position route can't be empty");
+
+        let configurables = DcaPredicateConfigurables::default()
+            .with_OWNER(position.owner)
+            .unwrap()
+            .with_KEEPER(self.keeper)
+            .unwrap()
+            .with_ASSET_IN(position.asset_in)
+            .unwrap()
+            .with_ASSET_OUT(position.asset_out)
+            .unwrap()
+            .with_TOTAL_AMOUNT(position.total_amount)
+            .unwrap()
+            .with_CHUNK_AMOUNT(position.chunk_amount)
+            .unwrap()
+            .with_MIN_AMOUNT_OUT_PER_CHUNK(position.min_amount_out_per_chunk)
+            .unwrap()
+            .with_START_HEIGHT(position.start_height)
+            .unwrap()
+            .with_BLOCK_INTERVAL(position.block_interval)
+            .unwrap()
+            .with_SWAP_SCRIPT_BYTECODE_HASH(self.swap_script_bytecode_hash)
+            .unwrap();
+        // the keeper signature is the only witness of the tranche transactions
+        let data = DcaPredicateEncoder::default()
+            .encode_data(DcaSigner::Keeper(0))
+            .unwrap();
+
+        let predicate = Predicate::load_from(DCA_PREDICATE_BINARY_PATH)
+            .unwrap()
+            .with_configurables(configurables)
+            .with_data(data)
+            .with_provider(provider.clone());
+
+        self.positions
+            .insert(predicate.address().into(), (position, predicate.clone()));
+        predicate
+    }
+
+    pub fn position(&self, predicate_address: &Address) -> Option<&DcaPosition> {
+        self.positions
+            .get(predicate_address)
+            .map(|(position, _)| position)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (&Address, &DcaPosition)> {
+        self.positions
+            .iter()
+            .map(|(address, (position, _))| (address, position))
+    }
+
+    /// Submits the tranche of every position unlocked at the current block height.
+    /// Tranches for which the route pays less than the per-chunk minimum are skipped.
+    pub async fn execute_due_tranches(
+        &self,
+        provider: &Provider,
+        amm: &MiraAMMContract<impl Account>,
+        swap_script: &SwapExactInputScript<impl Account>,
+    ) -> Vec<TrancheResult> {
+        let height = provider.latest_block_height().await.unwrap();
+        let amm_fees = fees(&amm.instance).await.value;
+
+        let mut responses = vec![];
+        for (position, predicate) in self.positions.values() {
+            let remaining = predicate
+                .get_asset_balance(&position.asset_in)
+                .await
+                .unwrap();
+            match position.next_tranche_height(remaining) {
+                Some(unlock_height) if unlock_height <= height => {}
+                _ => continue,
+            }
+
+            let pools = pools_metadata(&amm.instance, &position.pools).await;
+            let amounts_out =
+                get_amounts_out(amm_fees, &pools, position.chunk_amount, position.asset_in);
+            if amounts_out.last().unwrap().0 < position.min_amount_out_per_chunk {
+                continue;
+            }
+            let amount_out_min =
+                swap_exact_input_amount_out_min(&amounts_out, &position.pools, self.tolerance)
+                    .max(position.min_amount_out_per_chunk);
+
+            // all of the predicate coins are spent so that the predicate sees the whole deposit
+            let inputs = predicate
+                .get_asset_inputs_for_amount(position.asset_in, remaining, None)
+                .await
+                .unwrap();
+            let outputs = vec![Output::Change {
+                asset_id: position.asset_in,
+                amount: 0,
+                to: predicate.address().into(),
+            }];
+
+            let response = swap_script
+                .main(
+                    position.chunk_amount,
+                    position.asset_in,
+                    amount_out_min,
+                    position.pools.clone(),
+                    Identity::Address(position.owner),
+                    height + TRANCHE_DEADLINE_BLOCKS,
+                )
+                .with_contracts(&[&amm.instance])
+                .with_inputs(inputs)
+                .with_outputs(outputs)
+                .with_tx_policies(TxPolicies::default().with_maturity(height))
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await;
+            responses.push(response);
+        }
+        responses
+    }
+
+    /// Keeps submitting tranches until every tracked position is exhausted.
+    /// Failed tranches are submitted again at the next poll.
+    pub async fn run(
+        &self,
+        provider: &Provider,
+        amm: &MiraAMMContract<impl Account>,
+        swap_script: &SwapExactInputScript<impl Account>,
+        poll_interval: Duration,
+    ) {
+        while self.has_active_positions().await {
+            self.execute_due_tranches(provider, amm, swap_script).await;
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    async fn has_active_positions(&self) -> bool {
+        for (position, predicate) in self.positions.values() {
+            let remaining = predicate
+                .get_asset_balance(&position.asset_in)
+                .await
+                .unwrap();
+            if position.next_tranche_height(remaining).is_some() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns what is left of the deposit to the owner, who has to sign the transaction
+    pub async fn withdraw(&mut self, predicate_address: &Address, owner: &impl Account) {
+        let (position, predicate) = self.positions.remove(predicate_address).unwrap();
+        assert_eq!(
+            position.owner,
+            Address::from(owner.address()),
+            "Only the owner can withdraw the deposit"
+        );
+
+        // the owner signature is the only witness of the transaction
+        let data = DcaPredicateEncoder::default()
+            .encode_data(DcaSigner::Owner(0))
+            .unwrap();
+        withdraw_signed_by_owner(predicate.with_data(data), owner, position.asset_in).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(start_height: u32, block_interval: u32) -> DcaPosition {
+        DcaPosition {
+            owner: Address::new([0; 32]),
+            asset_in: AssetId::new([1; 32]),
+            asset_out: AssetId::new([2; 32]),
+            total_amount: 3_000,
+            chunk_amount: 1_000,
+            min_amount_out_per_chunk: 0,
+            start_height,
+            block_interval,
+            pools: vec![],
+        }
+    }
+
+    #[test]
+    fn next_tranche_height_follows_the_remaining_deposit() {
+        let position = position(100, 5);
+
+        assert_eq!(position.next_tranche_height(3_000), Some(100));
+        assert_eq!(position.next_tranche_height(2_000), Some(105));
+        assert_eq!(position.next_tranche_height(1_000), Some(110));
+        assert_eq!(position.next_tranche_height(999), None);
+    }
+
+    #[test]
+    fn dust_does_not_move_the_unlock_height() {
+        let position = position(100, 5);
+
+        // a tranche executed, then dust sent to the predicate
+        assert_eq!(position.next_tranche_height(2_001), Some(105));
+        assert_eq!(position.next_tranche_height(2_999), Some(105));
+        // dust sent before the first tranche
+        assert_eq!(position.next_tranche_height(3_001), Some(100));
+        // a tranche executed and a whole chunk sent back, which gets swapped for the owner
+        assert_eq!(position.next_tranche_height(3_000), Some(100));
+    }
+
+    #[test]
+    fn next_tranche_height_does_not_overflow() {
+        assert_eq!(position(u32::MAX, 1).next_tranche_height(2_000), None);
+        assert_eq!(position(0, u32::MAX).next_tranche_height(1_000), None);
+        assert_eq!(
+            position(u32::MAX, 1).next_tranche_height(3_000),
+            Some(u32::MAX)
+        );
+    }
+}
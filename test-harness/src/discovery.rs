@@ -0,0 +1,316 @@
+use fuels::{
+    prelude::{Account, AssetId},
+    tx::Receipt,
+    types::ContractId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    analytics::{decode_events, fetch_receipts, PoolEvent, TransactionReceipts},
+    assets::{fetch_lp_metadata, fetch_token_metadata, TokenMetadata},
+    fixtures::AMM,
+    interface::{MiraAMM, MockToken},
+    types::PoolId,
+    utils::common::get_lp_asset_id,
+};
+
+/// Pool created by the AMM, with the metadata of its assets and LP asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveredPool {
+    pub pool_id: PoolId,
+    /// Height of the block the pool was created in
+    pub created_at: u32,
+    pub token_0: TokenMetadata,
+    pub token_1: TokenMetadata,
+    /// Metadata the AMM gives its LP asset, unless the calls failed
+    pub lp_token: Option<TokenMetadata>,
+}
+
+/// Pools found by scanning blocks for the `CreatePoolEvent` logs of the AMM, in the order they
+/// were created
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolRegistry {
+    pools: Vec<DiscoveredPool>,
+}
+
+impl PoolRegistry {
+    /// Scans blocks `from_height` to `to_height` for the pools created by `amm`
+    pub async fn discover<A: Account>(amm: &MiraAMM<A>, from_height: u32, to_height: u32) -> Self {
+        let provider = amm.account().try_provider().unwrap().clone();
+        let transactions = fetch_receipts(&provider, from_height, to_height).await;
+        let mut registry = Self::default();
+        registry.extend(amm, &transactions).await;
+        registry
+    }
+
+    /// Adds the pools created in `transactions`, e.g. the blocks scanned since the last discovery
+    pub async fn extend<A: Account>(
+        &mut self,
+        amm: &MiraAMM<A>,
+        transactions: &[TransactionReceipts],
+    ) {
+        let amm_contract_id = ContractId::from(amm.contract_id());
+        for transaction in transactions {
+            let events = decode_events(amm_contract_id, std::slice::from_ref(transaction));
+            let created_pools = events.into_iter().filter_map(|event| match event.event {
+                PoolEvent::CreatePool(create_pool) => Some(create_pool),
+                _ => None,
+            });
+            let token_contracts = create_pool_contracts(amm_contract_id, &transaction.receipts);
+            for (create_pool, token_contracts) in created_pools.zip(token_contracts) {
+                if self.pool(&create_pool.pool_id).is_some() {
+                    continue;
+                }
+                let (asset_0, asset_1, _) = create_pool.pool_id;
+                let (contract_0, contract_1) = token_contracts.unzip();
+                let lp_asset = get_lp_asset_id(amm_contract_id, &create_pool.pool_id);
+                self.pools.push(DiscoveredPool {
+                    pool_id: create_pool.pool_id,
+                    created_at: transaction.block_height,
+                    token_0: token_metadata(amm, contract_0, asset_0, create_pool.decimals_0).await,
+                    token_1: token_metadata(amm, contract_1, asset_1, create_pool.decimals_1).await,
+                    lp_token: fetch_lp_metadata(amm, lp_asset).await,
+                });
+            }
+        }
+    }
+
+    pub fn pools(&self) -> &[DiscoveredPool] {
+        &self.pools
+    }
+
+    /// Ids of every pool, to be fed to `pools_metadata` for quoting and routing
+    pub fn pool_ids(&self) -> Vec<PoolId> {
+        self.pools.iter().map(|pool| pool.pool_id).collect()
+    }
+
+    pub fn pool(&self, pool_id: &PoolId) -> Option<&DiscoveredPool> {
+        self.pools.iter().find(|pool| pool.pool_id == *pool_id)
+    }
+
+    /// Pools trading `asset`, as either of their assets
+    pub fn pools_with_asset(&self, asset: AssetId) -> Vec<&DiscoveredPool> {
+        self.pools
+            .iter()
+            .filter(|pool| pool.pool_id.0 == asset || pool.pool_id.1 == asset)
+            .collect()
+    }
+
+    /// Paths of up to `max_hops` pools from `asset_in` to `asset_out`, never going through
+    /// an asset twice, shortest first. Each of them is a valid `pools` argument of the swap scripts.
+    pub fn routes(
+        &self,
+        asset_in: AssetId,
+        asset_out: AssetId,
+        max_hops: usize,
+    ) -> Vec<Vec<PoolId>> {
+        let mut routes = vec![];
+        let mut paths: Vec<(Vec<PoolId>, Vec<AssetId>)> = vec![(vec![], vec![asset_in])];
+        for _ in 0..max_hops {
+            let mut next_paths = vec![];
+            for (pools, assets) in paths {
+                let asset = *assets.last().unwrap();
+                for pool in self.pools_with_asset(asset) {
+                    let next_asset = if pool.pool_id.0 == asset {
+                        pool.pool_id.1
+                    } else {
+                        pool.pool_id.0
+                    };
+                    if assets.contains(&next_asset) {
+                        continue;
+                    }
+                    let mut next_pools = pools.clone();
+                    next_pools.push(pool.pool_id);
+                    if next_asset == asset_out {
+                        routes.push(next_pools);
+                    } else {
+                        let mut next_assets = assets.clone();
+                        next_assets.push(next_asset);
+                        next_paths.push((next_pools, next_assets));
+                    }
+                }
+            }
+            paths = next_paths;
+        }
+        routes
+    }
+}
+
+/// Token contracts of every pool created in a transaction, in the order of the
+/// `CreatePoolEvent` logs: `create_pool` reads the decimals of asset 0 then asset 1 from their
+/// contracts right before logging the event
+fn create_pool_contracts(
+    amm_contract_id: ContractId,
+    receipts: &[Receipt],
+) -> Vec<Option<(ContractId, ContractId)>> {
+    let create_pool_log_id = AMM
+        .log_id("struct interfaces::events::CreatePoolEvent")
+        .unwrap();
+    let mut calls = vec![];
+    let mut contracts = vec![];
+    for receipt in receipts {
+        match receipt {
+            Receipt::Call { id, to, .. } if *id == amm_contract_id => calls.push(*to),
+            Receipt::LogData { id, rb, .. }
+                if *id == amm_contract_id && *rb == create_pool_log_id =>
+            {
+                contracts.push(match calls[..] {
+                    [.., contract_0, contract_1] => Some((contract_0, contract_1)),
+                    _ => None,
+                });
+                calls.clear();
+            }
+            _ => {}
+        }
+    }
+    contracts
+}
+
+/// Metadata of `asset` from its token contract, if it was found and implements SRC-20
+async fn token_metadata<A: Account>(
+    amm: &MiraAMM<A>,
+    contract_id: Option<ContractId>,
+    asset: AssetId,
+    decimals: u8,
+) -> TokenMetadata {
+    if let Some(contract_id) = contract_id {
+        let contract = MockToken::new(contract_id, amm.account());
+        if let Some(metadata) = fetch_token_metadata(&contract, asset).await {
+            return metadata;
+        }
+    }
+    TokenMetadata {
+        asset_id: asset,
+        contract_id: None,
+        name: None,
+        symbol: None,
+        decimals,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(byte: u8) -> AssetId {
+        AssetId::new([byte; 32])
+    }
+
+    fn token(asset_id: AssetId) -> TokenMetadata {
+        TokenMetadata {
+            asset_id,
+            contract_id: None,
+            name: None,
+            symbol: None,
+            decimals: 9,
+        }
+    }
+
+    fn registry(pool_ids: &[PoolId]) -> PoolRegistry {
+        PoolRegistry {
+            pools: pool_ids
+                .iter()
+                .map(|pool_id| DiscoveredPool {
+                    pool_id: *pool_id,
+                    created_at: 1,
+                    token_0: token(pool_id.0),
+                    token_1: token(pool_id.1),
+                    lp_token: Some(token(asset(0))),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn finds_pools_by_asset() {
+        let (pool_ab, pool_bc) = ((asset(1), asset(2), false), (asset(2), asset(3), true));
+        let registry = registry(&[pool_ab, pool_bc]);
+
+        let pool_ids = |asset| -> Vec<PoolId> {
+            registry
+                .pools_with_asset(asset)
+                .into_iter()
+                .map(|pool| pool.pool_id)
+                .collect()
+        };
+        assert_eq!(pool_ids(asset(1)), vec![pool_ab]);
+        assert_eq!(pool_ids(asset(2)), vec![pool_ab, pool_bc]);
+        assert_eq!(pool_ids(asset(4)), vec![]);
+        assert_eq!(registry.pool(&pool_bc).unwrap().created_at, 1);
+    }
+
+    #[test]
+    fn routes_shortest_first_without_cycles() {
+        let pool_ab = (asset(1), asset(2), false);
+        let pool_ab_stable = (asset(1), asset(2), true);
+        let pool_bc = (asset(2), asset(3), false);
+        let pool_ac = (asset(1), asset(3), false);
+        let registry = registry(&[pool_ab, pool_ab_stable, pool_bc, pool_ac]);
+
+        assert_eq!(
+            registry.routes(asset(1), asset(3), 3),
+            vec![
+                vec![pool_ac],
+                vec![pool_ab, pool_bc],
+                vec![pool_ab_stable, pool_bc]
+            ]
+        );
+        assert_eq!(registry.routes(asset(1), asset(3), 1), vec![vec![pool_ac]]);
+        assert_eq!(
+            registry.routes(asset(3), asset(2), 2),
+            vec![
+                vec![pool_bc],
+                vec![pool_ac, pool_ab],
+                vec![pool_ac, pool_ab_stable]
+            ]
+        );
+        assert!(registry.routes(asset(1), asset(4), 3).is_empty());
+    }
+
+    #[test]
+    fn finds_token_contracts_of_created_pools() {
+        let amm = ContractId::new([7; 32]);
+        let contract = |byte| ContractId::new([byte; 32]);
+        let call = |id, to| Receipt::Call {
+            id,
+            to,
+            amount: 0,
+            asset_id: AssetId::zeroed(),
+            gas: 0,
+            param1: 0,
+            param2: 0,
+            pc: 0,
+            is: 0,
+        };
+        let create_pool_log = Receipt::LogData {
+            id: amm,
+            ra: 0,
+            rb: AMM
+                .log_id("struct interfaces::events::CreatePoolEvent")
+                .unwrap(),
+            ptr: 0,
+            len: 0,
+            digest: Default::default(),
+            pc: 0,
+            is: 0,
+            data: None,
+        };
+        let receipts = vec![
+            call(ContractId::zeroed(), amm),
+            // only the last two calls before the event read the decimals
+            call(amm, contract(1)),
+            call(amm, contract(2)),
+            call(amm, contract(3)),
+            // calls made by other contracts are ignored
+            call(contract(3), contract(4)),
+            create_pool_log.clone(),
+            call(amm, contract(5)),
+            create_pool_log,
+        ];
+
+        assert_eq!(
+            create_pool_contracts(amm, &receipts),
+            vec![Some((contract(2), contract(3))), None]
+        );
+    }
+}
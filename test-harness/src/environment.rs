@@ -0,0 +1,572 @@
+use fuels::{prelude::*, programs::calls::ContractDependency, types::Bits256};
+
+use crate::{
+    data_structures::{AmmConfiguration, MiraAMMContract, WalletAssetConfiguration},
+    interface::{
+        amm::{create_pool, set_hook},
+        hook::deploy_mock_hook_contract,
+        mock::{add_token, deploy_mock_token_contract, get_sub_id, mint_tokens},
+        scripts::get_transaction_inputs_outputs,
+        AddLiquidityScript, AddLiquidityScriptConfigurables, Asset, BatchSwapScript,
+        BatchSwapScriptConfigurables, CreatePoolAndAddLiquidityScript,
+        CreatePoolAndAddLiquidityScriptConfigurables, MockHook, MockToken, PoolsOverviewScript,
+        PoolsOverviewScriptConfigurables, PositionValuationScript,
+        PositionValuationScriptConfigurables, RemoveLiquidityScript,
+        RemoveLiquidityScriptConfigurables, SwapExactInputScript,
+        SwapExactInputScriptConfigurables, SwapExactInputSplitScript,
+        SwapExactInputSplitScriptConfigurables, SwapExactOutputScript,
+        SwapExactOutputScriptConfigurables,
+    },
+    paths::{
+        ADD_LIQUIDITY_SCRIPT_BINARY_PATH, BATCH_SWAP_SCRIPT_BINARY_PATH,
+        CREATE_POOL_AND_ADD_LIQUIDITY_SCRIPT_BINARY_PATH, POOLS_OVERVIEW_SCRIPT_BINARY_PATH,
+        POSITION_VALUATION_SCRIPT_BINARY_PATH, REMOVE_LIQUIDITY_SCRIPT_BINARY_PATH,
+        SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH, SWAP_EXACT_INPUT_SPLIT_SCRIPT_BINARY_PATH,
+        SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH,
+    },
+    setup::common::{deploy_amm, setup_wallet_and_provider},
+    types::PoolId,
+    utils::common::order_sub_ids,
+};
+
+const DEFAULT_DECIMALS: u8 = 9;
+const DEFAULT_MINT_AMOUNT: u64 = 1_000_000_000;
+/// Number of blocks the deadline of the environment is ahead of the chain
+const DEADLINE_BLOCKS: u32 = 10;
+
+/// Periphery scripts which can be loaded into a `TestEnvironment`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeripheryScript {
+    AddLiquidity,
+    CreatePoolAndAddLiquidity,
+    RemoveLiquidity,
+    SwapExactInput,
+    SwapExactOutput,
+    SwapExactInputSplit,
+    BatchSwap,
+    PositionValuation,
+    PoolsOverview,
+}
+
+/// Pool created by the environment
+#[derive(Debug, Clone, Copy)]
+pub struct PoolConfig {
+    /// Indexes of the pool assets in `TestEnvironment::tokens`
+    pub tokens: (usize, usize),
+    pub is_stable: bool,
+    /// Amounts of `tokens.0` and `tokens.1` deposited through the `add_liquidity_script`
+    pub liquidity: Option<(u64, u64)>,
+}
+
+impl PoolConfig {
+    pub fn volatile(token_0: usize, token_1: usize) -> Self {
+        Self {
+            tokens: (token_0, token_1),
+            is_stable: false,
+            liquidity: None,
+        }
+    }
+
+    pub fn stable(token_0: usize, token_1: usize) -> Self {
+        Self {
+            tokens: (token_0, token_1),
+            is_stable: true,
+            liquidity: None,
+        }
+    }
+
+    pub fn with_liquidity(self, amount_0: u64, amount_1: u64) -> Self {
+        Self {
+            liquidity: Some((amount_0, amount_1)),
+            ..self
+        }
+    }
+}
+
+/// Mock token of the environment
+#[derive(Debug, Clone, Copy)]
+pub struct Token {
+    pub id: AssetId,
+    pub sub_id: Bits256,
+    pub decimals: u8,
+}
+
+/// Loader instances of the periphery scripts, paid for by the main wallet of the environment
+/// unless loaded for another account through `TestEnvironment::scripts_for`
+pub struct Scripts<A: Account = WalletUnlocked> {
+    add_liquidity: Option<AddLiquidityScript<A>>,
+    create_pool_and_add_liquidity: Option<CreatePoolAndAddLiquidityScript<A>>,
+    remove_liquidity: Option<RemoveLiquidityScript<A>>,
+    swap_exact_input: Option<SwapExactInputScript<A>>,
+    swap_exact_output: Option<SwapExactOutputScript<A>>,
+    swap_exact_input_split: Option<SwapExactInputSplitScript<A>>,
+    batch_swap: Option<BatchSwapScript<A>>,
+    position_valuation: Option<PositionValuationScript<A>>,
+    pools_overview: Option<PoolsOverviewScript<A>>,
+}
+
+impl<A: Account> Default for Scripts<A> {
+    fn default() -> Self {
+        Self {
+            add_liquidity: None,
+            create_pool_and_add_liquidity: None,
+            remove_liquidity: None,
+            swap_exact_input: None,
+            swap_exact_output: None,
+            swap_exact_input_split: None,
+            batch_swap: None,
+            position_valuation: None,
+            pools_overview: None,
+        }
+    }
+}
+
+impl<A: Account> Scripts<A> {
+    /// Loads `scripts` for `account`, calling into the AMM deployed at `amm_contract_id`
+    pub async fn new(
+        account: &A,
+        amm_contract_id: ContractId,
+        scripts: &[PeripheryScript],
+    ) -> Self {
+        let mut instances = Self::default();
+        for script in scripts {
+            instances.load(*script, account, amm_contract_id).await;
+        }
+        instances
+    }
+
+    pub fn add_liquidity(&self) -> &AddLiquidityScript<A> {
+        loaded(&self.add_liquidity, PeripheryScript::AddLiquidity)
+    }
+
+    pub fn create_pool_and_add_liquidity(&self) -> &CreatePoolAndAddLiquidityScript<A> {
+        loaded(
+            &self.create_pool_and_add_liquidity,
+            PeripheryScript::CreatePoolAndAddLiquidity,
+        )
+    }
+
+    pub fn remove_liquidity(&self) -> &RemoveLiquidityScript<A> {
+        loaded(&self.remove_liquidity, PeripheryScript::RemoveLiquidity)
+    }
+
+    pub fn swap_exact_input(&self) -> &SwapExactInputScript<A> {
+        loaded(&self.swap_exact_input, PeripheryScript::SwapExactInput)
+    }
+
+    pub fn swap_exact_output(&self) -> &SwapExactOutputScript<A> {
+        loaded(&self.swap_exact_output, PeripheryScript::SwapExactOutput)
+    }
+
+    pub fn swap_exact_input_split(&self) -> &SwapExactInputSplitScript<A> {
+        loaded(
+            &self.swap_exact_input_split,
+            PeripheryScript::SwapExactInputSplit,
+        )
+    }
+
+    pub fn batch_swap(&self) -> &BatchSwapScript<A> {
+        loaded(&self.batch_swap, PeripheryScript::BatchSwap)
+    }
+
+    pub fn position_valuation(&self) -> &PositionValuationScript<A> {
+        loaded(&self.position_valuation, PeripheryScript::PositionValuation)
+    }
+
+    pub fn pools_overview(&self) -> &PoolsOverviewScript<A> {
+        loaded(&self.pools_overview, PeripheryScript::PoolsOverview)
+    }
+
+    async fn load(&mut self, script: PeripheryScript, account: &A, amm_contract_id: ContractId) {
+        match script {
+            PeripheryScript::AddLiquidity => {
+                let configurables = AddLiquidityScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance =
+                    AddLiquidityScript::new(account.clone(), ADD_LIQUIDITY_SCRIPT_BINARY_PATH)
+                        .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.add_liquidity = Some(instance);
+            }
+            PeripheryScript::CreatePoolAndAddLiquidity => {
+                let configurables = CreatePoolAndAddLiquidityScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance = CreatePoolAndAddLiquidityScript::new(
+                    account.clone(),
+                    CREATE_POOL_AND_ADD_LIQUIDITY_SCRIPT_BINARY_PATH,
+                )
+                .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.create_pool_and_add_liquidity = Some(instance);
+            }
+            PeripheryScript::RemoveLiquidity => {
+                let configurables = RemoveLiquidityScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance = RemoveLiquidityScript::new(
+                    account.clone(),
+                    REMOVE_LIQUIDITY_SCRIPT_BINARY_PATH,
+                )
+                .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.remove_liquidity = Some(instance);
+            }
+            PeripheryScript::SwapExactInput => {
+                let configurables = SwapExactInputScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance =
+                    SwapExactInputScript::new(account.clone(), SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH)
+                        .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.swap_exact_input = Some(instance);
+            }
+            PeripheryScript::SwapExactOutput => {
+                let configurables = SwapExactOutputScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance = SwapExactOutputScript::new(
+                    account.clone(),
+                    SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH,
+                )
+                .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.swap_exact_output = Some(instance);
+            }
+            PeripheryScript::SwapExactInputSplit => {
+                let configurables = SwapExactInputSplitScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance = SwapExactInputSplitScript::new(
+                    account.clone(),
+                    SWAP_EXACT_INPUT_SPLIT_SCRIPT_BINARY_PATH,
+                )
+                .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.swap_exact_input_split = Some(instance);
+            }
+            PeripheryScript::BatchSwap => {
+                let configurables = BatchSwapScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance =
+                    BatchSwapScript::new(account.clone(), BATCH_SWAP_SCRIPT_BINARY_PATH)
+                        .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.batch_swap = Some(instance);
+            }
+            PeripheryScript::PositionValuation => {
+                let configurables = PositionValuationScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance = PositionValuationScript::new(
+                    account.clone(),
+                    POSITION_VALUATION_SCRIPT_BINARY_PATH,
+                )
+                .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.position_valuation = Some(instance);
+            }
+            PeripheryScript::PoolsOverview => {
+                let configurables = PoolsOverviewScriptConfigurables::default()
+                    .with_AMM_CONTRACT_ID(amm_contract_id)
+                    .unwrap();
+                let mut instance =
+                    PoolsOverviewScript::new(account.clone(), POOLS_OVERVIEW_SCRIPT_BINARY_PATH)
+                        .with_configurables(configurables);
+                instance.convert_into_loader().await.unwrap();
+                self.pools_overview = Some(instance);
+            }
+        }
+    }
+}
+
+fn loaded<T>(script: &Option<T>, kind: PeripheryScript) -> &T {
+    script
+        .as_ref()
+        .unwrap_or_else(|| panic!("{kind:?} script was not loaded into the environment"))
+}
+
+/// Local node with the AMM deployed and owned by `wallet`, mock tokens minted to `wallet`,
+/// and the configured pools, hook and scripts
+pub struct TestEnvironment {
+    pub provider: Provider,
+    pub wallet: WalletUnlocked,
+    /// Extra wallets, funded with the base asset only
+    pub wallets: Vec<WalletUnlocked>,
+    pub amm: MiraAMMContract,
+    /// Mock hook the AMM calls after every mint, burn and swap, when configured
+    pub hook: Option<MockHook<WalletUnlocked>>,
+    pub token_contract: MockToken<WalletUnlocked>,
+    pub token_contract_id: ContractId,
+    /// Tokens in the order of their configured decimals. Tokens of the same decimals are sorted
+    /// by asset id, so that pools between increasing indexes of them keep their order.
+    pub tokens: Vec<Token>,
+    /// Pools in the order they were configured
+    pub pools: Vec<PoolId>,
+    pub scripts: Scripts,
+    pub deadline: u32,
+}
+
+impl TestEnvironment {
+    pub fn builder() -> TestEnvironmentBuilder {
+        TestEnvironmentBuilder::default()
+    }
+
+    pub fn token(&self, asset_id: AssetId) -> &Token {
+        self.tokens
+            .iter()
+            .find(|token| token.id == asset_id)
+            .expect("Unknown token")
+    }
+
+    /// Loads `scripts` for `account`, which pays for the calls made through them, e.g. a
+    /// predicate or multisig account rather than a wallet.
+    /// The account needs the base asset to upload the script blobs it is the first to use.
+    pub async fn scripts_for<A: Account>(
+        &self,
+        account: &A,
+        scripts: &[PeripheryScript],
+    ) -> Scripts<A> {
+        Scripts::new(account, self.amm.id, scripts).await
+    }
+
+    /// Contracts a call into the AMM depends on: the AMM itself and its hook, if any, which
+    /// the AMM calls on every mint, burn and swap
+    pub fn contracts(&self) -> Vec<&dyn ContractDependency> {
+        let mut contracts: Vec<&dyn ContractDependency> = vec![&self.amm.instance];
+        if let Some(hook) = &self.hook {
+            contracts.push(hook);
+        }
+        contracts
+    }
+
+    /// Deposits into `pool_id` from the main wallet through the `add_liquidity_script`,
+    /// without minimum amounts
+    pub async fn add_liquidity(&self, pool_id: PoolId, amount_0: u64, amount_1: u64) -> Asset {
+        let (inputs, outputs) = get_transaction_inputs_outputs(
+            &self.wallet,
+            &vec![(pool_id.0, amount_0), (pool_id.1, amount_1)],
+        )
+        .await;
+
+        self.scripts
+            .add_liquidity()
+            .main(
+                pool_id,
+                amount_0,
+                amount_1,
+                0,
+                0,
+                self.wallet.address().into(),
+                self.deadline,
+            )
+            .with_contracts(&self.contracts())
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .call()
+            .await
+            .unwrap()
+            .value
+    }
+}
+
+pub struct TestEnvironmentBuilder {
+    token_decimals: Vec<u8>,
+    mint_amount: u64,
+    protocol_fees: Option<(u64, u64)>,
+    hook: bool,
+    pools: Vec<PoolConfig>,
+    wallets: usize,
+    wallet_base_asset_amount: u64,
+    scripts: Vec<PeripheryScript>,
+}
+
+impl Default for TestEnvironmentBuilder {
+    fn default() -> Self {
+        Self {
+            token_decimals: vec![DEFAULT_DECIMALS; 2],
+            mint_amount: DEFAULT_MINT_AMOUNT,
+            protocol_fees: None,
+            hook: false,
+            pools: vec![],
+            wallets: 0,
+            wallet_base_asset_amount: 0,
+            scripts: vec![],
+        }
+    }
+}
+
+impl TestEnvironmentBuilder {
+    /// One mock token per entry, with the given decimals
+    pub fn tokens(mut self, decimals: &[u8]) -> Self {
+        self.token_decimals = decimals.to_vec();
+        self
+    }
+
+    /// Amount of every token minted to the main wallet
+    pub fn mint_amount(mut self, amount: u64) -> Self {
+        self.mint_amount = amount;
+        self
+    }
+
+    /// `volatile_fee` and `stable_fee` protocol fees in basis points, none being charged by default
+    pub fn protocol_fees(mut self, volatile_fee: u64, stable_fee: u64) -> Self {
+        self.protocol_fees = Some((volatile_fee, stable_fee));
+        self
+    }
+
+    /// Installs the mock hook on the AMM before any pool is created
+    pub fn hook(mut self) -> Self {
+        self.hook = true;
+        self
+    }
+
+    pub fn pool(mut self, pool: PoolConfig) -> Self {
+        self.pools.push(pool);
+        self
+    }
+
+    /// `count` extra wallets, each funded with `base_asset_amount` by the main wallet
+    pub fn wallets(mut self, count: usize, base_asset_amount: u64) -> Self {
+        self.wallets = count;
+        self.wallet_base_asset_amount = base_asset_amount;
+        self
+    }
+
+    /// Scripts to load. The `add_liquidity_script` is always loaded when a pool is seeded.
+    pub fn scripts(mut self, scripts: &[PeripheryScript]) -> Self {
+        self.scripts = scripts.to_vec();
+        self
+    }
+
+    pub async fn build(self) -> TestEnvironment {
+        let (wallet, _asset_ids, provider) =
+            setup_wallet_and_provider(&WalletAssetConfiguration::default()).await;
+        let amm = deploy_amm(
+            &wallet,
+            &AmmConfiguration {
+                protocol_fees: self.protocol_fees,
+                ..Default::default()
+            },
+        )
+        .await;
+        let hook = if self.hook {
+            let (hook_id, hook) = deploy_mock_hook_contract(&wallet).await;
+            set_hook(&amm.instance, Some(hook_id)).await;
+            Some(hook)
+        } else {
+            None
+        };
+        let (token_contract_id, token_contract) = deploy_mock_token_contract(&wallet).await;
+
+        let mut tokens = Vec::with_capacity(self.token_decimals.len());
+        for (index, decimals) in self.token_decimals.iter().enumerate() {
+            let letter = char::from(b'A' + u8::try_from(index).unwrap());
+            let id = add_token(
+                &token_contract,
+                format!("TOKEN_{letter}"),
+                format!("TK{letter}"),
+                *decimals,
+            )
+            .await
+            .value;
+            let sub_id = get_sub_id(&token_contract, id).await.value.unwrap();
+            mint_tokens(&token_contract, id, self.mint_amount).await;
+            tokens.push(Token {
+                id,
+                sub_id,
+                decimals: *decimals,
+            });
+        }
+        // tokens of the same decimals are interchangeable, which leaves their ids to be sorted
+        let mut decimals = self.token_decimals.clone();
+        decimals.sort_unstable();
+        decimals.dedup();
+        for decimals in decimals {
+            let indexes: Vec<usize> = (0..tokens.len())
+                .filter(|index| tokens[*index].decimals == decimals)
+                .collect();
+            let mut group: Vec<Token> = indexes.iter().map(|index| tokens[*index]).collect();
+            group.sort_by_key(|token| token.id);
+            for (index, token) in indexes.into_iter().zip(group) {
+                tokens[index] = token;
+            }
+        }
+
+        let mut pools = Vec::with_capacity(self.pools.len());
+        for pool in &self.pools {
+            let (token_0, token_1) = (tokens[pool.tokens.0], tokens[pool.tokens.1]);
+            let (sub_id_0, sub_id_1) =
+                order_sub_ids((token_0.id, token_1.id), (token_0.sub_id, token_1.sub_id));
+            let pool_id = create_pool(
+                &amm.instance,
+                &token_contract,
+                token_contract_id,
+                sub_id_0,
+                token_contract_id,
+                sub_id_1,
+                pool.is_stable,
+            )
+            .await
+            .value;
+            pools.push(pool_id);
+        }
+
+        let mut wallets = Vec::with_capacity(self.wallets);
+        for _ in 0..self.wallets {
+            let extra_wallet = WalletUnlocked::new_random(Some(provider.clone()));
+            wallet
+                .transfer(
+                    extra_wallet.address(),
+                    self.wallet_base_asset_amount,
+                    *provider.base_asset_id(),
+                    TxPolicies::default(),
+                )
+                .await
+                .unwrap();
+            wallets.push(extra_wallet);
+        }
+
+        let mut scripts_to_load = self.scripts.clone();
+        let seeds_pools = self.pools.iter().any(|pool| pool.liquidity.is_some());
+        if seeds_pools && !scripts_to_load.contains(&PeripheryScript::AddLiquidity) {
+            scripts_to_load.push(PeripheryScript::AddLiquidity);
+        }
+        let scripts = Scripts::new(&wallet, amm.id, &scripts_to_load).await;
+
+        let deadline = provider.latest_block_height().await.unwrap() + DEADLINE_BLOCKS;
+
+        let environment = TestEnvironment {
+            provider,
+            wallet,
+            wallets,
+            amm,
+            hook,
+            token_contract,
+            token_contract_id,
+            tokens,
+            pools,
+            scripts,
+            deadline,
+        };
+
+        for (pool, pool_id) in self.pools.iter().zip(&environment.pools) {
+            if let Some((amount_0, amount_1)) = pool.liquidity {
+                let (amount_0, amount_1) = if pool_id.0 == environment.tokens[pool.tokens.0].id {
+                    (amount_0, amount_1)
+                } else {
+                    (amount_1, amount_0)
+                };
+                environment
+                    .add_liquidity(*pool_id, amount_0, amount_1)
+                    .await;
+            }
+        }
+
+        environment
+    }
+}
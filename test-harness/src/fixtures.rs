@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+
+use fuels::{
+    core::Configurables,
+    prelude::{Account, Bech32ContractId, Contract, TxPolicies},
+    tx::StorageSlot,
+    types::Salt,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+const MANIFEST: &str = include_str!("../../fixtures/manifest.json");
+
+/// Core contract build shipped in `fixtures`, embedded so that it can be deployed from any
+/// working directory
+pub struct Fixture {
+    /// Path of the artifacts in `fixtures`, without their suffix
+    pub name: &'static str,
+    pub binary: &'static [u8],
+    pub abi: &'static str,
+    pub storage_slots: &'static str,
+}
+
+pub const AMM: Fixture = Fixture {
+    name: "mira-amm/mira_amm_contract",
+    binary: include_bytes!("../../fixtures/mira-amm/mira_amm_contract.bin"),
+    abi: include_str!("../../fixtures/mira-amm/mira_amm_contract-abi.json"),
+    storage_slots: include_str!("../../fixtures/mira-amm/mira_amm_contract-storage_slots.json"),
+};
+
+pub const MOCK_TOKEN: Fixture = Fixture {
+    name: "mock-token/mock_token",
+    binary: include_bytes!("../../fixtures/mock-token/mock_token.bin"),
+    abi: include_str!("../../fixtures/mock-token/mock_token-abi.json"),
+    storage_slots: include_str!("../../fixtures/mock-token/mock_token-storage_slots.json"),
+};
+
+/// `fixtures/manifest.json`, written by `tools/fetch_abis.sh`
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    /// Branch and commit of mira-v1-core the fixtures were built from, as `<branch>@<commit>`
+    pub core_version: String,
+    /// SHA-256 of every artifact, by path in `fixtures`
+    pub artifacts: BTreeMap<String, String>,
+    /// SHA-256 of the ABI every binary was built with, by SHA-256 of the binary
+    pub abis: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Commit of mira-v1-core, `unknown` for fixtures which weren't rebuilt since the manifest
+    /// was introduced
+    pub fn core_commit(&self) -> &str {
+        self.core_version
+            .split_once('@')
+            .map(|(_, commit)| commit)
+            .expect("core_version isn't `<branch>@<commit>`")
+    }
+}
+
+pub fn manifest() -> Manifest {
+    serde_json::from_str(MANIFEST).unwrap()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Abi {
+    functions: Vec<AbiFunction>,
+    concrete_types: Vec<AbiConcreteType>,
+    logged_types: Vec<AbiLoggedType>,
+}
+
+#[derive(Deserialize)]
+struct AbiFunction {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiConcreteType {
+    #[serde(rename = "type")]
+    type_name: String,
+    concrete_type_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiLoggedType {
+    log_id: String,
+    concrete_type_id: String,
+}
+
+impl Fixture {
+    /// Artifacts by path in `fixtures`
+    pub fn artifacts(&self) -> [(String, &'static [u8]); 3] {
+        [
+            (format!("{}.bin", self.name), self.binary),
+            (format!("{}-abi.json", self.name), self.abi.as_bytes()),
+            (
+                format!("{}-storage_slots.json", self.name),
+                self.storage_slots.as_bytes(),
+            ),
+        ]
+    }
+
+    pub fn parsed_storage_slots(&self) -> Vec<StorageSlot> {
+        serde_json::from_str(self.storage_slots).unwrap()
+    }
+
+    /// Checks the artifacts against the manifest, and that the ABI is the one the binary was
+    /// built with, as contracts match calls by function selector
+    pub fn check(&self, manifest: &Manifest) -> Result<(), String> {
+        for (path, artifact) in self.artifacts() {
+            let expected = manifest
+                .artifacts
+                .get(&path)
+                .ok_or_else(|| format!("`{path}` is missing from the fixtures manifest"))?;
+            let actual = sha256(artifact);
+            if *expected != actual {
+                return Err(format!(
+                    "`{path}` has SHA-256 {actual} but the manifest expects {expected}"
+                ));
+            }
+        }
+
+        let binary = sha256(self.binary);
+        let expected = manifest.abis.get(&binary).ok_or_else(|| {
+            format!(
+                "`{}` binary {binary} has no ABI in the fixtures manifest",
+                self.name
+            )
+        })?;
+        let actual = sha256(self.abi.as_bytes());
+        if *expected != actual {
+            return Err(format!(
+                "`{}` ABI has SHA-256 {actual} but its binary was built with {expected}",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Id of the logs of `type_name`, e.g. `struct interfaces::events::SwapEvent`, as found in
+    /// the `rb` register of their `LogData` receipts
+    pub fn log_id(&self, type_name: &str) -> Option<u64> {
+        let abi: Abi = serde_json::from_str(self.abi).unwrap();
+        let concrete_type = abi
+            .concrete_types
+            .into_iter()
+            .find(|concrete_type| concrete_type.type_name == type_name)?;
+        abi.logged_types
+            .into_iter()
+            .find(|logged_type| logged_type.concrete_type_id == concrete_type.concrete_type_id)
+            .map(|logged_type| logged_type.log_id.parse().unwrap())
+    }
+
+    /// Deploys the contract with its storage slots, after checking the fixture.
+    /// Panics if the artifacts don't match the manifest or each other.
+    pub async fn deploy(&self, account: &impl Account) -> Bech32ContractId {
+        self.deploy_with(account, Configurables::default(), Salt::default())
+            .await
+    }
+
+    /// `deploy` with `configurables` applied to the checked binary, under `salt` so that
+    /// several instances of the same binary can live on one node
+    pub async fn deploy_with(
+        &self,
+        account: &impl Account,
+        configurables: impl Into<Configurables>,
+        salt: Salt,
+    ) -> Bech32ContractId {
+        let manifest = manifest();
+        if let Err(error) = self.check(&manifest) {
+            panic!(
+                "Invalid fixture for mira-v1-core {}: {error}. Run `tools/fetch_abis.sh` to rebuild the fixtures.",
+                manifest.core_version
+            );
+        }
+        Contract::regular(self.binary.to_vec(), salt, self.parsed_storage_slots())
+            .with_configurables(configurables)
+            .deploy(account, TxPolicies::default())
+            .await
+            .unwrap()
+    }
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_fixtures_match_manifest() {
+        let manifest = manifest();
+
+        assert_eq!(manifest.artifacts.len(), 6);
+        assert_eq!(manifest.abis.len(), 2);
+        assert!(manifest.core_version.starts_with("mainnet-deployment@"));
+        assert!(!manifest.core_commit().is_empty());
+        assert_eq!(AMM.check(&manifest), Ok(()));
+        assert_eq!(MOCK_TOKEN.check(&manifest), Ok(()));
+    }
+
+    #[test]
+    fn rejects_artifact_differing_from_manifest() {
+        let mut manifest = manifest();
+        manifest.artifacts.insert(
+            "mira-amm/mira_amm_contract.bin".to_string(),
+            "00".repeat(32),
+        );
+
+        assert!(AMM.check(&manifest).unwrap_err().contains("SHA-256"));
+        assert_eq!(MOCK_TOKEN.check(&manifest), Ok(()));
+    }
+
+    #[test]
+    fn rejects_abi_of_another_binary() {
+        let mismatched = Fixture {
+            abi: MOCK_TOKEN.abi,
+            ..AMM
+        };
+        let mut manifest = manifest();
+        manifest.artifacts.insert(
+            "mira-amm/mira_amm_contract-abi.json".to_string(),
+            sha256(MOCK_TOKEN.abi.as_bytes()),
+        );
+
+        let error = mismatched.check(&manifest).unwrap_err();
+        assert!(error.contains("but its binary was built with"));
+        assert!(error.contains(&manifest.abis[&sha256(AMM.binary)]));
+    }
+
+    #[test]
+    fn finds_log_ids_of_events() {
+        assert_eq!(
+            AMM.log_id("struct interfaces::events::SwapEvent"),
+            Some(7_938_487_056_892_321_597)
+        );
+        assert_eq!(
+            AMM.log_id("struct interfaces::events::BurnEvent"),
+            Some(3_903_049_024_573_527_731)
+        );
+        assert_eq!(AMM.log_id("struct interfaces::events::UnknownEvent"), None);
+    }
+}
@@ -1,10 +1,12 @@
 use fuels::{
+    core::Configurables,
     prelude::*,
-    programs::responses::CallResponse,
-    types::{input::Input, output::Output, Bits256},
+    programs::{calls::Execution, responses::CallResponse},
+    types::{input::Input, output::Output, Bits256, Salt},
 };
 
-use crate::paths::MOCK_TOKEN_CONTRACT_BINARY_PATH;
+use crate::fixtures::MOCK_TOKEN;
+use crate::paths::{MOCK_HOOK_CONTRACT_BINARY_PATH, TWAP_ORACLE_CONTRACT_BINARY_PATH};
 
 use crate::types::PoolId;
 
@@ -13,6 +15,10 @@ abigen!(
         name = "AddLiquidityScript",
         abi = "scripts/add_liquidity_script/out/debug/add_liquidity_script-abi.json"
     ),
+    Script(
+        name = "CreatePoolAndAddLiquidityScript",
+        abi = "scripts/create_pool_and_add_liquidity_script/out/debug/create_pool_and_add_liquidity_script-abi.json"
+    ),
     Script(
         name = "RemoveLiquidityScript",
         abi = "scripts/remove_liquidity_script/out/debug/remove_liquidity_script-abi.json"
@@ -25,6 +31,30 @@ abigen!(
         name = "SwapExactOutputScript",
         abi = "scripts/swap_exact_output_script/out/debug/swap_exact_output_script-abi.json"
     ),
+    Script(
+        name = "SwapExactInputSplitScript",
+        abi = "scripts/swap_exact_input_split_script/out/debug/swap_exact_input_split_script-abi.json"
+    ),
+    Script(
+        name = "BatchSwapScript",
+        abi = "scripts/batch_swap_script/out/debug/batch_swap_script-abi.json"
+    ),
+    Script(
+        name = "PositionValuationScript",
+        abi = "scripts/position_valuation_script/out/debug/position_valuation_script-abi.json"
+    ),
+    Script(
+        name = "PoolsOverviewScript",
+        abi = "scripts/pools_overview_script/out/debug/pools_overview_script-abi.json"
+    ),
+    Predicate(
+        name = "DcaPredicate",
+        abi = "predicates/dca_predicate/out/debug/dca_predicate-abi.json"
+    ),
+    Predicate(
+        name = "LimitOrderPredicate",
+        abi = "predicates/limit_order_predicate/out/debug/limit_order_predicate-abi.json"
+    ),
     Contract(
         name = "MiraAMM",
         abi = "fixtures/mira-amm/mira_amm_contract-abi.json"
@@ -32,6 +62,14 @@ abigen!(
     Contract(
         name = "MockToken",
         abi = "fixtures/mock-token/mock_token-abi.json"
+    ),
+    Contract(
+        name = "MockHook",
+        abi = "contracts/mock_hook_contract/out/debug/mock_hook_contract-abi.json"
+    ),
+    Contract(
+        name = "TwapOracle",
+        abi = "contracts/twap_oracle_contract/out/debug/twap_oracle_contract-abi.json"
     )
 );
 
@@ -39,8 +77,8 @@ pub mod amm {
     use super::*;
     use fuels::types::Identity;
 
-    pub async fn initialize_ownership(
-        contract: &MiraAMM<WalletUnlocked>,
+    pub async fn initialize_ownership<A: Account>(
+        contract: &MiraAMM<A>,
         owner: Identity,
     ) -> CallResponse<()> {
         contract
@@ -51,9 +89,9 @@ pub mod amm {
             .unwrap()
     }
 
-    pub async fn create_pool(
-        contract: &MiraAMM<WalletUnlocked>,
-        token_contract: &MockToken<WalletUnlocked>,
+    pub async fn create_pool<A: Account, T: Account>(
+        contract: &MiraAMM<A>,
+        token_contract: &MockToken<T>,
         token_0_contract_id: ContractId,
         token_0_sub_id: Bits256,
         token_1_contract_id: ContractId,
@@ -75,8 +113,50 @@ pub mod amm {
             .unwrap()
     }
 
-    pub async fn pool_metadata(
-        contract: &MiraAMM<WalletUnlocked>,
+    pub async fn mint<A: Account>(
+        contract: &MiraAMM<A>,
+        pool_id: PoolId,
+        to: Identity,
+    ) -> CallResponse<Asset> {
+        contract
+            .methods()
+            .mint(pool_id, to)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn fees<A: Account>(contract: &MiraAMM<A>) -> CallResponse<(u64, u64, u64, u64)> {
+        contract.methods().fees().call().await.unwrap()
+    }
+
+    pub async fn set_protocol_fees<A: Account>(
+        contract: &MiraAMM<A>,
+        volatile_fee: u64,
+        stable_fee: u64,
+    ) -> CallResponse<()> {
+        contract
+            .methods()
+            .set_protocol_fees(volatile_fee, stable_fee)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn set_hook<A: Account>(
+        contract: &MiraAMM<A>,
+        hook: Option<ContractId>,
+    ) -> CallResponse<()> {
+        contract.methods().set_hook(hook).call().await.unwrap()
+    }
+
+    pub async fn hook<A: Account>(contract: &MiraAMM<A>) -> CallResponse<Option<ContractId>> {
+        contract.methods().hook().call().await.unwrap()
+    }
+
+    pub async fn pool_metadata<A: Account>(
+        contract: &MiraAMM<A>,
         pool_id: PoolId,
     ) -> CallResponse<Option<PoolMetadata>> {
         contract
@@ -86,31 +166,112 @@ pub mod amm {
             .await
             .unwrap()
     }
+
+    /// SRC-20 name of an LP asset
+    pub async fn name<A: Account>(
+        contract: &MiraAMM<A>,
+        asset: AssetId,
+    ) -> CallResponse<Option<String>> {
+        contract.methods().name(asset).call().await.unwrap()
+    }
+
+    pub async fn symbol<A: Account>(
+        contract: &MiraAMM<A>,
+        asset: AssetId,
+    ) -> CallResponse<Option<String>> {
+        contract.methods().symbol(asset).call().await.unwrap()
+    }
+
+    pub async fn decimals<A: Account>(
+        contract: &MiraAMM<A>,
+        asset: AssetId,
+    ) -> CallResponse<Option<u8>> {
+        contract.methods().decimals(asset).call().await.unwrap()
+    }
+
+    /// `decimals`, simulated so that a failing call reads as a miss
+    pub async fn try_decimals<A: Account>(contract: &MiraAMM<A>, asset: AssetId) -> Option<u8> {
+        let call = contract.methods().decimals(asset);
+        call.simulate(Execution::StateReadOnly).await.ok()?.value
+    }
+
+    pub async fn try_name<A: Account>(contract: &MiraAMM<A>, asset: AssetId) -> Option<String> {
+        let call = contract.methods().name(asset);
+        call.simulate(Execution::StateReadOnly).await.ok()?.value
+    }
+
+    pub async fn try_symbol<A: Account>(contract: &MiraAMM<A>, asset: AssetId) -> Option<String> {
+        let call = contract.methods().symbol(asset);
+        call.simulate(Execution::StateReadOnly).await.ok()?.value
+    }
+}
+
+/// SRC-20 metadata calls, made through the mock token ABI which any SRC-20 contract answers
+pub mod src20 {
+    use super::*;
+
+    pub async fn name<A: Account>(
+        contract: &MockToken<A>,
+        asset: AssetId,
+    ) -> CallResponse<Option<String>> {
+        contract.methods().name(asset).call().await.unwrap()
+    }
+
+    pub async fn symbol<A: Account>(
+        contract: &MockToken<A>,
+        asset: AssetId,
+    ) -> CallResponse<Option<String>> {
+        contract.methods().symbol(asset).call().await.unwrap()
+    }
+
+    pub async fn decimals<A: Account>(
+        contract: &MockToken<A>,
+        asset: AssetId,
+    ) -> CallResponse<Option<u8>> {
+        contract.methods().decimals(asset).call().await.unwrap()
+    }
+
+    /// `decimals`, simulated so that a contract which doesn't implement SRC-20, or reverts,
+    /// reads as a miss
+    pub async fn try_decimals<A: Account>(contract: &MockToken<A>, asset: AssetId) -> Option<u8> {
+        let call = contract.methods().decimals(asset);
+        call.simulate(Execution::StateReadOnly).await.ok()?.value
+    }
+
+    pub async fn try_name<A: Account>(contract: &MockToken<A>, asset: AssetId) -> Option<String> {
+        let call = contract.methods().name(asset);
+        call.simulate(Execution::StateReadOnly).await.ok()?.value
+    }
+
+    pub async fn try_symbol<A: Account>(contract: &MockToken<A>, asset: AssetId) -> Option<String> {
+        let call = contract.methods().symbol(asset);
+        call.simulate(Execution::StateReadOnly).await.ok()?.value
+    }
 }
 
 pub mod mock {
     use super::*;
 
-    pub async fn deploy_mock_token_contract(
-        wallet: &WalletUnlocked,
-    ) -> (ContractId, MockToken<WalletUnlocked>) {
-        let contract_id = Contract::load_from(
-            MOCK_TOKEN_CONTRACT_BINARY_PATH,
-            LoadConfiguration::default(),
-        )
-        .unwrap()
-        .deploy(wallet, TxPolicies::default())
-        .await
-        .unwrap();
+    pub async fn deploy_mock_token_contract<A: Account>(account: &A) -> (ContractId, MockToken<A>) {
+        deploy_mock_token_contract_with_salt(account, Salt::default()).await
+    }
+
+    pub async fn deploy_mock_token_contract_with_salt<A: Account>(
+        account: &A,
+        salt: Salt,
+    ) -> (ContractId, MockToken<A>) {
+        let contract_id = MOCK_TOKEN
+            .deploy_with(account, Configurables::default(), salt)
+            .await;
 
         let id = ContractId::from(contract_id.clone());
-        let instance = MockToken::new(contract_id, wallet.clone());
+        let instance = MockToken::new(contract_id, account.clone());
 
         (id, instance)
     }
 
-    pub async fn add_token(
-        contract: &MockToken<WalletUnlocked>,
+    pub async fn add_token<A: Account>(
+        contract: &MockToken<A>,
         name: String,
         symbol: String,
         decimals: u8,
@@ -123,8 +284,8 @@ pub mod mock {
             .unwrap()
     }
 
-    pub async fn mint_tokens(
-        contract: &MockToken<WalletUnlocked>,
+    pub async fn mint_tokens<A: Account>(
+        contract: &MockToken<A>,
         asset_id: AssetId,
         amount: u64,
     ) -> CallResponse<()> {
@@ -137,8 +298,8 @@ pub mod mock {
             .unwrap()
     }
 
-    pub async fn get_sub_id(
-        contract: &MockToken<WalletUnlocked>,
+    pub async fn get_sub_id<A: Account>(
+        contract: &MockToken<A>,
         asset_id: AssetId,
     ) -> CallResponse<Option<Bits256>> {
         contract
@@ -150,20 +311,158 @@ pub mod mock {
     }
 }
 
+pub mod hook {
+    use super::*;
+
+    pub async fn deploy_mock_hook_contract<A: Account>(account: &A) -> (ContractId, MockHook<A>) {
+        let contract_id =
+            Contract::load_from(MOCK_HOOK_CONTRACT_BINARY_PATH, LoadConfiguration::default())
+                .unwrap()
+                .deploy(account, TxPolicies::default())
+                .await
+                .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = MockHook::new(contract_id, account.clone());
+
+        (id, instance)
+    }
+
+    pub async fn calls<A: Account>(contract: &MockHook<A>) -> CallResponse<u64> {
+        contract.methods().calls().call().await.unwrap()
+    }
+
+    pub async fn last_call<A: Account>(contract: &MockHook<A>) -> CallResponse<Option<HookCall>> {
+        contract.methods().last_call().call().await.unwrap()
+    }
+}
+
+pub mod oracle {
+    use super::*;
+    use fuels::types::U256;
+
+    /// Deploys the TWAP oracle reading the pools of `amm_contract_id`, keeping
+    /// `initial_cardinality` observations per pool instead of the contract default if given
+    pub async fn deploy_twap_oracle_contract<A: Account>(
+        account: &A,
+        amm_contract_id: ContractId,
+        initial_cardinality: Option<u64>,
+    ) -> (ContractId, TwapOracle<A>) {
+        let mut configurables = TwapOracleConfigurables::default()
+            .with_AMM_CONTRACT_ID(amm_contract_id)
+            .unwrap();
+        if let Some(cardinality) = initial_cardinality {
+            configurables = configurables.with_INITIAL_CARDINALITY(cardinality).unwrap();
+        }
+        let contract_id = Contract::load_from(
+            TWAP_ORACLE_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default().with_configurables(configurables),
+        )
+        .unwrap()
+        .deploy(account, TxPolicies::default())
+        .await
+        .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = TwapOracle::new(contract_id, account.clone());
+
+        (id, instance)
+    }
+
+    /// Keeper update of `pool_id`, for oracles which aren't the AMM hook
+    pub async fn update<A: Account, B: Account>(
+        contract: &TwapOracle<A>,
+        amm: &MiraAMM<B>,
+        pool_id: PoolId,
+    ) -> CallResponse<()> {
+        contract
+            .methods()
+            .update(pool_id)
+            .with_contracts(&[amm])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    /// Grows the ring of `pool_id` to `cardinality` observations, as a keeper would
+    pub async fn increase_cardinality<A: Account>(
+        contract: &TwapOracle<A>,
+        pool_id: PoolId,
+        cardinality: u64,
+    ) -> CallResponse<()> {
+        contract
+            .methods()
+            .increase_cardinality(pool_id, cardinality)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn cardinality<A: Account>(
+        contract: &TwapOracle<A>,
+        pool_id: PoolId,
+    ) -> CallResponse<(u64, u64)> {
+        contract
+            .methods()
+            .cardinality(pool_id)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn consult<A: Account>(
+        contract: &TwapOracle<A>,
+        pool_id: PoolId,
+        window: u64,
+    ) -> CallResponse<Option<(U256, U256)>> {
+        contract
+            .methods()
+            .consult(pool_id, window)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn observation_count<A: Account>(
+        contract: &TwapOracle<A>,
+        pool_id: PoolId,
+    ) -> CallResponse<u64> {
+        contract
+            .methods()
+            .observation_count(pool_id)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn observation<A: Account>(
+        contract: &TwapOracle<A>,
+        pool_id: PoolId,
+        index: u64,
+    ) -> CallResponse<Option<Observation>> {
+        contract
+            .methods()
+            .observation(pool_id, index)
+            .call()
+            .await
+            .unwrap()
+    }
+}
+
 pub mod scripts {
     use super::*;
 
     pub const MAXIMUM_INPUT_AMOUNT: u64 = 100_000;
 
-    pub async fn get_transaction_inputs_outputs(
-        wallet: &WalletUnlocked,
+    pub async fn get_transaction_inputs_outputs<A: Account>(
+        account: &A,
         assets: &Vec<(AssetId, u64)>,
     ) -> (Vec<Input>, Vec<Output>) {
-        let mut inputs: Vec<Input> = vec![]; // capacity depends on wallet resources
+        let mut inputs: Vec<Input> = vec![]; // capacity depends on account resources
         let mut outputs: Vec<Output> = Vec::with_capacity(assets.len());
 
         for (asset, amount) in assets {
-            let asset_inputs = wallet
+            let asset_inputs = account
                 .get_asset_inputs_for_amount(*asset, *amount, None)
                 .await
                 .unwrap();
@@ -171,9 +470,22 @@ pub mod scripts {
             outputs.push(Output::Change {
                 asset_id: *asset,
                 amount: 0,
-                to: wallet.address().into(),
+                to: account.address().into(),
             });
         }
         (inputs, outputs)
     }
+
+    pub async fn pools_overview<A: Account, C: Account>(
+        script: &PoolsOverviewScript<A>,
+        amm: &MiraAMM<C>,
+        pool_ids: Vec<PoolId>,
+    ) -> CallResponse<AmmOverview> {
+        script
+            .main(pool_ids)
+            .with_contracts(&[amm])
+            .call()
+            .await
+            .unwrap()
+    }
 }
@@ -1,7 +1,20 @@
+pub mod analytics;
+pub mod assets;
 pub mod data_structures;
+pub mod dca;
+pub mod discovery;
+pub mod environment;
+pub mod fixtures;
 pub mod interface;
 pub mod math;
+pub mod matrix;
+pub mod order_book;
 pub mod paths;
+pub mod pnl;
+pub mod property;
+pub mod quote;
 pub mod setup;
+pub mod slippage;
 pub mod types;
 pub mod utils;
+pub mod valuation;
@@ -1,4 +1,9 @@
-use fuels::types::U256;
+use fuels::types::{AssetId, U256};
+
+use crate::{interface::PoolMetadata, types::PoolId, utils::common::MINIMUM_LIQUIDITY};
+
+/// Fees, price impacts and tolerances are expressed in basis points of this denominator
+pub const BASIS_POINTS_DENOMINATOR: u64 = 10_000;
 
 pub fn proportional_value(numerator_1: u64, numerator_2: u64, denominator: u64) -> u64 {
     u64::try_from(U256::from(numerator_1) * U256::from(numerator_2) / U256::from(denominator))
@@ -9,3 +14,848 @@ pub fn initial_liquidity(deposit_0: u64, deposit_1: u64) -> u64 {
     let product = U256::from(deposit_0) * U256::from(deposit_1);
     u64::try_from(product.integer_sqrt()).unwrap()
 }
+
+/// Liquidity minted by the AMM for a deposit, `total_liquidity` including the locked minimum
+pub fn get_mint_liquidity(
+    amount_0: u64,
+    amount_1: u64,
+    reserve_0: u64,
+    reserve_1: u64,
+    total_liquidity: u64,
+) -> u64 {
+    if total_liquidity == 0 {
+        initial_liquidity(amount_0, amount_1) - MINIMUM_LIQUIDITY
+    } else {
+        proportional_value(amount_0, total_liquidity, reserve_0).min(proportional_value(
+            amount_1,
+            total_liquidity,
+            reserve_1,
+        ))
+    }
+}
+
+// Rust port of the deposit math of `libraries/math/src/pool_math.sw`
+
+/// Deposit amounts for `pool`: the desired ones for the first deposit, the smallest ones minting
+/// as much liquidity as the desired ones afterwards
+pub fn get_pool_deposit_amounts(
+    pool: &PoolMetadata,
+    amount_0_desired: u64,
+    amount_1_desired: u64,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> (u64, u64) {
+    assert!(
+        amount_0_desired > 0 && amount_1_desired > 0,
+        "Insufficient amount"
+    );
+
+    let total_liquidity = U256::from(pool.liquidity.amount);
+    let (amount_0, amount_1) = if total_liquidity.is_zero() {
+        (amount_0_desired, amount_1_desired)
+    } else {
+        assert!(
+            pool.reserve_0 > 0 && pool.reserve_1 > 0,
+            "Insufficient liquidity"
+        );
+        let (reserve_0, reserve_1) = (U256::from(pool.reserve_0), U256::from(pool.reserve_1));
+        let liquidity = (U256::from(amount_0_desired) * total_liquidity / reserve_0)
+            .min(U256::from(amount_1_desired) * total_liquidity / reserve_1);
+        assert!(!liquidity.is_zero(), "Insufficient liquidity minted");
+        (
+            u64::try_from(rounding_up_division(liquidity * reserve_0, total_liquidity)).unwrap(),
+            u64::try_from(rounding_up_division(liquidity * reserve_1, total_liquidity)).unwrap(),
+        )
+    };
+    assert!(
+        amount_0 >= amount_0_min && amount_1 >= amount_1_min,
+        "Insufficient amount"
+    );
+    (amount_0, amount_1)
+}
+
+/// Amounts of the pool assets redeemed by burning `liquidity` out of `total_liquidity`
+pub fn get_burn_amounts(
+    liquidity: u64,
+    reserve_0: u64,
+    reserve_1: u64,
+    total_liquidity: u64,
+) -> (u64, u64) {
+    assert!(total_liquidity > 0, "Insufficient liquidity");
+    assert!(liquidity <= total_liquidity, "Liquidity exceeds supply");
+    (
+        proportional_value(liquidity, reserve_0, total_liquidity),
+        proportional_value(liquidity, reserve_1, total_liquidity),
+    )
+}
+
+/// Splits `amount` by weight the way the `swap_exact_input_split_script` allocates its output:
+/// shares are rounded down and the last one gets the remainder
+pub fn split_by_weight(amount: u64, weights: &[u16]) -> Vec<u64> {
+    let total_weight: u64 = weights.iter().map(|weight| u64::from(*weight)).sum();
+    assert!(total_weight > 0, "Zero total weight");
+
+    let mut amount_left = amount;
+    let mut shares = Vec::with_capacity(weights.len());
+    for (i, weight) in weights.iter().enumerate() {
+        let share = if i == weights.len() - 1 {
+            amount_left
+        } else {
+            proportional_value(amount, u64::from(*weight), total_weight)
+        };
+        amount_left -= share;
+        shares.push(share);
+    }
+    shares
+}
+
+// Rust port of `libraries/math/src/pool_math.sw`, kept in sync with the Sway implementation
+
+fn one_e_18() -> U256 {
+    U256::exp10(18)
+}
+
+/// Upper bound of the 1e18 adjusted amounts the stable curve can be evaluated at without
+/// overflowing, i.e. 1e10 whole tokens
+fn max_adjusted() -> U256 {
+    U256::exp10(28)
+}
+
+/// Error of the stable curve solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverError {
+    /// Neither Newton's method nor bisection found `y` within the curve domain
+    NoConvergence,
+}
+
+pub fn pow_decimals(decimals: u8) -> U256 {
+    U256::from(10).pow(U256::from(decimals))
+}
+
+fn adjust(amount: U256, pow_decimals: U256) -> U256 {
+    amount * one_e_18() / pow_decimals
+}
+
+pub fn get_amount_out(
+    is_stable: bool,
+    reserve_in: U256,
+    reserve_out: U256,
+    pow_decimals_in: U256,
+    pow_decimals_out: U256,
+    input_amount: U256,
+) -> U256 {
+    if is_stable {
+        let xy = k(
+            true,
+            reserve_in,
+            reserve_out,
+            pow_decimals_in,
+            pow_decimals_out,
+        );
+
+        let amount_in_adjusted = adjust(input_amount, pow_decimals_in);
+        let reserve_in_adjusted = adjust(reserve_in, pow_decimals_in);
+        let reserve_out_adjusted = adjust(reserve_out, pow_decimals_out);
+        let y = get_y(
+            amount_in_adjusted + reserve_in_adjusted,
+            xy,
+            reserve_out_adjusted,
+        )
+        .expect("NoConvergence");
+        // the curve is too coarse for dust inputs to move `y` below the reserve
+        if y >= reserve_out_adjusted {
+            return U256::zero();
+        }
+        (reserve_out_adjusted - y) * pow_decimals_out / one_e_18()
+    } else {
+        input_amount * reserve_out / (reserve_in + input_amount)
+    }
+}
+
+pub fn get_amount_in(
+    is_stable: bool,
+    reserve_in: U256,
+    reserve_out: U256,
+    pow_decimals_in: U256,
+    pow_decimals_out: U256,
+    output_amount: U256,
+) -> U256 {
+    if is_stable {
+        let xy = k(
+            true,
+            reserve_in,
+            reserve_out,
+            pow_decimals_in,
+            pow_decimals_out,
+        );
+
+        let amount_out_adjusted = adjust(output_amount, pow_decimals_out);
+        let reserve_in_adjusted = adjust(reserve_in, pow_decimals_in);
+        let reserve_out_adjusted = adjust(reserve_out, pow_decimals_out);
+        let y = get_y(
+            reserve_out_adjusted - amount_out_adjusted,
+            xy,
+            reserve_in_adjusted,
+        )
+        .expect("NoConvergence")
+            - reserve_in_adjusted;
+        rounding_up_division(y * pow_decimals_in, one_e_18())
+    } else {
+        rounding_up_division(output_amount * reserve_in, reserve_out - output_amount)
+    }
+}
+
+/// Price of one whole unit of the input asset in whole units of the output asset, scaled by 1e18:
+/// the reserves ratio for volatile pools, the slope of `x^3y + y^3x` for stable ones
+pub fn spot_price(
+    is_stable: bool,
+    reserve_in: U256,
+    reserve_out: U256,
+    pow_decimals_in: U256,
+    pow_decimals_out: U256,
+) -> U256 {
+    let x = adjust(reserve_in, pow_decimals_in);
+    let y = adjust(reserve_out, pow_decimals_out);
+    assert!(!x.is_zero() && !y.is_zero(), "Insufficient liquidity");
+    if is_stable {
+        // (dk/dx) / (dk/dy) = (3x^2y + y^3) / (x^3 + 3xy^2)
+        let denominator = d(x, y);
+        assert!(!denominator.is_zero(), "Insufficient liquidity");
+        d(y, x) * one_e_18() / denominator
+    } else {
+        y * one_e_18() / x
+    }
+}
+
+/// Value of `amounts` in the first asset of a pool holding `reserves`, at its spot price,
+/// rounded down. `None` when it doesn't fit in a `u128`, as for a pool far off its peg.
+pub fn value_in_asset_0(
+    is_stable: bool,
+    reserves: (u64, u64),
+    decimals: (u8, u8),
+    amounts: (u64, u64),
+) -> Option<u128> {
+    let (reserve_0, reserve_1) = reserves;
+    let (amount_0, amount_1) = amounts;
+    if reserve_0 == 0 || reserve_1 == 0 {
+        return Some(amount_0.into());
+    }
+    let amount_1_value = if is_stable {
+        let (pow_decimals_0, pow_decimals_1) = (pow_decimals(decimals.0), pow_decimals(decimals.1));
+        let price = spot_price(
+            true,
+            U256::from(reserve_1),
+            U256::from(reserve_0),
+            pow_decimals_1,
+            pow_decimals_0,
+        );
+        U256::from(amount_1)
+            .checked_mul(price)?
+            .checked_mul(pow_decimals_0)?
+            / (pow_decimals_1 * one_e_18())
+    } else {
+        // over the reserves ratio rather than the rounded spot price
+        U256::from(amount_1) * U256::from(reserve_0) / U256::from(reserve_1)
+    };
+    u128::from(amount_0).checked_add(u128::try_from(amount_1_value).ok()?)
+}
+
+/// `(total_fee, protocol_fee)` rates charged on swaps through a pool type, in basis points
+pub fn swap_fees(fees: (u64, u64, u64, u64), is_stable: bool) -> (u64, u64) {
+    let (lp_fee_volatile, lp_fee_stable, protocol_fee_volatile, protocol_fee_stable) = fees;
+    if is_stable {
+        (lp_fee_stable + protocol_fee_stable, protocol_fee_stable)
+    } else {
+        (
+            lp_fee_volatile + protocol_fee_volatile,
+            protocol_fee_volatile,
+        )
+    }
+}
+
+/// Spot price once `input_amount` has been swapped through the pool charging `fee` basis points.
+/// The LP part of the fee stays in the reserves, only the `protocol_fee` part leaves them.
+#[allow(clippy::too_many_arguments)]
+pub fn marginal_price_after_trade(
+    is_stable: bool,
+    reserve_in: U256,
+    reserve_out: U256,
+    pow_decimals_in: U256,
+    pow_decimals_out: U256,
+    input_amount: u64,
+    fee: u64,
+    protocol_fee: u64,
+) -> U256 {
+    let output_amount = get_amount_out(
+        is_stable,
+        reserve_in,
+        reserve_out,
+        pow_decimals_in,
+        pow_decimals_out,
+        U256::from(subtract_fee(input_amount, fee)),
+    );
+    spot_price(
+        is_stable,
+        reserve_in + U256::from(subtract_fee(input_amount, protocol_fee)),
+        reserve_out - output_amount,
+        pow_decimals_in,
+        pow_decimals_out,
+    )
+}
+
+/// Mirrors the on-chain `get_amounts_out` over already fetched `fees()` and pool metadata
+pub fn get_amounts_out(
+    fees: (u64, u64, u64, u64),
+    pools: &[(PoolId, PoolMetadata)],
+    amount_in: u64,
+    asset_in: AssetId,
+) -> Vec<(u64, AssetId)> {
+    assert!(!pools.is_empty(), "Router: INVALID_PATH");
+
+    let mut amounts = vec![(amount_in, asset_in)];
+    for (pool_id, pool) in pools {
+        let (amount_in, asset_in) = *amounts.last().unwrap();
+        let (fee, _) = swap_fees(fees, pool_id.2);
+        let amount_in = U256::from(subtract_fee(amount_in, fee));
+        let amount_out = if asset_in == pool_id.0 {
+            get_amount_out(
+                pool_id.2,
+                U256::from(pool.reserve_0),
+                U256::from(pool.reserve_1),
+                pow_decimals(pool.decimals_0),
+                pow_decimals(pool.decimals_1),
+                amount_in,
+            )
+        } else {
+            get_amount_out(
+                pool_id.2,
+                U256::from(pool.reserve_1),
+                U256::from(pool.reserve_0),
+                pow_decimals(pool.decimals_1),
+                pow_decimals(pool.decimals_0),
+                amount_in,
+            )
+        };
+        let asset_out = if pool_id.0 == asset_in {
+            pool_id.1
+        } else {
+            pool_id.0
+        };
+        amounts.push((u64::try_from(amount_out).unwrap(), asset_out));
+    }
+    amounts
+}
+
+/// Mirrors the on-chain `get_amounts_in` over already fetched `fees()` and pool metadata
+pub fn get_amounts_in(
+    fees: (u64, u64, u64, u64),
+    pools: &[(PoolId, PoolMetadata)],
+    amount_out: u64,
+    asset_out: AssetId,
+) -> Vec<(u64, AssetId)> {
+    assert!(!pools.is_empty(), "Router: INVALID_PATH");
+
+    let mut amounts = vec![(amount_out, asset_out)];
+    for (pool_id, pool) in pools.iter().rev() {
+        let (amount_out, asset_out) = *amounts.last().unwrap();
+        let (fee, _) = swap_fees(fees, pool_id.2);
+        let amount_in = if asset_out == pool_id.0 {
+            get_amount_in(
+                pool_id.2,
+                U256::from(pool.reserve_1),
+                U256::from(pool.reserve_0),
+                pow_decimals(pool.decimals_1),
+                pow_decimals(pool.decimals_0),
+                U256::from(amount_out),
+            )
+        } else {
+            get_amount_in(
+                pool_id.2,
+                U256::from(pool.reserve_0),
+                U256::from(pool.reserve_1),
+                pow_decimals(pool.decimals_0),
+                pow_decimals(pool.decimals_1),
+                U256::from(amount_out),
+            )
+        };
+        let asset_in = if pool_id.0 == asset_out {
+            pool_id.1
+        } else {
+            pool_id.0
+        };
+        amounts.push((add_fee(u64::try_from(amount_in).unwrap(), fee), asset_in));
+    }
+    amounts
+}
+
+/// Moves the reserves of `pools` by the hop amounts of a swap along them, as returned by
+/// `get_amounts_out`. The protocol fee part of every input leaves the reserves.
+pub fn apply_amounts(
+    fees: (u64, u64, u64, u64),
+    pools: &mut [(PoolId, PoolMetadata)],
+    amounts: &[(u64, AssetId)],
+) {
+    for (i, (pool_id, pool)) in pools.iter_mut().enumerate() {
+        let (_, protocol_fee) = swap_fees(fees, pool_id.2);
+        let amount_in = subtract_fee(amounts[i].0, protocol_fee);
+        let asset_in = amounts[i].1;
+        let (amount_out, _) = amounts[i + 1];
+        if asset_in == pool_id.0 {
+            pool.reserve_0 += amount_in;
+            pool.reserve_1 -= amount_out;
+        } else {
+            pool.reserve_1 += amount_in;
+            pool.reserve_0 -= amount_out;
+        }
+    }
+}
+
+/// Mirrors the `swap_exact_input_split_script`: the output of swapping the whole `amount_in` is
+/// allocated by weight, then each allocation is bought with an exact output swap against the
+/// reserves left by the previous ones. Returns (input spent, output amount) for every weight.
+pub fn split_exact_input(
+    fees: (u64, u64, u64, u64),
+    pools: &[(PoolId, PoolMetadata)],
+    amount_in: u64,
+    asset_in: AssetId,
+    weights: &[u16],
+) -> Vec<(u64, u64)> {
+    let (total_amount_out, asset_out) = *get_amounts_out(fees, pools, amount_in, asset_in)
+        .last()
+        .unwrap();
+    let allocations = split_by_weight(total_amount_out, weights);
+
+    let mut pools = pools.to_vec();
+    let mut amount_in_left = amount_in;
+    let mut legs = Vec::with_capacity(weights.len());
+    for (i, allocation) in allocations.into_iter().enumerate() {
+        // rounding can leave the input short of the last allocation
+        let allocation = if i == weights.len() - 1 && amount_in_left > 0 {
+            let amount_out = get_amounts_out(fees, &pools, amount_in_left, asset_in)
+                .last()
+                .unwrap()
+                .0;
+            allocation.min(amount_out)
+        } else if i == weights.len() - 1 {
+            0
+        } else {
+            allocation
+        };
+        if allocation == 0 {
+            legs.push((0, 0));
+            continue;
+        }
+
+        let mut amounts = get_amounts_in(fees, &pools, allocation, asset_out);
+        amounts.reverse();
+        let spent = amounts.first().unwrap().0;
+        assert!(spent <= amount_in_left, "Exceeding input amount");
+        apply_amounts(fees, &mut pools, &amounts);
+        amount_in_left -= spent;
+        legs.push((spent, allocation));
+    }
+    legs
+}
+
+/// Invariant of a pool holding `x` and `y`: `x^3y + y^3x` over amounts scaled to 18 decimals
+/// for stable pools, `xy` for volatile ones
+pub fn k(is_stable: bool, x: U256, y: U256, pow_decimals_x: U256, pow_decimals_y: U256) -> U256 {
+    if is_stable {
+        let _x = x * one_e_18() / pow_decimals_x;
+        let _y = y * one_e_18() / pow_decimals_y;
+        let _a = (_x * _y) / one_e_18();
+        let _b = (_x * _x) / one_e_18() + (_y * _y) / one_e_18();
+        _a * _b // x3y+y3x >= k
+    } else {
+        x * y // xy >= k
+    }
+}
+
+fn f(x_0: U256, y: U256) -> U256 {
+    x_0 * (y * y / one_e_18() * y / one_e_18()) + (x_0 * x_0 / one_e_18() * x_0 / one_e_18()) * y
+}
+
+fn d(x_0: U256, y: U256) -> U256 {
+    U256::from(3) * x_0 * (y * y / one_e_18()) / one_e_18()
+        + (x_0 * x_0 / one_e_18() * x_0 / one_e_18())
+}
+
+/// Solves `f(x_0, y) = xy` for `y` with Newton's method starting from `y`,
+/// falling back to bisection when the iterations stall
+pub fn get_y(x_0: U256, xy: U256, y: U256) -> Result<U256, SolverError> {
+    match newton_y(x_0, xy, y) {
+        Some(y) => Ok(y),
+        None => bisect_y(x_0, xy, y),
+    }
+}
+
+/// Returns `None` when the derivative is zero, a step leaves the curve domain
+/// or the iterations don't converge within 1 unit
+fn newton_y(x_0: U256, xy: U256, y: U256) -> Option<U256> {
+    let mut y = y;
+    for _ in 0..255 {
+        let y_prev = y;
+        let k = f(x_0, y);
+        let d = d(x_0, y);
+        if d.is_zero() {
+            return None;
+        }
+        if k < xy {
+            let dy = (xy - k) / d;
+            if dy > max_adjusted().saturating_sub(y) {
+                return None;
+            }
+            y += dy;
+        } else {
+            let dy = (k - xy) / d;
+            if dy > y {
+                return None;
+            }
+            y -= dy;
+        }
+        if y > y_prev {
+            if y - y_prev <= U256::one() {
+                return Some(y);
+            }
+        } else if y_prev - y <= U256::one() {
+            return Some(y);
+        }
+    }
+    None
+}
+
+/// Smallest `y` for which `f(x_0, y) >= xy`, `f` being non-decreasing in `y`
+pub fn bisect_y(x_0: U256, xy: U256, y: U256) -> Result<U256, SolverError> {
+    let max = max_adjusted();
+    let mut high = y.max(U256::one()).min(max);
+    while f(x_0, high) < xy {
+        if high == max {
+            return Err(SolverError::NoConvergence);
+        }
+        high = (high * U256::from(2)).min(max);
+    }
+
+    let mut low = U256::zero();
+    if f(x_0, low) >= xy {
+        return Ok(low);
+    }
+    while high - low > U256::one() {
+        let mid = (low + high) / U256::from(2);
+        if f(x_0, mid) >= xy {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    Ok(high)
+}
+
+fn calculate_fee_to_subtract(amount: u64, fee_bp: u64) -> u64 {
+    let nominator = U256::from(amount) * U256::from(fee_bp);
+    let fee = rounding_up_division(nominator, U256::from(BASIS_POINTS_DENOMINATOR));
+    u64::try_from(fee).unwrap()
+}
+
+fn calculate_fee_to_add(amount: u64, fee_bp: u64) -> u64 {
+    let nominator = U256::from(amount) * U256::from(fee_bp);
+    let denominator = U256::from(BASIS_POINTS_DENOMINATOR - fee_bp);
+    let fee = rounding_up_division(nominator, denominator);
+    u64::try_from(fee).unwrap()
+}
+
+pub fn subtract_fee(amount: u64, fee: u64) -> u64 {
+    amount - calculate_fee_to_subtract(amount, fee)
+}
+
+pub fn add_fee(amount: u64, fee: u64) -> u64 {
+    amount + calculate_fee_to_add(amount, fee)
+}
+
+pub fn rounding_up_division(nominator: U256, denominator: U256) -> U256 {
+    let rounding_down_division_result = nominator / denominator;
+    if nominator % denominator == U256::zero() {
+        rounding_down_division_result
+    } else {
+        rounding_down_division_result + U256::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::Asset;
+    use proptest::prelude::*;
+
+    /// Deterministic xorshift generator, so that a failing case can be reproduced from its seed
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Reserve in `[1, bound]`, biased towards dust and small pools
+        fn reserve(&mut self, decimals: u8) -> u64 {
+            let bound = match self.next() % 4 {
+                0 => 1_000,
+                1 => 1_000_000_000,
+                _ => max_raw(decimals),
+            };
+            self.next() % bound + 1
+        }
+    }
+
+    /// Largest raw amount of an asset with `decimals` still within the curve domain
+    fn max_raw(decimals: u8) -> u64 {
+        let max = max_adjusted() * pow_decimals(decimals) / one_e_18();
+        u64::try_from(max).unwrap_or(u64::MAX)
+    }
+
+    fn u256(value: u128) -> U256 {
+        U256::from(value)
+    }
+
+    #[test]
+    fn get_y_matches_bisection_on_balanced_pool() {
+        let reserve = u256(1_000_000) * one_e_18();
+        let xy = k(true, reserve, reserve, one_e_18(), one_e_18());
+        let x_0 = reserve + u256(1_000) * one_e_18();
+
+        let y = get_y(x_0, xy, reserve).unwrap();
+
+        assert!(y < reserve);
+        assert!(y.abs_diff(bisect_y(x_0, xy, reserve).unwrap()) <= U256::one());
+    }
+
+    #[test]
+    fn get_y_falls_back_to_bisection_on_zero_derivative() {
+        let x_0 = u256(1_000_000_000);
+        let y = u256(1_000_000_000);
+        assert!(d(x_0, y).is_zero());
+
+        let xy = f(x_0, one_e_18());
+
+        assert_eq!(get_y(x_0, xy, y), Ok(one_e_18()));
+    }
+
+    #[test]
+    fn get_y_reports_no_convergence_on_flat_curve() {
+        // the curve is constantly zero at x_0 = 0
+        assert_eq!(
+            get_y(U256::zero(), one_e_18(), one_e_18()),
+            Err(SolverError::NoConvergence)
+        );
+        assert_eq!(
+            get_y(U256::zero(), U256::zero(), one_e_18()),
+            Ok(U256::zero())
+        );
+    }
+
+    #[test]
+    fn get_y_reports_no_convergence_out_of_domain() {
+        let xy = f(max_adjusted(), max_adjusted());
+
+        assert_eq!(
+            get_y(U256::one(), xy, U256::one()),
+            Err(SolverError::NoConvergence)
+        );
+    }
+
+    #[test]
+    fn spot_price_is_normalised_by_decimals() {
+        let (pow_9, pow_6) = (pow_decimals(9), pow_decimals(6));
+
+        assert_eq!(
+            spot_price(false, u256(1_000_000), u256(1_000_000), pow_9, pow_9),
+            one_e_18()
+        );
+        assert_eq!(
+            spot_price(false, u256(1_000_000_000), u256(2_000_000), pow_9, pow_6),
+            u256(2) * one_e_18()
+        );
+        assert_eq!(
+            spot_price(true, u256(1_000_000_000), u256(1_000_000), pow_9, pow_6),
+            one_e_18()
+        );
+        assert_eq!(
+            spot_price(true, u256(1_000_000_000), u256(2_000_000), pow_9, pow_6),
+            u256(14) * one_e_18() / u256(13)
+        );
+    }
+
+    #[test]
+    fn marginal_price_decreases_with_trade_size() {
+        let (reserve_in, reserve_out, pow_9) = (u256(1_000_000), u256(1_000_000), pow_decimals(9));
+
+        assert_eq!(
+            marginal_price_after_trade(false, reserve_in, reserve_out, pow_9, pow_9, 1_000, 30, 0),
+            u256(999_004) * one_e_18() / u256(1_001_000)
+        );
+        assert_eq!(
+            marginal_price_after_trade(false, reserve_in, reserve_out, pow_9, pow_9, 1_000, 30, 10),
+            u256(999_004) * one_e_18() / u256(1_000_999)
+        );
+        for is_stable in [false, true] {
+            let spot = spot_price(is_stable, reserve_in, reserve_out, pow_9, pow_9);
+            let mut previous = spot;
+            for amount_in in [1_000, 10_000, 100_000] {
+                let price = marginal_price_after_trade(
+                    is_stable,
+                    reserve_in,
+                    reserve_out,
+                    pow_9,
+                    pow_9,
+                    amount_in,
+                    30,
+                    0,
+                );
+                assert!(price < previous);
+                previous = price;
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_get_y_over_reserves_and_decimals() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2_000 {
+            let decimals_in = (rng.next() % 25) as u8;
+            let decimals_out = (rng.next() % 25) as u8;
+            let reserve_in = rng.reserve(decimals_in);
+            let reserve_out = rng.reserve(decimals_out);
+            let amount_in = rng.next() % reserve_in + 1;
+
+            let (pow_decimals_in, pow_decimals_out) =
+                (pow_decimals(decimals_in), pow_decimals(decimals_out));
+            let x = adjust(U256::from(reserve_in), pow_decimals_in);
+            let y = adjust(U256::from(reserve_out), pow_decimals_out);
+            let xy = k(true, x, y, one_e_18(), one_e_18());
+            let x_0 = x + adjust(U256::from(amount_in), pow_decimals_in);
+
+            let solved = get_y(x_0, xy, y).unwrap();
+            let exact = bisect_y(x_0, xy, y).unwrap();
+            assert!(
+                solved.abs_diff(exact) <= exact / u256(1_000_000) + u256(2),
+                "reserves ({reserve_in}, {reserve_out}), decimals ({decimals_in}, {decimals_out}), amount in {amount_in}: {solved} != {exact}"
+            );
+
+            let amount_out = get_amount_out(
+                true,
+                U256::from(reserve_in),
+                U256::from(reserve_out),
+                pow_decimals_in,
+                pow_decimals_out,
+                U256::from(amount_in),
+            );
+            assert!(amount_out <= U256::from(reserve_out));
+        }
+    }
+
+    fn pool_metadata(
+        reserve_0: u64,
+        reserve_1: u64,
+        total_liquidity: u64,
+        decimals_0: u8,
+        decimals_1: u8,
+    ) -> PoolMetadata {
+        PoolMetadata {
+            reserve_0,
+            reserve_1,
+            liquidity: Asset {
+                id: AssetId::default(),
+                amount: total_liquidity,
+            },
+            decimals_0,
+            decimals_1,
+        }
+    }
+
+    #[test]
+    fn split_divides_the_output_by_weight() {
+        let pool_id = (AssetId::new([1; 32]), AssetId::new([2; 32]), false);
+        let pools = [(
+            pool_id,
+            pool_metadata(1_000_000, 1_000_000, 1_000_000, 9, 9),
+        )];
+        let fees = (30, 5, 0, 0);
+        let amount_in = 100_000;
+
+        let total_amount_out = get_amounts_out(fees, &pools, amount_in, pool_id.0)
+            .last()
+            .unwrap()
+            .0;
+        let legs = split_exact_input(fees, &pools, amount_in, pool_id.0, &[1, 1]);
+
+        // splitting the input would give the first recipient more than the second one
+        assert_eq!(legs[0].1, total_amount_out / 2);
+        assert_eq!(legs[0].1 + legs[1].1, total_amount_out);
+        assert!(legs[0].0 < legs[1].0);
+        assert!(legs[0].0 + legs[1].0 <= amount_in);
+    }
+
+    proptest! {
+        #[test]
+        fn deposit_mints_the_liquidity_of_the_desired_amounts_without_excess(
+            reserve_0 in 1_000..u64::MAX / 4,
+            reserve_1 in 1_000..u64::MAX / 4,
+            amount_0_desired in 1..u64::MAX / 4,
+            amount_1_desired in 1..u64::MAX / 4,
+            (decimals_0, decimals_1) in prop_oneof![Just((9, 9)), Just((6, 18)), Just((18, 6))],
+        ) {
+            let total_liquidity = initial_liquidity(reserve_0, reserve_1);
+            let pool = pool_metadata(reserve_0, reserve_1, total_liquidity, decimals_0, decimals_1);
+            let desired_liquidity = get_mint_liquidity(
+                amount_0_desired,
+                amount_1_desired,
+                reserve_0,
+                reserve_1,
+                total_liquidity,
+            );
+            prop_assume!(desired_liquidity > 0);
+
+            let (amount_0, amount_1) =
+                get_pool_deposit_amounts(&pool, amount_0_desired, amount_1_desired, 0, 0);
+            prop_assert!(amount_0 <= amount_0_desired && amount_1 <= amount_1_desired);
+
+            let minted = get_mint_liquidity(amount_0, amount_1, reserve_0, reserve_1, total_liquidity);
+            prop_assert_eq!(minted, desired_liquidity);
+            // one unit less of either asset mints less, i.e. nothing beyond rounding is donated
+            prop_assert!(
+                get_mint_liquidity(amount_0 - 1, amount_1, reserve_0, reserve_1, total_liquidity)
+                    < minted
+            );
+            prop_assert!(
+                get_mint_liquidity(amount_0, amount_1 - 1, reserve_0, reserve_1, total_liquidity)
+                    < minted
+            );
+
+            // the reserves backing every LP unit never decrease
+            let new_total_liquidity = U256::from(total_liquidity + minted);
+            prop_assert!(
+                U256::from(reserve_0 + amount_0) * U256::from(total_liquidity)
+                    >= U256::from(reserve_0) * new_total_liquidity
+            );
+            prop_assert!(
+                U256::from(reserve_1 + amount_1) * U256::from(total_liquidity)
+                    >= U256::from(reserve_1) * new_total_liquidity
+            );
+        }
+
+        #[test]
+        fn first_deposit_sets_the_price(
+            amount_0_desired in 1..u64::MAX / 4,
+            amount_1_desired in 1..u64::MAX / 4,
+            decimals_0 in 0..=18u8,
+            decimals_1 in 0..=18u8,
+        ) {
+            let pool = pool_metadata(0, 0, 0, decimals_0, decimals_1);
+            prop_assert_eq!(
+                get_pool_deposit_amounts(&pool, amount_0_desired, amount_1_desired, 0, 0),
+                (amount_0_desired, amount_1_desired)
+            );
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::property::{PoolParams, RouteParams};
+
+/// Fees of the matrix routes, in the order the AMM `fees()` returns them: the LP fees the AMM is
+/// built with, then the protocol fees it charges
+pub const FEES: (u64, u64, u64, u64) = (30, 5, 10, 1);
+
+/// Whole units of both assets held by every pool of the matrix, when their decimals allow it
+const MAX_RESERVE_UNITS: u64 = 1_000_000;
+
+/// Multiple of a pool reserve minted to the wallet: `deploy_route` mints twice the reserves of
+/// every asset, the inner ones being held by two pools
+const MINTED_RESERVES: u64 = 4;
+
+/// Whole units of both assets held by every pool of the matrix, as many as the balances minted
+/// to the wallet leave room for in a `u64`, which is a handful for 18 decimals
+fn reserve_units(decimals: (u8, u8)) -> u64 {
+    let max_decimals = decimals.0.max(decimals.1);
+    (u64::MAX / MINTED_RESERVES / 10u64.pow(max_decimals.into())).min(MAX_RESERVE_UNITS)
+}
+
+/// Route of `hops` pools alternating between assets of `decimals.0` and `decimals.1`, every pool
+/// holding as many whole units of both assets, i.e. trading at its peg
+pub fn route(decimals: (u8, u8), is_stable: bool, hops: usize) -> RouteParams {
+    let units = reserve_units(decimals);
+    let decimals: Vec<u8> = (0..=hops)
+        .map(|i| if i % 2 == 0 { decimals.0 } else { decimals.1 })
+        .collect();
+    let pools = decimals
+        .windows(2)
+        .map(|pair| PoolParams {
+            is_stable,
+            reserve_in: units * 10u64.pow(pair[0].into()),
+            reserve_out: units * 10u64.pow(pair[1].into()),
+        })
+        .collect();
+    RouteParams { decimals, pools }
+}
+
+/// Expands to a `#[tokio::test]` per decimals combination, pool type and number of hops,
+/// each awaiting `$check((decimals_0, decimals_1), is_stable, hops)`
+#[macro_export]
+macro_rules! route_matrix {
+    ($check:path) => {
+        $crate::route_matrix!(@cases $check;
+            decimals_0_6_volatile_1_hop: (0, 6), false, 1;
+            decimals_0_6_volatile_2_hops: (0, 6), false, 2;
+            decimals_0_6_volatile_3_hops: (0, 6), false, 3;
+            decimals_0_6_stable_1_hop: (0, 6), true, 1;
+            decimals_0_6_stable_2_hops: (0, 6), true, 2;
+            decimals_0_6_stable_3_hops: (0, 6), true, 3;
+            decimals_6_18_volatile_1_hop: (6, 18), false, 1;
+            decimals_6_18_volatile_2_hops: (6, 18), false, 2;
+            decimals_6_18_volatile_3_hops: (6, 18), false, 3;
+            decimals_6_18_stable_1_hop: (6, 18), true, 1;
+            decimals_6_18_stable_2_hops: (6, 18), true, 2;
+            decimals_6_18_stable_3_hops: (6, 18), true, 3;
+            decimals_9_9_volatile_1_hop: (9, 9), false, 1;
+            decimals_9_9_volatile_2_hops: (9, 9), false, 2;
+            decimals_9_9_volatile_3_hops: (9, 9), false, 3;
+            decimals_9_9_stable_1_hop: (9, 9), true, 1;
+            decimals_9_9_stable_2_hops: (9, 9), true, 2;
+            decimals_9_9_stable_3_hops: (9, 9), true, 3;
+            decimals_18_18_volatile_1_hop: (18, 18), false, 1;
+            decimals_18_18_volatile_2_hops: (18, 18), false, 2;
+            decimals_18_18_volatile_3_hops: (18, 18), false, 3;
+            decimals_18_18_stable_1_hop: (18, 18), true, 1;
+            decimals_18_18_stable_2_hops: (18, 18), true, 2;
+            decimals_18_18_stable_3_hops: (18, 18), true, 3;
+        );
+    };
+    (@cases $check:path; $($name:ident: $decimals:expr, $is_stable:expr, $hops:expr;)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                $check($decimals, $is_stable, $hops).await;
+            }
+        )*
+    };
+}
+
+/// Expands to a `#[tokio::test]` per decimals combination and pool type, each awaiting
+/// `$check((decimals_0, decimals_1), is_stable)` for scripts acting on a single pool
+#[macro_export]
+macro_rules! pool_matrix {
+    ($check:path) => {
+        $crate::pool_matrix!(@cases $check;
+            decimals_0_6_volatile: (0, 6), false;
+            decimals_0_6_stable: (0, 6), true;
+            decimals_6_18_volatile: (6, 18), false;
+            decimals_6_18_stable: (6, 18), true;
+            decimals_9_9_volatile: (9, 9), false;
+            decimals_9_9_stable: (9, 9), true;
+            decimals_18_18_volatile: (18, 18), false;
+            decimals_18_18_stable: (18, 18), true;
+        );
+    };
+    (@cases $check:path; $($name:ident: $decimals:expr, $is_stable:expr;)*) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                $check($decimals, $is_stable).await;
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_alternate_decimals_at_peg() {
+        let params = route((6, 18), true, 3);
+
+        assert_eq!(params.decimals, vec![6, 18, 6, 18]);
+        assert_eq!(params.pools.len(), 3);
+        assert!(params.pools.iter().all(|pool| pool.is_stable));
+        assert_eq!(params.pools[0].reserve_in, 4_000_000);
+        assert_eq!(params.pools[0].reserve_out, 4_000_000_000_000_000_000);
+        assert_eq!(params.pools[1].reserve_in, 4_000_000_000_000_000_000);
+        assert_eq!(params.pools[1].reserve_out, 4_000_000);
+    }
+
+    #[test]
+    fn holds_max_units_below_18_decimals() {
+        for decimals in [(0, 6), (9, 9)] {
+            let params = route(decimals, false, 1);
+            assert_eq!(
+                params.pools[0].reserve_in,
+                MAX_RESERVE_UNITS * 10u64.pow(decimals.0.into())
+            );
+            assert_eq!(
+                params.pools[0].reserve_out,
+                MAX_RESERVE_UNITS * 10u64.pow(decimals.1.into())
+            );
+        }
+    }
+
+    #[test]
+    fn wallet_balances_fit_in_u64() {
+        for decimals in [(0, 6), (6, 18), (9, 9), (18, 18)] {
+            let params = route(decimals, false, 3);
+            for pool in &params.pools {
+                assert!(pool.reserve_in.checked_mul(MINTED_RESERVES).is_some());
+                assert!(pool.reserve_out.checked_mul(MINTED_RESERVES).is_some());
+            }
+        }
+    }
+}
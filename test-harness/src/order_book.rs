@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use fuels::{
+    core::Configurables,
+    prelude::*,
+    programs::responses::CallResponse,
+    types::{output::Output, Bits256, Identity, U256},
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    data_structures::MiraAMMContract,
+    interface::{
+        amm::fees, LimitOrderPredicateConfigurables, LimitOrderPredicateEncoder,
+        SwapExactInputScript, SwapExactInputScriptConfigurables,
+    },
+    math::{get_amounts_out, rounding_up_division},
+    paths::{LIMIT_ORDER_PREDICATE_BINARY_PATH, SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH},
+    types::PoolId,
+    utils::common::pools_metadata,
+};
+
+/// Resting order to sell `asset_in` for at least `min_amount_out` of `asset_out` per `amount_in`
+#[derive(Debug, Clone)]
+pub struct LimitOrder {
+    pub owner: Address,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    /// Route used by the keeper to fill the order, has to end with `asset_out`
+    pub pools: Vec<PoolId>,
+}
+
+impl LimitOrder {
+    /// Minimum output the swap script has to be called with to fill `amount` of the order
+    pub fn min_amount_out_for(&self, amount: u64) -> u64 {
+        let nominator = U256::from(self.min_amount_out) * U256::from(amount);
+        u64::try_from(rounding_up_division(nominator, U256::from(self.amount_in))).unwrap()
+    }
+}
+
+/// Tracks limit order predicates and fills them through the `swap_exact_input_script`.
+///
+/// The predicates only accept the exact (non-loader) script bytecode, so fills have to be
+/// submitted with a script instance which was not converted into a loader.
+pub struct OrderBook {
+    amm_contract_id: ContractId,
+    swap_script_bytecode_hash: Bits256,
+    orders: HashMap<Address, (LimitOrder, Predicate)>,
+}
+
+impl OrderBook {
+    pub fn new(amm_contract_id: ContractId) -> Self {
+        Self {
+            amm_contract_id,
+            swap_script_bytecode_hash: swap_script_bytecode_hash(amm_contract_id),
+            orders: HashMap::new(),
+        }
+    }
+
+    pub fn amm_contract_id(&self) -> ContractId {
+        self.amm_contract_id
+    }
+
+    /// Builds the predicate for the order and starts tracking it.
+    /// The order becomes active once the owner sends `asset_in` to the returned predicate.
+    pub fn create_order(&mut self, order: LimitOrder, provider: &Provider) -> Predicate {
+        assert!(order.amount_in > 0, "Order amount can't be zero");
+        assert!(!order.pools.is_empty(), "Order route can't be empty");
+
+        let configurables = LimitOrderPredicateConfigurables::default()
+            .with_OWNER(order.owner)
+            .unwrap()
+            .with_ASSET_IN(order.asset_in)
+            .unwrap()
+            .with_ASSET_OUT(order.asset_out)
+            .unwrap()
+            .with_AMOUNT_IN(order.amount_in)
+            .unwrap()
+            .with_MIN_AMOUNT_OUT(order.min_amount_out)
+            .unwrap()
+            .with_SWAP_SCRIPT_BYTECODE_HASH(self.swap_script_bytecode_hash)
+            .unwrap();
+        let data = LimitOrderPredicateEncoder::default()
+            .encode_data(None)
+            .unwrap();
+
+        let predicate = Predicate::load_from(LIMIT_ORDER_PREDICATE_BINARY_PATH)
+            .unwrap()
+            .with_configurables(configurables)
+            .with_data(data)
+            .with_provider(provider.clone());
+
+        self.orders
+            .insert(predicate.address().into(), (order, predicate.clone()));
+        predicate
+    }
+
+    pub fn order(&self, predicate_address: &Address) -> Option<&LimitOrder> {
+        self.orders.get(predicate_address).map(|(order, _)| order)
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = (&Address, &LimitOrder)> {
+        self.orders
+            .iter()
+            .map(|(address, (order, _))| (address, order))
+    }
+
+    /// Fills the whole predicate balance if the route currently pays at least the order price.
+    /// Returns `None` when the price hasn't crossed yet or the order holds no funds.
+    pub async fn fill_order(
+        &self,
+        predicate_address: &Address,
+        amm: &MiraAMMContract<impl Account>,
+        swap_script: &SwapExactInputScript<impl Account>,
+        deadline: u32,
+    ) -> Option<CallResponse<Vec<(u64, AssetId)>>> {
+        let (order, predicate) = self.orders.get(predicate_address)?;
+        let balance = predicate.get_asset_balance(&order.asset_in).await.unwrap();
+        self.fill_order_partially(predicate_address, balance, amm, swap_script, deadline)
+            .await
+    }
+
+    /// Fills `amount` of the order if the route currently pays at least the order price for it.
+    /// The rest of the predicate balance stays resting in the order.
+    /// Returns `None` when the price hasn't crossed yet or the order holds less than `amount`.
+    pub async fn fill_order_partially(
+        &self,
+        predicate_address: &Address,
+        amount: u64,
+        amm: &MiraAMMContract<impl Account>,
+        swap_script: &SwapExactInputScript<impl Account>,
+        deadline: u32,
+    ) -> Option<CallResponse<Vec<(u64, AssetId)>>> {
+        let (order, predicate) = self.orders.get(predicate_address)?;
+
+        let balance = predicate.get_asset_balance(&order.asset_in).await.unwrap();
+        if amount == 0 || amount > balance {
+            return None;
+        }
+
+        let amm_fees = fees(&amm.instance).await.value;
+        let pools = pools_metadata(&amm.instance, &order.pools).await;
+        let amounts_out = get_amounts_out(amm_fees, &pools, amount, order.asset_in);
+        let amount_out_min = order.min_amount_out_for(amount);
+        if amounts_out.last().unwrap().0 < amount_out_min {
+            return None;
+        }
+
+        let inputs = predicate
+            .get_asset_inputs_for_amount(order.asset_in, amount, None)
+            .await
+            .unwrap();
+        // the predicate only accepts fills which send the unswapped coins back to it
+        let outputs = vec![Output::Change {
+            asset_id: order.asset_in,
+            amount: 0,
+            to: predicate.address().into(),
+        }];
+
+        let response = swap_script
+            .main(
+                amount,
+                order.asset_in,
+                amount_out_min,
+                order.pools.clone(),
+                Identity::Address(order.owner),
+                deadline,
+            )
+            .with_contracts(&[&amm.instance])
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .unwrap();
+        Some(response)
+    }
+
+    /// Returns the order funds to the owner, who has to sign the transaction
+    pub async fn cancel_order(&mut self, predicate_address: &Address, owner: &impl Account) {
+        let (order, predicate) = self.orders.remove(predicate_address).unwrap();
+        assert_eq!(
+            order.owner,
+            Address::from(owner.address()),
+            "Only the owner can cancel the order"
+        );
+
+        // the owner signature is the only witness of the transaction
+        let data = LimitOrderPredicateEncoder::default()
+            .encode_data(Some(0))
+            .unwrap();
+        withdraw_signed_by_owner(predicate.with_data(data), owner, order.asset_in).await;
+    }
+}
+
+/// Moves the whole `asset` balance of the predicate to `owner`, who signs the transaction
+pub async fn withdraw_signed_by_owner(predicate: Predicate, owner: &impl Account, asset: AssetId) {
+    let balance = predicate.get_asset_balance(&asset).await.unwrap();
+    let inputs = predicate
+        .get_asset_inputs_for_amount(asset, balance, None)
+        .await
+        .unwrap();
+    let outputs = vec![Output::Change {
+        asset_id: asset,
+        amount: 0,
+        to: owner.address().into(),
+    }];
+
+    let provider = owner.try_provider().unwrap();
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    owner.add_witnesses(&mut tb).unwrap();
+    owner.adjust_for_fee(&mut tb, 0).await.unwrap();
+    let tx = tb.build(provider).await.unwrap();
+
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+}
+
+/// Hash of the `swap_exact_input_script` bytecode as it is executed for the given AMM
+pub fn swap_script_bytecode_hash(amm_contract_id: ContractId) -> Bits256 {
+    let mut binary = std::fs::read(SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH).unwrap();
+    let configurables: Configurables = SwapExactInputScriptConfigurables::default()
+        .with_AMM_CONTRACT_ID(amm_contract_id)
+        .unwrap()
+        .into();
+    configurables.update_constants_in(&mut binary);
+
+    Bits256(<[u8; 32]>::from(Sha256::digest(&binary)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order() -> LimitOrder {
+        LimitOrder {
+            owner: Address::new([0; 32]),
+            asset_in: AssetId::new([1; 32]),
+            asset_out: AssetId::new([2; 32]),
+            amount_in: 10_000,
+            min_amount_out: 11_000,
+            pools: vec![],
+        }
+    }
+
+    #[test]
+    fn partial_fills_keep_the_order_price() {
+        let order = order();
+
+        assert_eq!(
+            order.min_amount_out_for(order.amount_in),
+            order.min_amount_out
+        );
+        assert_eq!(order.min_amount_out_for(2_500), 2_750);
+        assert_eq!(order.min_amount_out_for(7_500), 8_250);
+    }
+
+    #[test]
+    fn partial_fills_round_the_order_price_up() {
+        let order = order();
+
+        // 1.1 rounded up, so that splitting the order into dust fills never pays less
+        assert_eq!(order.min_amount_out_for(1), 2);
+        assert_eq!(order.min_amount_out_for(9), 10);
+        assert_eq!(order.min_amount_out_for(10), 11);
+    }
+}
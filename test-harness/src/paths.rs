@@ -1,11 +1,28 @@
 pub const ADD_LIQUIDITY_SCRIPT_BINARY_PATH: &str =
     "../../scripts/add_liquidity_script/out/debug/add_liquidity_script.bin";
+pub const CREATE_POOL_AND_ADD_LIQUIDITY_SCRIPT_BINARY_PATH: &str =
+    "../../scripts/create_pool_and_add_liquidity_script/out/debug/create_pool_and_add_liquidity_script.bin";
 pub const REMOVE_LIQUIDITY_SCRIPT_BINARY_PATH: &str =
     "../../scripts/remove_liquidity_script/out/debug/remove_liquidity_script.bin";
 pub const SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH: &str =
     "../../scripts/swap_exact_input_script/out/debug/swap_exact_input_script.bin";
 pub const SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH: &str =
     "../../scripts/swap_exact_output_script/out/debug/swap_exact_output_script.bin";
-
-pub const AMM_CONTRACT_BINARY_PATH: &str = "../../fixtures/mira-amm/mira_amm_contract.bin";
-pub const MOCK_TOKEN_CONTRACT_BINARY_PATH: &str = "../../fixtures/mock-token/mock_token.bin";
+pub const SWAP_EXACT_INPUT_SPLIT_SCRIPT_BINARY_PATH: &str =
+    "../../scripts/swap_exact_input_split_script/out/debug/swap_exact_input_split_script.bin";
+pub const BATCH_SWAP_SCRIPT_BINARY_PATH: &str =
+    "../../scripts/batch_swap_script/out/debug/batch_swap_script.bin";
+pub const POSITION_VALUATION_SCRIPT_BINARY_PATH: &str =
+    "../../scripts/position_valuation_script/out/debug/position_valuation_script.bin";
+pub const POOLS_OVERVIEW_SCRIPT_BINARY_PATH: &str =
+    "../../scripts/pools_overview_script/out/debug/pools_overview_script.bin";
+pub const ACCOUNT_PREDICATE_BINARY_PATH: &str =
+    "../../predicates/account_predicate/out/debug/account_predicate.bin";
+pub const DCA_PREDICATE_BINARY_PATH: &str =
+    "../../predicates/dca_predicate/out/debug/dca_predicate.bin";
+pub const LIMIT_ORDER_PREDICATE_BINARY_PATH: &str =
+    "../../predicates/limit_order_predicate/out/debug/limit_order_predicate.bin";
+pub const MOCK_HOOK_CONTRACT_BINARY_PATH: &str =
+    "../../contracts/mock_hook_contract/out/debug/mock_hook_contract.bin";
+pub const TWAP_ORACLE_CONTRACT_BINARY_PATH: &str =
+    "../../contracts/twap_oracle_contract/out/debug/twap_oracle_contract.bin";
@@ -0,0 +1,330 @@
+use fuels::types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    interface::{MintEvent, PoolMetadata},
+    math::{get_burn_amounts, k, pow_decimals, value_in_asset_0},
+    types::PoolId,
+};
+
+/// Deposit of a liquidity provider, as logged by the `MintEvent` of the `add_liquidity_script`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LpEntry {
+    pub pool_id: PoolId,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    /// LP assets received for the deposit
+    pub liquidity: u64,
+    /// Amounts `liquidity` burned into right after the deposit, short of the deposit by the
+    /// minimum liquidity locked by the first deposit of a pool, or by the excess of an off-ratio
+    /// deposit donated to the pool
+    pub burn_amount_0: u64,
+    pub burn_amount_1: u64,
+}
+
+impl LpEntry {
+    /// Entry of a deposit of `amounts` for `liquidity`, `pool` being a snapshot taken right after it
+    pub fn new(pool_id: PoolId, amounts: (u64, u64), liquidity: u64, pool: &PoolMetadata) -> Self {
+        let (burn_amount_0, burn_amount_1) = get_burn_amounts(
+            liquidity,
+            pool.reserve_0,
+            pool.reserve_1,
+            pool.liquidity.amount,
+        );
+        Self {
+            pool_id,
+            amount_0: amounts.0,
+            amount_1: amounts.1,
+            liquidity,
+            burn_amount_0,
+            burn_amount_1,
+        }
+    }
+
+    /// Entry of the deposit logged by `event`, `pool` being a snapshot taken right after it
+    pub fn from_event(event: &MintEvent, pool: &PoolMetadata) -> Self {
+        Self::new(
+            event.pool_id,
+            (event.asset_0_in, event.asset_1_in),
+            event.liquidity.amount,
+            pool,
+        )
+    }
+}
+
+/// Performance of a position against holding its deposit, at the time of a pool snapshot.
+///
+/// Values are in the first asset of the pool at the spot price of the snapshot. The fees are
+/// told apart from the price moves by the growth of the invariant of the position, `k` being
+/// homogeneous in the amounts: of degree 2 for volatile pools and 4 for stable ones. Both are
+/// measured against what the position was worth right after the deposit, the rest of the deposit
+/// being reported as donated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionPnl {
+    /// Amounts the position burns into
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub position_value: u128,
+    /// Value of the deposit, had it been held instead
+    pub hold_value: u128,
+    /// Value of the position, had the pool charged no fees since the deposit
+    pub value_without_fees: u128,
+    /// Value of the part of the deposit the position can't burn back, locked or donated to the
+    /// pool at entry
+    pub donated_value: u128,
+    /// `value_without_fees` less the value of the burn amounts of the entry, negative once the
+    /// price moved away from the entry
+    pub impermanent_loss: i128,
+    pub fees_earned: u128,
+    /// `position_value - hold_value`, i.e. the impermanent loss plus the fees earned, less the
+    /// donated value
+    pub pnl: i128,
+}
+
+/// Error of the PnL computation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PnlError {
+    /// A value doesn't fit in a `u128`, or a difference of values in an `i128`
+    Overflow,
+}
+
+/// PnL of `entry` against a snapshot of its pool, current or historical, taken after the deposit
+pub fn position_pnl(
+    entry: &LpEntry,
+    pool: &PoolMetadata,
+) -> std::result::Result<PositionPnl, PnlError> {
+    let is_stable = entry.pool_id.2;
+    let reserves = (pool.reserve_0, pool.reserve_1);
+    let decimals = (pool.decimals_0, pool.decimals_1);
+    let value = |amounts| {
+        value_in_asset_0(is_stable, reserves, decimals, amounts).ok_or(PnlError::Overflow)
+    };
+    let (amount_0, amount_1) = get_burn_amounts(
+        entry.liquidity,
+        pool.reserve_0,
+        pool.reserve_1,
+        pool.liquidity.amount,
+    );
+
+    let position_value = value((amount_0, amount_1))?;
+    let hold_value = value((entry.amount_0, entry.amount_1))?;
+    let entry_value = value((entry.burn_amount_0, entry.burn_amount_1))?;
+    // the deposit held, beyond what the liquidity received was worth right after it
+    let donated_value = hold_value.saturating_sub(entry_value);
+    let invariant_now = position_invariant(is_stable, (amount_0, amount_1), decimals);
+    let invariant_at_entry = position_invariant(
+        is_stable,
+        (entry.burn_amount_0, entry.burn_amount_1),
+        decimals,
+    );
+    let value_without_fees = if invariant_now.is_zero() {
+        0
+    } else {
+        let value_without_fees = U256::from(position_value)
+            .checked_mul(invariant_at_entry)
+            .ok_or(PnlError::Overflow)?
+            / invariant_now;
+        // rounding can't make fees negative
+        u128::try_from(value_without_fees).map_or(position_value, |value| value.min(position_value))
+    };
+
+    Ok(PositionPnl {
+        amount_0,
+        amount_1,
+        position_value,
+        hold_value,
+        value_without_fees,
+        donated_value,
+        impermanent_loss: difference(value_without_fees, entry_value)?,
+        fees_earned: position_value - value_without_fees,
+        pnl: difference(position_value, hold_value)?,
+    })
+}
+
+fn difference(value: u128, other: u128) -> std::result::Result<i128, PnlError> {
+    let value = i128::try_from(value).map_err(|_| PnlError::Overflow)?;
+    let other = i128::try_from(other).map_err(|_| PnlError::Overflow)?;
+    Ok(value - other)
+}
+
+/// Root of `k` of the degree of the curve, so that it scales linearly with the amounts
+fn position_invariant(is_stable: bool, amounts: (u64, u64), decimals: (u8, u8)) -> U256 {
+    let k = k(
+        is_stable,
+        U256::from(amounts.0),
+        U256::from(amounts.1),
+        pow_decimals(decimals.0),
+        pow_decimals(decimals.1),
+    );
+    if is_stable {
+        k.integer_sqrt().integer_sqrt()
+    } else {
+        k.integer_sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::types::AssetId;
+
+    use super::*;
+    use crate::{
+        interface::Asset,
+        math::{get_y, spot_price},
+        utils::common::MINIMUM_LIQUIDITY,
+    };
+
+    const SUPPLY: u64 = 1_000_000;
+
+    fn entry(is_stable: bool) -> LpEntry {
+        LpEntry {
+            pool_id: (AssetId::new([1; 32]), AssetId::new([2; 32]), is_stable),
+            amount_0: 1_000,
+            amount_1: 1_000,
+            liquidity: 1_000,
+            burn_amount_0: 1_000,
+            burn_amount_1: 1_000,
+        }
+    }
+
+    fn snapshot(reserve_0: u64, reserve_1: u64) -> PoolMetadata {
+        PoolMetadata {
+            reserve_0,
+            reserve_1,
+            liquidity: Asset {
+                id: AssetId::new([3; 32]),
+                amount: SUPPLY,
+            },
+            decimals_0: 6,
+            decimals_1: 6,
+        }
+    }
+
+    #[test]
+    fn breaks_even_at_entry() {
+        for is_stable in [false, true] {
+            let pnl = position_pnl(&entry(is_stable), &snapshot(SUPPLY, SUPPLY)).unwrap();
+
+            assert_eq!((pnl.amount_0, pnl.amount_1), (1_000, 1_000));
+            assert_eq!(pnl.position_value, 2_000);
+            assert_eq!(pnl.hold_value, 2_000);
+            assert_eq!((pnl.impermanent_loss, pnl.fees_earned, pnl.pnl), (0, 0, 0));
+            assert_eq!(pnl.donated_value, 0);
+        }
+    }
+
+    #[test]
+    fn first_deposit_donates_the_locked_minimum() {
+        let pool = snapshot(SUPPLY, SUPPLY);
+        let pool_id = entry(false).pool_id;
+        let entry = LpEntry::new(pool_id, (SUPPLY, SUPPLY), SUPPLY - MINIMUM_LIQUIDITY, &pool);
+        let pnl = position_pnl(&entry, &pool).unwrap();
+
+        assert_eq!(
+            (entry.burn_amount_0, entry.burn_amount_1),
+            (999_000, 999_000)
+        );
+        assert_eq!(pnl.donated_value, 2 * u128::from(MINIMUM_LIQUIDITY));
+        assert_eq!((pnl.impermanent_loss, pnl.fees_earned), (0, 0));
+        assert_eq!(pnl.pnl, -2 * i128::from(MINIMUM_LIQUIDITY));
+    }
+
+    #[test]
+    fn off_ratio_deposit_earns_fees_on_the_liquidity_received() {
+        // twice the asset_0 the reserves call for, the excess donated to the pool
+        let pool_id = entry(false).pool_id;
+        let entry = LpEntry::new(pool_id, (2_000, 1_000), 1_000, &snapshot(SUPPLY, SUPPLY));
+        let at_entry = position_pnl(&entry, &snapshot(SUPPLY, SUPPLY)).unwrap();
+
+        assert_eq!(at_entry.donated_value, 1_000);
+        assert_eq!((at_entry.impermanent_loss, at_entry.fees_earned), (0, 0));
+        assert_eq!(at_entry.pnl, -1_000);
+
+        let pnl = position_pnl(&entry, &snapshot(1_010_000, 1_010_000)).unwrap();
+
+        assert_eq!(pnl.fees_earned, 20);
+        assert_eq!(pnl.impermanent_loss, 0);
+        assert_eq!(pnl.pnl, -980);
+    }
+
+    #[test]
+    fn volatile_position_loses_a_fifth_when_price_quadruples() {
+        // same `xy` as at entry, asset_1 now worth 4 asset_0
+        let pnl = position_pnl(&entry(false), &snapshot(2 * SUPPLY, SUPPLY / 2)).unwrap();
+
+        assert_eq!((pnl.amount_0, pnl.amount_1), (2_000, 500));
+        assert_eq!(pnl.position_value, 4_000);
+        assert_eq!(pnl.hold_value, 5_000);
+        assert_eq!(pnl.value_without_fees, 4_000);
+        assert_eq!(pnl.impermanent_loss, -1_000);
+        assert_eq!(pnl.fees_earned, 0);
+        assert_eq!(pnl.pnl, -1_000);
+    }
+
+    #[test]
+    fn fees_grow_the_invariant() {
+        // a percent of fees accrued at an unchanged price
+        let pnl = position_pnl(&entry(false), &snapshot(1_010_000, 1_010_000)).unwrap();
+
+        assert_eq!(pnl.position_value, 2_020);
+        assert_eq!(pnl.value_without_fees, 2_000);
+        assert_eq!(pnl.impermanent_loss, 0);
+        assert_eq!(pnl.fees_earned, 20);
+        assert_eq!(pnl.pnl, 20);
+    }
+
+    #[test]
+    fn stable_position_loses_more_than_volatile_one_at_same_price() {
+        // moved along the stable curve, without fees
+        let (one_e_12, one_e_18) = (U256::exp10(12), U256::exp10(18));
+        let reserve = U256::from(SUPPLY) * one_e_12;
+        let xy = k(true, reserve, reserve, one_e_18, one_e_18);
+        let reserve_0 = 3 * SUPPLY / 2;
+        let y = get_y(U256::from(reserve_0) * one_e_12, xy, reserve).unwrap();
+        let reserve_1 = (y / one_e_12).as_u64();
+        let stable = position_pnl(&entry(true), &snapshot(reserve_0, reserve_1)).unwrap();
+
+        assert!(stable.impermanent_loss < 0);
+        assert_eq!(
+            stable.pnl,
+            stable.impermanent_loss + stable.fees_earned as i128 - stable.donated_value as i128
+        );
+        assert!(stable.fees_earned <= 1);
+
+        // same `xy` as at entry, at the price of asset_1 in the stable pool
+        let price = spot_price(
+            true,
+            U256::from(reserve_1),
+            U256::from(reserve_0),
+            pow_decimals(6),
+            pow_decimals(6),
+        );
+        let sqrt_price = (price * one_e_18).integer_sqrt();
+        let volatile = position_pnl(
+            &entry(false),
+            &snapshot(
+                (U256::from(SUPPLY) * sqrt_price / one_e_18).as_u64(),
+                (U256::from(SUPPLY) * one_e_18 / sqrt_price).as_u64(),
+            ),
+        )
+        .unwrap();
+
+        // the stable curve concentrates its liquidity around the peg
+        assert!(volatile.impermanent_loss < 0);
+        assert!(stable.impermanent_loss < volatile.impermanent_loss);
+    }
+
+    #[test]
+    fn rejects_values_beyond_i128() {
+        // the second asset is worth `u64::MAX` of the first one
+        let entry = LpEntry {
+            amount_1: u64::MAX,
+            ..entry(false)
+        };
+
+        assert_eq!(
+            position_pnl(&entry, &snapshot(u64::MAX, 1)),
+            Err(PnlError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use std::{cell::Cell, future::Future};
+
+use fuels::{
+    prelude::*,
+    types::{Bits256, Identity, Salt},
+};
+use proptest::prelude::*;
+
+use crate::{
+    data_structures::{AmmConfiguration, MiraAMMContract, WalletAssetConfiguration},
+    environment::{PeripheryScript, Scripts},
+    interface::{
+        amm::{create_pool, mint},
+        mock::{add_token, deploy_mock_token_contract_with_salt, get_sub_id, mint_tokens},
+    },
+    setup::common::{deploy_amm, setup_wallet_and_provider},
+    types::PoolId,
+    utils::common::order_sub_ids,
+};
+
+/// Cases run by every property, all of them on the node of their test thread
+pub const CASES: u32 = 64;
+
+/// Highest LP fee generated, in basis points
+const MAX_LP_FEE: u64 = 100;
+/// Highest protocol fee generated, in basis points
+const MAX_PROTOCOL_FEE: u64 = 30;
+const MIN_DECIMALS: u8 = 0;
+const MAX_DECIMALS: u8 = 18;
+const MIN_VOLATILE_RESERVE: u64 = 1_000_000;
+/// Highest reserve generated, so that amounts derived from reserves in basis points fit in `u64`
+const MAX_RESERVE: u64 = 1_000_000_000_000_000;
+/// Bounds of the stable reserves, in whole units, lowered where `MAX_RESERVE` requires it
+const MIN_STABLE_UNITS: u64 = 1_000;
+const MAX_STABLE_UNITS: u64 = 1_000_000;
+
+/// Pool of a generated route, reserves being given in the direction of the route
+#[derive(Debug, Clone)]
+pub struct PoolParams {
+    pub is_stable: bool,
+    pub reserve_in: u64,
+    pub reserve_out: u64,
+}
+
+/// Generated route: `decimals[i]` and `decimals[i + 1]` are the decimals of the assets
+/// of `pools[i]`
+#[derive(Debug, Clone)]
+pub struct RouteParams {
+    pub decimals: Vec<u8>,
+    pub pools: Vec<PoolParams>,
+}
+
+/// AMM deployed for a generated route, the wallet holding the LP assets of every pool
+pub struct RouteFixture {
+    pub amm: MiraAMMContract,
+    pub wallet: WalletUnlocked,
+    /// Assets along the route, from the input to the output
+    pub assets: Vec<AssetId>,
+    pub pools: Vec<PoolId>,
+    /// Scripts requested from `deploy_route`, loaded for the wallet
+    pub scripts: Scripts,
+    pub deadline: u32,
+}
+
+/// `(lp_fee_volatile, lp_fee_stable, protocol_fee_volatile, protocol_fee_stable)` fees in basis
+/// points, in the order the AMM `fees()` returns them
+pub fn amm_fees() -> impl Strategy<Value = (u64, u64, u64, u64)> {
+    (
+        0..=MAX_LP_FEE,
+        0..=MAX_LP_FEE,
+        0..=MAX_PROTOCOL_FEE,
+        0..=MAX_PROTOCOL_FEE,
+    )
+}
+
+/// Routes of 1 to `max_pools` pools of either type, between assets of random decimals
+pub fn route_params(max_pools: usize) -> impl Strategy<Value = RouteParams> {
+    (1..=max_pools)
+        .prop_flat_map(|pools| {
+            prop::collection::vec(MIN_DECIMALS..=MAX_DECIMALS, pools + 1).prop_flat_map(
+                |decimals| {
+                    let pools: Vec<_> = decimals
+                        .windows(2)
+                        .map(|pair| pool_params(pair[0], pair[1]))
+                        .collect();
+                    (Just(decimals), pools)
+                },
+            )
+        })
+        .prop_map(|(decimals, pools)| RouteParams { decimals, pools })
+}
+
+fn pool_params(decimals_in: u8, decimals_out: u8) -> BoxedStrategy<PoolParams> {
+    let volatile = (
+        MIN_VOLATILE_RESERVE..=MAX_RESERVE,
+        MIN_VOLATILE_RESERVE..=MAX_RESERVE,
+    )
+        .prop_map(|(reserve_in, reserve_out)| PoolParams {
+            is_stable: false,
+            reserve_in,
+            reserve_out,
+        });
+    let Some((min_reserve, max_reserve)) = stable_reserve_bounds(decimals_in, decimals_out) else {
+        return volatile.boxed();
+    };
+
+    // stable pools stay within 2x of their peg, where the curve is meant to be traded
+    let min_decimals = decimals_in.min(decimals_out);
+    let scale_in = 10u64.pow((decimals_in - min_decimals).into());
+    let scale_out = 10u64.pow((decimals_out - min_decimals).into());
+    let stable =
+        (min_reserve..=max_reserve, 50..=200u64).prop_map(move |(reserve, ratio_percent)| {
+            PoolParams {
+                is_stable: true,
+                reserve_in: reserve * scale_in,
+                reserve_out: (reserve * scale_out * ratio_percent / 100).max(1),
+            }
+        });
+    prop_oneof![volatile, stable].boxed()
+}
+
+/// Bounds of the stable reserve of the asset with fewer decimals: `MIN_STABLE_UNITS` to
+/// `MAX_STABLE_UNITS` whole units, lowered for the other reserve to stay under `MAX_RESERVE`
+/// at twice the peg. `None` when the decimals are too far apart for a single unit to fit.
+fn stable_reserve_bounds(decimals_in: u8, decimals_out: u8) -> Option<(u64, u64)> {
+    let pow = |decimals: u8| 10u128.pow(decimals.into());
+    let min_decimals = decimals_in.min(decimals_out);
+    let max_reserve = (u128::from(MAX_STABLE_UNITS) * pow(min_decimals))
+        .min(u128::from(MAX_RESERVE) / 2 / pow(decimals_in.abs_diff(decimals_out)));
+    if max_reserve == 0 {
+        return None;
+    }
+    let min_reserve = (u128::from(MIN_STABLE_UNITS) * pow(min_decimals)).min(max_reserve);
+    Some((
+        u64::try_from(min_reserve).unwrap(),
+        u64::try_from(max_reserve).unwrap(),
+    ))
+}
+
+/// Node the property cases of a test thread run on, so that they don't start one each
+struct CaseNode {
+    runtime: tokio::runtime::Runtime,
+    wallet: WalletUnlocked,
+    /// Cases deployed so far, salting the contracts of the next one apart from theirs
+    cases: Cell<u64>,
+}
+
+thread_local! {
+    static CASE_NODE: CaseNode = CaseNode::start();
+}
+
+impl CaseNode {
+    fn start() -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        // every case deploys its own AMM and tokens, paying for them with the base asset
+        let (wallet, _asset_ids, _provider) =
+            runtime.block_on(setup_wallet_and_provider(&WalletAssetConfiguration {
+                number_of_assets: 1,
+                coins_per_asset: 1_000,
+                amount_per_coin: 1_000_000_000,
+            }));
+        Self {
+            runtime,
+            wallet,
+            cases: Cell::new(0),
+        }
+    }
+
+    fn next_salt(&self) -> Salt {
+        let case = self.cases.get() + 1;
+        self.cases.set(case);
+        let mut salt = [0u8; 32];
+        salt[24..].copy_from_slice(&case.to_be_bytes());
+        Salt::new(salt)
+    }
+}
+
+/// Runs a case on the node of the test thread, as `proptest!` cases can't be async
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    CASE_NODE.with(|node| node.runtime.block_on(future))
+}
+
+/// Deploys the AMM with `fees` and the pools of `route`, seeded with their reserves, on a node
+/// of its own and loads `scripts` for the wallet. The wallet is left with as much of every
+/// asset as the pools hold.
+pub async fn deploy_route(
+    route: &RouteParams,
+    fees: (u64, u64, u64, u64),
+    scripts: &[PeripheryScript],
+) -> RouteFixture {
+    let (wallet, _asset_ids, _provider) =
+        setup_wallet_and_provider(&WalletAssetConfiguration::default()).await;
+    deploy_route_for(wallet, Salt::default(), route, fees, scripts).await
+}
+
+/// `deploy_route` on the node of the case run by `block_on`, with contracts salted apart from
+/// those of earlier cases. The assets and LP assets of the route are new to the wallet, which
+/// may hold those of earlier cases.
+pub async fn deploy_case_route(
+    route: &RouteParams,
+    fees: (u64, u64, u64, u64),
+    scripts: &[PeripheryScript],
+) -> RouteFixture {
+    let (wallet, salt) = CASE_NODE.with(|node| (node.wallet.clone(), node.next_salt()));
+    deploy_route_for(wallet, salt, route, fees, scripts).await
+}
+
+async fn deploy_route_for(
+    wallet: WalletUnlocked,
+    salt: Salt,
+    route: &RouteParams,
+    fees: (u64, u64, u64, u64),
+    scripts: &[PeripheryScript],
+) -> RouteFixture {
+    let (lp_fee_volatile, lp_fee_stable, protocol_fee_volatile, protocol_fee_stable) = fees;
+    let amm = deploy_amm(
+        &wallet,
+        &AmmConfiguration {
+            lp_fees: Some((lp_fee_volatile, lp_fee_stable)),
+            protocol_fees: Some((protocol_fee_volatile, protocol_fee_stable)),
+            salt,
+            ..Default::default()
+        },
+    )
+    .await;
+    let (token_contract_id, token_contract) =
+        deploy_mock_token_contract_with_salt(&wallet, salt).await;
+
+    let mut assets: Vec<AssetId> = Vec::with_capacity(route.decimals.len());
+    let mut sub_ids: Vec<Bits256> = Vec::with_capacity(route.decimals.len());
+    for (index, decimals) in route.decimals.iter().enumerate() {
+        let asset_id = add_token(
+            &token_contract,
+            format!("TOKEN_{index}"),
+            format!("TK{index}"),
+            *decimals,
+        )
+        .await
+        .value;
+        sub_ids.push(get_sub_id(&token_contract, asset_id).await.value.unwrap());
+
+        let reserves: u64 = route
+            .pools
+            .iter()
+            .enumerate()
+            .map(|(pool, params)| {
+                if index == pool {
+                    params.reserve_in
+                } else if index == pool + 1 {
+                    params.reserve_out
+                } else {
+                    0
+                }
+            })
+            .sum();
+        mint_tokens(&token_contract, asset_id, reserves * 2).await;
+        assets.push(asset_id);
+    }
+
+    let mut pools = Vec::with_capacity(route.pools.len());
+    for (index, params) in route.pools.iter().enumerate() {
+        let (asset_in, asset_out) = (assets[index], assets[index + 1]);
+        let (sub_id_0, sub_id_1) =
+            order_sub_ids((asset_in, asset_out), (sub_ids[index], sub_ids[index + 1]));
+        let pool_id = create_pool(
+            &amm.instance,
+            &token_contract,
+            token_contract_id,
+            sub_id_0,
+            token_contract_id,
+            sub_id_1,
+            params.is_stable,
+        )
+        .await
+        .value;
+
+        // deposited straight into the AMM, so that the fixture doesn't depend on the
+        // `add_liquidity_script` it is used to test
+        for (asset, amount) in [
+            (asset_in, params.reserve_in),
+            (asset_out, params.reserve_out),
+        ] {
+            wallet
+                .force_transfer_to_contract(
+                    amm.instance.contract_id(),
+                    amount,
+                    asset,
+                    TxPolicies::default(),
+                )
+                .await
+                .unwrap();
+        }
+        mint(
+            &amm.instance,
+            pool_id,
+            Identity::Address(wallet.address().into()),
+        )
+        .await;
+        pools.push(pool_id);
+    }
+
+    let scripts = Scripts::new(&wallet, amm.id, scripts).await;
+    let provider = wallet.try_provider().unwrap();
+    let deadline = provider.latest_block_height().await.unwrap() + 10;
+
+    RouteFixture {
+        amm,
+        wallet,
+        assets,
+        pools,
+        scripts,
+        deadline,
+    }
+}
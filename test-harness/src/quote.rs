@@ -0,0 +1,396 @@
+use fuels::types::{AssetId, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    interface::PoolMetadata,
+    math::{
+        add_fee, get_amount_in, get_amount_out, pow_decimals, rounding_up_division, spot_price,
+        subtract_fee, swap_fees, BASIS_POINTS_DENOMINATOR,
+    },
+    types::PoolId,
+};
+
+/// One pool of a quoted route.
+///
+/// Prices are the amount of whole `asset_out` units paid for one whole `asset_in` unit,
+/// scaled by 1e18, and are serialized as decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HopQuote {
+    pub pool_id: PoolId,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    /// Amount sent to the pool, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// LP and protocol fee taken out of `amount_in`
+    pub fee: u64,
+    #[serde(with = "u256_decimal")]
+    pub spot_price_before: U256,
+    /// Price actually paid, fee included
+    #[serde(with = "u256_decimal")]
+    pub execution_price: U256,
+    /// Spot price once `amount_in` has been swapped, the LP fee staying in the pool
+    #[serde(with = "u256_decimal")]
+    pub spot_price_after: U256,
+    /// Shortfall of the price paid for the amount net of fee against `spot_price_before`,
+    /// rounded up
+    pub price_impact_bps: u64,
+}
+
+/// Per hop breakdown of a route, built from the same math as the router `get_amounts_out`
+/// and `get_amounts_in` over already fetched `fees()` and pool metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteReport {
+    pub asset_in: AssetId,
+    pub amount_in: u64,
+    pub asset_out: AssetId,
+    pub amount_out: u64,
+    /// In the order the route is swapped
+    pub hops: Vec<HopQuote>,
+}
+
+impl QuoteReport {
+    /// Quote for swapping exactly `amount_in` of `asset_in` through `pools`
+    pub fn exact_input(
+        fees: (u64, u64, u64, u64),
+        pools: &[(PoolId, PoolMetadata)],
+        amount_in: u64,
+        asset_in: AssetId,
+    ) -> Self {
+        assert!(!pools.is_empty(), "Router: INVALID_PATH");
+
+        let mut hops: Vec<HopQuote> = Vec::with_capacity(pools.len());
+        let (mut amount, mut asset) = (amount_in, asset_in);
+        for (pool_id, pool) in pools {
+            let side = PoolSide::new(pool_id, pool, asset);
+            let (fee, protocol_fee) = swap_fees(fees, pool_id.2);
+            let amount_in_net = subtract_fee(amount, fee);
+            let amount_out = get_amount_out(
+                pool_id.2,
+                side.reserve_in,
+                side.reserve_out,
+                side.pow_decimals_in,
+                side.pow_decimals_out,
+                U256::from(amount_in_net),
+            );
+            let hop = side.hop(
+                *pool_id,
+                amount,
+                amount_in_net,
+                u64::try_from(amount_out).unwrap(),
+                protocol_fee,
+            );
+            (amount, asset) = (hop.amount_out, hop.asset_out);
+            hops.push(hop);
+        }
+
+        Self {
+            asset_in,
+            amount_in,
+            asset_out: asset,
+            amount_out: amount,
+            hops,
+        }
+    }
+
+    /// Quote for receiving exactly `amount_out` of `asset_out` through `pools`
+    pub fn exact_output(
+        fees: (u64, u64, u64, u64),
+        pools: &[(PoolId, PoolMetadata)],
+        amount_out: u64,
+        asset_out: AssetId,
+    ) -> Self {
+        assert!(!pools.is_empty(), "Router: INVALID_PATH");
+
+        let mut hops: Vec<HopQuote> = Vec::with_capacity(pools.len());
+        let (mut amount, mut asset) = (amount_out, asset_out);
+        for (pool_id, pool) in pools.iter().rev() {
+            let asset_in = if pool_id.0 == asset {
+                pool_id.1
+            } else {
+                pool_id.0
+            };
+            let side = PoolSide::new(pool_id, pool, asset_in);
+            let amount_in_net = u64::try_from(get_amount_in(
+                pool_id.2,
+                side.reserve_in,
+                side.reserve_out,
+                side.pow_decimals_in,
+                side.pow_decimals_out,
+                U256::from(amount),
+            ))
+            .unwrap();
+            let (fee, protocol_fee) = swap_fees(fees, pool_id.2);
+            let amount_in = add_fee(amount_in_net, fee);
+            let hop = side.hop(*pool_id, amount_in, amount_in_net, amount, protocol_fee);
+            (amount, asset) = (hop.amount_in, hop.asset_in);
+            hops.push(hop);
+        }
+        hops.reverse();
+
+        Self {
+            asset_in: asset,
+            amount_in: amount,
+            asset_out,
+            amount_out,
+            hops,
+        }
+    }
+
+    /// Impact of the whole route, compounding the impact of every hop
+    pub fn price_impact_bps(&self) -> u64 {
+        let denominator = U256::from(BASIS_POINTS_DENOMINATOR);
+        let remaining = self.hops.iter().fold(denominator, |remaining, hop| {
+            remaining * U256::from(BASIS_POINTS_DENOMINATOR - hop.price_impact_bps) / denominator
+        });
+        BASIS_POINTS_DENOMINATOR - u64::try_from(remaining).unwrap()
+    }
+}
+
+/// Pool reserves and decimals ordered in the swap direction
+struct PoolSide {
+    is_stable: bool,
+    asset_in: AssetId,
+    asset_out: AssetId,
+    reserve_in: U256,
+    reserve_out: U256,
+    pow_decimals_in: U256,
+    pow_decimals_out: U256,
+}
+
+impl PoolSide {
+    fn new(pool_id: &PoolId, pool: &PoolMetadata, asset_in: AssetId) -> Self {
+        if asset_in == pool_id.0 {
+            Self {
+                is_stable: pool_id.2,
+                asset_in,
+                asset_out: pool_id.1,
+                reserve_in: U256::from(pool.reserve_0),
+                reserve_out: U256::from(pool.reserve_1),
+                pow_decimals_in: pow_decimals(pool.decimals_0),
+                pow_decimals_out: pow_decimals(pool.decimals_1),
+            }
+        } else {
+            Self {
+                is_stable: pool_id.2,
+                asset_in,
+                asset_out: pool_id.0,
+                reserve_in: U256::from(pool.reserve_1),
+                reserve_out: U256::from(pool.reserve_0),
+                pow_decimals_in: pow_decimals(pool.decimals_1),
+                pow_decimals_out: pow_decimals(pool.decimals_0),
+            }
+        }
+    }
+
+    fn hop(
+        &self,
+        pool_id: PoolId,
+        amount_in: u64,
+        amount_in_net: u64,
+        amount_out: u64,
+        protocol_fee: u64,
+    ) -> HopQuote {
+        let spot_price_before = spot_price(
+            self.is_stable,
+            self.reserve_in,
+            self.reserve_out,
+            self.pow_decimals_in,
+            self.pow_decimals_out,
+        );
+        // reserves the swap leaves: the LP fee stays in the pool, the protocol fee doesn't
+        let spot_price_after = spot_price(
+            self.is_stable,
+            self.reserve_in + U256::from(subtract_fee(amount_in, protocol_fee)),
+            self.reserve_out - U256::from(amount_out),
+            self.pow_decimals_in,
+            self.pow_decimals_out,
+        );
+        let price_paid_net = self.price(amount_in_net, amount_out);
+        let price_impact_bps = if price_paid_net >= spot_price_before {
+            0
+        } else {
+            let shortfall =
+                (spot_price_before - price_paid_net) * U256::from(BASIS_POINTS_DENOMINATOR);
+            u64::try_from(rounding_up_division(shortfall, spot_price_before)).unwrap()
+        };
+
+        HopQuote {
+            pool_id,
+            asset_in: self.asset_in,
+            asset_out: self.asset_out,
+            amount_in,
+            amount_out,
+            fee: amount_in - amount_in_net,
+            spot_price_before,
+            execution_price: self.price(amount_in, amount_out),
+            spot_price_after,
+            price_impact_bps,
+        }
+    }
+
+    /// Whole units of output per whole unit of input, scaled by 1e18
+    fn price(&self, amount_in: u64, amount_out: u64) -> U256 {
+        if amount_in == 0 {
+            return U256::zero();
+        }
+        U256::from(amount_out) * self.pow_decimals_in * U256::exp10(18)
+            / (U256::from(amount_in) * self.pow_decimals_out)
+    }
+}
+
+/// Serializes `U256` as a decimal string, which JSON numbers can't hold losslessly
+mod u256_decimal {
+    use fuels::types::U256;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_dec_str(&value).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::Asset,
+        math::{get_amounts_in, get_amounts_out, marginal_price_after_trade},
+    };
+
+    const FEES: (u64, u64, u64, u64) = (30, 5, 0, 0);
+
+    fn pool(reserve_0: u64, reserve_1: u64, decimals_0: u8, decimals_1: u8) -> PoolMetadata {
+        PoolMetadata {
+            reserve_0,
+            reserve_1,
+            liquidity: Asset {
+                id: AssetId::default(),
+                amount: 0,
+            },
+            decimals_0,
+            decimals_1,
+        }
+    }
+
+    /// asset_0 -(volatile, 1 : 2)-> asset_1 -(stable, 1 : 1.05)-> asset_2
+    fn route() -> (AssetId, AssetId, Vec<(PoolId, PoolMetadata)>) {
+        let (asset_0, asset_1, asset_2) = (
+            AssetId::new([1; 32]),
+            AssetId::new([2; 32]),
+            AssetId::new([3; 32]),
+        );
+        let pools = vec![
+            (
+                (asset_0, asset_1, false),
+                pool(1_000_000_000_000, 2_000_000_000, 9, 6),
+            ),
+            (
+                (asset_1, asset_2, true),
+                pool(2_000_000_000, 2_100_000_000, 6, 6),
+            ),
+        ];
+        (asset_0, asset_2, pools)
+    }
+
+    #[test]
+    fn exact_input_matches_get_amounts_out() {
+        let (asset_in, asset_out, pools) = route();
+        let report = QuoteReport::exact_input(FEES, &pools, 1_000_000_000, asset_in);
+
+        let amounts = get_amounts_out(FEES, &pools, 1_000_000_000, asset_in);
+        assert_eq!(report.amount_out, amounts[2].0);
+        assert_eq!(report.asset_out, asset_out);
+        for (i, hop) in report.hops.iter().enumerate() {
+            assert_eq!((hop.amount_in, hop.asset_in), amounts[i]);
+            assert_eq!((hop.amount_out, hop.asset_out), amounts[i + 1]);
+        }
+
+        let first = &report.hops[0];
+        assert_eq!(first.fee, 3_000_000);
+        assert_eq!(first.spot_price_before, U256::exp10(18) * 2);
+        assert!(first.execution_price < first.spot_price_before);
+        assert!(first.spot_price_after < first.spot_price_before);
+        assert_eq!(first.price_impact_bps, 10);
+        assert!(report.price_impact_bps() >= first.price_impact_bps);
+    }
+
+    #[test]
+    fn exact_output_matches_get_amounts_in() {
+        let (asset_in, asset_out, pools) = route();
+        let report = QuoteReport::exact_output(FEES, &pools, 1_000_000, asset_out);
+
+        let amounts = get_amounts_in(FEES, &pools, 1_000_000, asset_out);
+        assert_eq!(
+            (report.amount_in, report.asset_in),
+            (amounts[2].0, asset_in)
+        );
+        for (i, hop) in report.hops.iter().rev().enumerate() {
+            assert_eq!((hop.amount_out, hop.asset_out), amounts[i]);
+            assert_eq!((hop.amount_in, hop.asset_in), amounts[i + 1]);
+            assert!(hop.fee > 0);
+        }
+    }
+
+    #[test]
+    fn spot_price_after_keeps_the_lp_fee_in_the_pool() {
+        let fees = (30, 5, 10, 5);
+        let (asset_in, asset_out, pools) = route();
+        let (reserve_in, reserve_out) =
+            (U256::from(1_000_000_000_000_u64), U256::from(2_000_000_000));
+        let (pow_9, pow_6) = (pow_decimals(9), pow_decimals(6));
+
+        let report = QuoteReport::exact_input(fees, &pools, 1_000_000_000, asset_in);
+        let first = &report.hops[0];
+        assert_eq!(
+            first.spot_price_after,
+            marginal_price_after_trade(
+                false,
+                reserve_in,
+                reserve_out,
+                pow_9,
+                pow_6,
+                first.amount_in,
+                40,
+                10
+            )
+        );
+
+        let report = QuoteReport::exact_output(fees, &pools, 1_000_000, asset_out);
+        let first = &report.hops[0];
+        assert_eq!(
+            first.spot_price_after,
+            spot_price(
+                false,
+                reserve_in + U256::from(subtract_fee(first.amount_in, 10)),
+                reserve_out - U256::from(first.amount_out),
+                pow_9,
+                pow_6
+            )
+        );
+        assert!(
+            first.spot_price_after
+                < spot_price(
+                    false,
+                    reserve_in + U256::from(first.amount_in - first.fee),
+                    reserve_out - U256::from(first.amount_out),
+                    pow_9,
+                    pow_6
+                )
+        );
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let (asset_in, _, pools) = route();
+        let report = QuoteReport::exact_input(FEES, &pools, 1_000_000_000, asset_in);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["hops"][0]["spot_price_before"], "2000000000000000000");
+        assert_eq!(serde_json::from_value::<QuoteReport>(json).unwrap(), report);
+    }
+}
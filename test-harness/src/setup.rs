@@ -1,6 +1,9 @@
-use fuels::prelude::{
-    AssetId, Contract, LoadConfiguration, Provider, StorageConfiguration, TxPolicies,
-    WalletUnlocked,
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use fuels::{
+    prelude::{Account, AssetId, Predicate, Provider, WalletUnlocked},
+    types::Identity,
 };
 
 pub mod common {
@@ -8,22 +11,42 @@ pub mod common {
     use fuels::test_helpers::{setup_multiple_assets_coins, setup_test_provider};
 
     use crate::{
-        data_structures::{MiraAMMContract, WalletAssetConfiguration},
-        interface::MiraAMM,
-        paths::AMM_CONTRACT_BINARY_PATH,
+        data_structures::{AmmConfiguration, MiraAMMContract, WalletAssetConfiguration},
+        fixtures::AMM,
+        interface::{
+            amm::{initialize_ownership, set_protocol_fees},
+            MiraAMM, MiraAMMConfigurables,
+        },
+        paths::ACCOUNT_PREDICATE_BINARY_PATH,
     };
 
-    pub async fn deploy_amm(wallet: &WalletUnlocked) -> MiraAMMContract {
-        let configuration = LoadConfiguration::default()
-            .with_storage_configuration(StorageConfiguration::default());
-
-        let contract_id = Contract::load_from(AMM_CONTRACT_BINARY_PATH, configuration)
-            .unwrap()
-            .deploy(wallet, TxPolicies::default())
-            .await
-            .unwrap();
+    /// Deploys the AMM fixture with its shipped storage slots and configured LP fees, then
+    /// initializes its ownership and protocol fees as configured. Setting the protocol fees
+    /// requires `account` to be the owner.
+    pub async fn deploy_amm<A: Account>(
+        account: &A,
+        configuration: &AmmConfiguration,
+    ) -> MiraAMMContract<A> {
+        let mut configurables = MiraAMMConfigurables::default();
+        if let Some((volatile_fee, stable_fee)) = configuration.lp_fees {
+            configurables = configurables
+                .with_LP_FEE_VOLATILE(volatile_fee)
+                .unwrap()
+                .with_LP_FEE_STABLE(stable_fee)
+                .unwrap();
+        }
+        let contract_id = AMM
+            .deploy_with(account, configurables, configuration.salt)
+            .await;
 
-        let instance = MiraAMM::new(contract_id.clone(), wallet.clone());
+        let instance = MiraAMM::new(contract_id.clone(), account.clone());
+        let owner = configuration
+            .owner
+            .unwrap_or_else(|| Identity::Address(account.address().into()));
+        initialize_ownership(&instance, owner).await;
+        if let Some((volatile_fee, stable_fee)) = configuration.protocol_fees {
+            set_protocol_fees(&instance, volatile_fee, stable_fee).await;
+        }
 
         MiraAMMContract {
             instance,
@@ -51,4 +74,39 @@ pub mod common {
 
         (wallet, asset_ids, provider)
     }
+
+    /// Predicate spendable by any transaction, standing for a custody account.
+    /// It holds nothing until funded, including the base asset it pays fees with.
+    pub fn predicate_account(provider: &Provider) -> Predicate {
+        Predicate::load_from(ACCOUNT_PREDICATE_BINARY_PATH)
+            .unwrap()
+            .with_provider(provider.clone())
+    }
+
+    /// Height at which the next transaction is executed, which is what `height()` returns to it,
+    /// i.e. the earliest deadline the scripts accept
+    pub async fn execution_height(provider: &Provider) -> u32 {
+        provider.latest_block_height().await.unwrap() + 1
+    }
+
+    /// Produces `blocks` empty blocks and returns the new latest height
+    pub async fn produce_blocks(provider: &Provider, blocks: u32) -> u32 {
+        provider.produce_blocks(blocks, None).await.unwrap()
+    }
+
+    /// Produces empty blocks until the latest height reaches `height`
+    pub async fn produce_blocks_until(provider: &Provider, height: u32) {
+        let current_height = provider.latest_block_height().await.unwrap();
+        if height > current_height {
+            produce_blocks(provider, height - current_height).await;
+        }
+    }
+
+    /// Produces a block timestamped `duration` after the latest one and returns its timestamp
+    pub async fn advance_time(provider: &Provider, duration: Duration) -> DateTime<Utc> {
+        let latest_time = provider.latest_block_time().await.unwrap().unwrap();
+        let time = latest_time + chrono::Duration::from_std(duration).unwrap();
+        provider.produce_blocks(1, Some(time)).await.unwrap();
+        time
+    }
 }
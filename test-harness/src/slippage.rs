@@ -0,0 +1,191 @@
+use fuels::types::{AssetId, Identity, U256};
+
+use crate::{
+    interface::MintEvent,
+    math::{rounding_up_division, BASIS_POINTS_DENOMINATOR},
+    types::PoolId,
+};
+
+/// Slippage accepted on top of a simulated script result.
+///
+/// Bounds are always rounded so that they never accept more slippage than the tolerance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tolerance {
+    /// Same tolerance for every pool, in basis points
+    Bps(u64),
+    /// Tolerance per pool type in basis points, compounded over the pools of a route
+    Profile { volatile_bps: u64, stable_bps: u64 },
+}
+
+impl Tolerance {
+    /// Tolerance of a swap through `pools`, in basis points
+    pub fn route_bps(&self, pools: &[PoolId]) -> u64 {
+        match *self {
+            Tolerance::Bps(bps) => checked_bps(bps),
+            Tolerance::Profile {
+                volatile_bps,
+                stable_bps,
+            } => {
+                // 1 - (1 - t_0) * (1 - t_1) * ..., the part kept being rounded up
+                let denominator = U256::from(BASIS_POINTS_DENOMINATOR);
+                let kept = pools.iter().fold(denominator, |kept, pool_id| {
+                    let bps = if pool_id.2 { stable_bps } else { volatile_bps };
+                    let kept_bps = BASIS_POINTS_DENOMINATOR - checked_bps(bps);
+                    let nominator = kept * U256::from(kept_bps);
+                    rounding_up_division(nominator, denominator)
+                });
+                BASIS_POINTS_DENOMINATOR - u64::try_from(kept).unwrap()
+            }
+        }
+    }
+}
+
+/// `amount_out_min` of the `swap_exact_input_script`, from its simulated amounts
+pub fn swap_exact_input_amount_out_min(
+    simulated: &[(u64, AssetId)],
+    pools: &[PoolId],
+    tolerance: Tolerance,
+) -> u64 {
+    let (amount_out, _) = simulated.last().unwrap();
+    min_bound(*amount_out, tolerance.route_bps(pools))
+}
+
+/// `amount_in_max` of the `swap_exact_output_script`, from its simulated amounts
+pub fn swap_exact_output_amount_in_max(
+    simulated: &[(u64, AssetId)],
+    pools: &[PoolId],
+    tolerance: Tolerance,
+) -> u64 {
+    // amounts go from the output back to the input
+    let (amount_in, _) = simulated.last().unwrap();
+    max_bound(*amount_in, tolerance.route_bps(pools))
+}
+
+/// `amount_out_min` of the `swap_exact_input_split_script`, which bounds the total output
+pub fn swap_exact_input_split_amount_out_min(
+    simulated: &[(Identity, u64, u64)],
+    pools: &[PoolId],
+    tolerance: Tolerance,
+) -> u64 {
+    let total_amount_out = simulated.iter().map(|(_, _, amount_out)| *amount_out).sum();
+    min_bound(total_amount_out, tolerance.route_bps(pools))
+}
+
+/// `amount_out_min` of every order of the `batch_swap_script`, `routes` being the order pools
+pub fn batch_swap_amount_out_mins(
+    simulated: &[Vec<(u64, AssetId)>],
+    routes: &[Vec<PoolId>],
+    tolerance: Tolerance,
+) -> Vec<u64> {
+    assert_eq!(simulated.len(), routes.len(), "One route per order");
+    simulated
+        .iter()
+        .zip(routes)
+        .map(|(amounts, pools)| swap_exact_input_amount_out_min(amounts, pools, tolerance))
+        .collect()
+}
+
+/// `(amount_0_min, amount_1_min)` of the `add_liquidity_script`, from the deposit of its
+/// simulated `MintEvent`
+pub fn add_liquidity_amount_mins(simulated: &MintEvent, tolerance: Tolerance) -> (u64, u64) {
+    let bps = tolerance.route_bps(&[simulated.pool_id]);
+    (
+        min_bound(simulated.asset_0_in, bps),
+        min_bound(simulated.asset_1_in, bps),
+    )
+}
+
+/// `(amount_0_min, amount_1_min)` of the `remove_liquidity_script`, from its simulated output
+pub fn remove_liquidity_amount_mins(
+    pool_id: PoolId,
+    simulated: (u64, u64),
+    tolerance: Tolerance,
+) -> (u64, u64) {
+    let bps = tolerance.route_bps(&[pool_id]);
+    (min_bound(simulated.0, bps), min_bound(simulated.1, bps))
+}
+
+fn checked_bps(bps: u64) -> u64 {
+    assert!(bps <= BASIS_POINTS_DENOMINATOR, "Tolerance above 100%");
+    bps
+}
+
+/// `amount` reduced by `bps`, rounded up
+fn min_bound(amount: u64, bps: u64) -> u64 {
+    let nominator = U256::from(amount) * U256::from(BASIS_POINTS_DENOMINATOR - bps);
+    u64::try_from(rounding_up_division(
+        nominator,
+        U256::from(BASIS_POINTS_DENOMINATOR),
+    ))
+    .unwrap()
+}
+
+/// `amount` increased by `bps`, rounded down and capped to `u64::MAX`
+fn max_bound(amount: u64, bps: u64) -> u64 {
+    let bound = U256::from(amount) * U256::from(BASIS_POINTS_DENOMINATOR + bps)
+        / U256::from(BASIS_POINTS_DENOMINATOR);
+    u64::try_from(bound).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(is_stable: bool) -> PoolId {
+        (AssetId::new([1; 32]), AssetId::new([2; 32]), is_stable)
+    }
+
+    #[test]
+    fn bounds_never_exceed_tolerance() {
+        assert_eq!(min_bound(996, 50), 992); // 991.02 rounded up
+        assert_eq!(max_bound(1006, 50), 1011); // 1011.03 rounded down
+        assert_eq!(min_bound(u64::MAX, 0), u64::MAX);
+        assert_eq!(max_bound(u64::MAX, 1), u64::MAX);
+        assert_eq!(min_bound(1, BASIS_POINTS_DENOMINATOR), 0);
+    }
+
+    #[test]
+    fn profile_compounds_over_route() {
+        let profile = Tolerance::Profile {
+            volatile_bps: 100,
+            stable_bps: 10,
+        };
+
+        assert_eq!(profile.route_bps(&[pool(false)]), 100);
+        assert_eq!(profile.route_bps(&[pool(true)]), 10);
+        // 1 - 0.99 * 0.99 = 199 bps
+        assert_eq!(profile.route_bps(&[pool(false), pool(false)]), 199);
+        // 1 - 0.99 * 0.999 = 109.9 bps, rounded down
+        assert_eq!(profile.route_bps(&[pool(false), pool(true)]), 109);
+        assert_eq!(Tolerance::Bps(30).route_bps(&[pool(false), pool(true)]), 30);
+    }
+
+    #[test]
+    fn swap_bounds_use_route_ends() {
+        let (asset_0, asset_1) = (AssetId::new([1; 32]), AssetId::new([2; 32]));
+        let pools = [pool(false)];
+
+        assert_eq!(
+            swap_exact_input_amount_out_min(
+                &[(1000, asset_0), (996, asset_1)],
+                &pools,
+                Tolerance::Bps(100)
+            ),
+            987
+        );
+        assert_eq!(
+            swap_exact_output_amount_in_max(
+                &[(1000, asset_1), (1006, asset_0)],
+                &pools,
+                Tolerance::Bps(100)
+            ),
+            1016
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Tolerance above 100%")]
+    fn panics_on_tolerance_above_100_percent() {
+        Tolerance::Bps(BASIS_POINTS_DENOMINATOR + 1).route_bps(&[pool(false)]);
+    }
+}
@@ -1,5 +1,5 @@
 use fuels::{
-    accounts::{wallet::WalletUnlocked, ViewOnlyAccount},
+    accounts::{Account, ViewOnlyAccount},
     types::{AssetId, Bits256, Bytes32, ContractId},
 };
 use sha2::{Digest, Sha256};
@@ -8,19 +8,20 @@ use crate::data_structures::WalletBalances;
 
 pub mod common {
     use super::*;
+    use crate::interface::{amm::pool_metadata, MiraAMM, PoolMetadata};
     use crate::types::PoolId;
     use std::io::Write;
     pub const MINIMUM_LIQUIDITY: u64 = 1000;
 
     pub async fn pool_assets_balance(
-        wallet: &WalletUnlocked,
+        account: &impl ViewOnlyAccount,
         pool_id: &PoolId,
         contract_id: ContractId,
     ) -> WalletBalances {
-        let asset_a = wallet.get_asset_balance(&pool_id.0).await.unwrap();
-        let asset_b = wallet.get_asset_balance(&pool_id.1).await.unwrap();
+        let asset_a = account.get_asset_balance(&pool_id.0).await.unwrap();
+        let asset_b = account.get_asset_balance(&pool_id.1).await.unwrap();
         let lp_asset = get_lp_asset_id(contract_id, pool_id);
-        let liquidity_pool_asset = wallet.get_asset_balance(&lp_asset).await.unwrap();
+        let liquidity_pool_asset = account.get_asset_balance(&lp_asset).await.unwrap();
         WalletBalances {
             asset_a,
             asset_b,
@@ -28,6 +29,18 @@ pub mod common {
         }
     }
 
+    pub async fn pools_metadata<A: Account>(
+        contract: &MiraAMM<A>,
+        pools: &[PoolId],
+    ) -> Vec<(PoolId, PoolMetadata)> {
+        let mut metadata = Vec::with_capacity(pools.len());
+        for pool_id in pools {
+            let pool = pool_metadata(contract, *pool_id).await.value.unwrap();
+            metadata.push((*pool_id, pool));
+        }
+        metadata
+    }
+
     pub fn get_lp_asset_id(contract_id: ContractId, pool_id: &PoolId) -> AssetId {
         let sub_id = get_pool_sub_id(pool_id);
         get_contract_asset_id(sub_id, contract_id)
@@ -73,12 +86,12 @@ pub mod common {
 }
 
 pub mod mock {
-    use fuels::{accounts::wallet::WalletUnlocked, types::ContractId};
+    use fuels::{accounts::Account, types::ContractId};
 
     use crate::interface::mock;
 
-    pub async fn deploy_2_mock_tokens(
-        wallet: &WalletUnlocked,
+    pub async fn deploy_2_mock_tokens<A: Account>(
+        account: &A,
         name_0: String,
         symbol_0: String,
         decimals_0: u8,
@@ -86,7 +99,7 @@ pub mod mock {
         symbol_1: String,
         decimals_1: u8,
     ) -> ContractId {
-        let (token_contract_id, token_contract) = mock::deploy_mock_token_contract(wallet).await;
+        let (token_contract_id, token_contract) = mock::deploy_mock_token_contract(account).await;
 
         mock::add_token(&token_contract, name_0, symbol_0, decimals_0)
             .await
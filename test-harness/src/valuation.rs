@@ -0,0 +1,48 @@
+use fuels::types::ContractId;
+
+use crate::{
+    interface::{PoolMetadata, PositionValue},
+    math::get_burn_amounts,
+    types::PoolId,
+    utils::common::get_lp_asset_id,
+};
+
+/// Mirrors the `position_valuation_script` over indexed pool snapshots.
+/// Panics if a position refers to a pool missing from `snapshots`.
+pub fn value_positions(
+    amm_contract_id: ContractId,
+    snapshots: &[(PoolId, PoolMetadata)],
+    positions: &[(PoolId, u64)],
+) -> Vec<PositionValue> {
+    positions
+        .iter()
+        .map(|(pool_id, liquidity)| {
+            let (_, pool) = snapshots
+                .iter()
+                .find(|(snapshot_pool_id, _)| snapshot_pool_id == pool_id)
+                .expect("Pool doesn't exist");
+            value_position(amm_contract_id, *pool_id, pool, *liquidity)
+        })
+        .collect()
+}
+
+pub fn value_position(
+    amm_contract_id: ContractId,
+    pool_id: PoolId,
+    pool: &PoolMetadata,
+    liquidity: u64,
+) -> PositionValue {
+    let (amount_0, amount_1) = get_burn_amounts(
+        liquidity,
+        pool.reserve_0,
+        pool.reserve_1,
+        pool.liquidity.amount,
+    );
+    PositionValue {
+        pool_id,
+        lp_asset: get_lp_asset_id(amm_contract_id, &pool_id),
+        liquidity,
+        amount_0,
+        amount_1,
+    }
+}
@@ -12,6 +12,7 @@ echo "Building Mira v1 core"
 
 cd mira-v1-core
 git checkout mainnet-deployment
+CORE_VERSION="mainnet-deployment@$(git rev-parse HEAD)"
 forc build --release
 
 cd ../..
@@ -25,3 +26,13 @@ mv -f tmp_abis/mira-v1-core/contracts/mira_amm_contract/out/release/* fixtures/m
 mv -f tmp_abis/mira-v1-core/contracts/mocks/mock_token/out/release/* fixtures/mock-token
 
 rm -rf tmp_abis
+
+echo "Writing fixtures manifest"
+
+cd fixtures
+ARTIFACTS=$(sha256sum */* | awk '{ printf "%s    \"%s\": \"%s\"", (NR > 1 ? ",\n" : ""), $2, $1 }')
+ABIS=$(for BINARY in */*.bin; do
+  echo "$(sha256sum "$BINARY" | cut -d ' ' -f 1) $(sha256sum "${BINARY%.bin}-abi.json" | cut -d ' ' -f 1)"
+done | awk '{ printf "%s    \"%s\": \"%s\"", (NR > 1 ? ",\n" : ""), $1, $2 }')
+printf '{\n  "core_version": "%s",\n  "artifacts": {\n%s\n  },\n  "abis": {\n%s\n  }\n}\n' "$CORE_VERSION" "$ARTIFACTS" "$ABIS" > manifest.json
+cd ..
@@ -0,0 +1,391 @@
+//! Measures the gas used by the `swap_exact_input_script` and `swap_exact_output_script` over
+//! routes of 1 to 5 volatile or stable pools, run both as regular scripts and as loaders, and
+//! writes the results to `reports/gas.json` and `reports/gas.md`.
+//!
+//! Usage: `cargo run -p gas-benchmark -- [--out <dir>] [--baseline <gas.json>]`. Passing the
+//! JSON report of another commit as baseline adds the differences to the Markdown report.
+
+use std::{collections::HashMap, fmt::Write, fs, path::PathBuf, process::Command};
+
+use fuels::prelude::{VariableOutputPolicy, WalletUnlocked};
+use serde::{Deserialize, Serialize};
+use test_harness::{
+    environment::{PoolConfig, TestEnvironment},
+    interface::{
+        scripts::get_transaction_inputs_outputs, SwapExactInputScript,
+        SwapExactInputScriptConfigurables, SwapExactOutputScript,
+        SwapExactOutputScriptConfigurables,
+    },
+    paths::{SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH, SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH},
+};
+
+const MAX_HOPS: usize = 5;
+const POOL_LIQUIDITY: u64 = 100_000_000;
+const AMOUNT: u64 = 1_000;
+/// Input provided to the exact output swaps, enough to cover the fees of every hop
+const MAX_AMOUNT_IN: u64 = 2_000;
+/// Deadline of every swap, the benchmark producing more blocks than the environment allows for
+const DEADLINE: u32 = u32::MAX;
+const DEFAULT_REPORT_DIR: &str = "reports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Swap {
+    ExactInput,
+    ExactOutput,
+}
+
+impl Swap {
+    fn script_name(self) -> &'static str {
+        match self {
+            Swap::ExactInput => "swap_exact_input_script",
+            Swap::ExactOutput => "swap_exact_output_script",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PoolType {
+    Volatile,
+    Stable,
+}
+
+impl PoolType {
+    fn name(self) -> &'static str {
+        match self {
+            PoolType::Volatile => "volatile",
+            PoolType::Stable => "stable",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ScriptSize {
+    swap: Swap,
+    /// Bytes of the script with its configurables set
+    size: usize,
+    /// Bytes of the loader, the script itself being uploaded as a blob
+    loader_size: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Measurement {
+    swap: Swap,
+    pool_type: PoolType,
+    hops: usize,
+    loader: bool,
+    gas_used: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Report {
+    /// Commit the benchmark was run on, if run from a git checkout
+    commit: Option<String>,
+    script_sizes: Vec<ScriptSize>,
+    measurements: Vec<Measurement>,
+}
+
+impl Report {
+    fn gas_used(&self, swap: Swap, pool_type: PoolType, hops: usize, loader: bool) -> Option<u64> {
+        self.measurements
+            .iter()
+            .find(|measurement| {
+                measurement.swap == swap
+                    && measurement.pool_type == pool_type
+                    && measurement.hops == hops
+                    && measurement.loader == loader
+            })
+            .map(|measurement| measurement.gas_used)
+    }
+
+    fn script_size(&self, swap: Swap) -> Option<&ScriptSize> {
+        self.script_sizes.iter().find(|size| size.swap == swap)
+    }
+}
+
+/// Both swap scripts, as regular scripts and as loaders
+struct SwapScripts {
+    exact_input: SwapExactInputScript<WalletUnlocked>,
+    exact_input_loader: SwapExactInputScript<WalletUnlocked>,
+    exact_output: SwapExactOutputScript<WalletUnlocked>,
+    exact_output_loader: SwapExactOutputScript<WalletUnlocked>,
+}
+
+impl SwapScripts {
+    async fn load(environment: &TestEnvironment) -> Self {
+        let exact_input = SwapExactInputScript::new(
+            environment.wallet.clone(),
+            SWAP_EXACT_INPUT_SCRIPT_BINARY_PATH,
+        )
+        .with_configurables(
+            SwapExactInputScriptConfigurables::default()
+                .with_AMM_CONTRACT_ID(environment.amm.id)
+                .unwrap(),
+        );
+        let mut exact_input_loader = exact_input.clone();
+        exact_input_loader.convert_into_loader().await.unwrap();
+
+        let exact_output = SwapExactOutputScript::new(
+            environment.wallet.clone(),
+            SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH,
+        )
+        .with_configurables(
+            SwapExactOutputScriptConfigurables::default()
+                .with_AMM_CONTRACT_ID(environment.amm.id)
+                .unwrap(),
+        );
+        let mut exact_output_loader = exact_output.clone();
+        exact_output_loader.convert_into_loader().await.unwrap();
+
+        Self {
+            exact_input,
+            exact_input_loader,
+            exact_output,
+            exact_output_loader,
+        }
+    }
+
+    fn sizes(&self) -> Vec<ScriptSize> {
+        vec![
+            ScriptSize {
+                swap: Swap::ExactInput,
+                size: self.exact_input.code().len(),
+                loader_size: self.exact_input_loader.code().len(),
+            },
+            ScriptSize {
+                swap: Swap::ExactOutput,
+                size: self.exact_output.code().len(),
+                loader_size: self.exact_output_loader.code().len(),
+            },
+        ]
+    }
+}
+
+/// Environment with a route of `MAX_HOPS` pools of `pool_type`, `pools[i]` trading
+/// `tokens[i]` for `tokens[i + 1]`
+async fn route_environment(pool_type: PoolType) -> TestEnvironment {
+    let mut builder = TestEnvironment::builder().tokens(&[9; MAX_HOPS + 1]);
+    for index in 0..MAX_HOPS {
+        let pool = match pool_type {
+            PoolType::Volatile => PoolConfig::volatile(index, index + 1),
+            PoolType::Stable => PoolConfig::stable(index, index + 1),
+        };
+        builder = builder.pool(pool.with_liquidity(POOL_LIQUIDITY, POOL_LIQUIDITY));
+    }
+    builder.build().await
+}
+
+async fn swap_exact_input(
+    environment: &TestEnvironment,
+    script: &SwapExactInputScript<WalletUnlocked>,
+    hops: usize,
+) -> u64 {
+    let asset_in = environment.tokens[0].id;
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&environment.wallet, &vec![(asset_in, AMOUNT)]).await;
+
+    script
+        .main(
+            AMOUNT,
+            asset_in,
+            0,
+            environment.pools[..hops].to_vec(),
+            environment.wallet.address().into(),
+            DEADLINE,
+        )
+        .with_contracts(&[&environment.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .gas_used
+}
+
+async fn swap_exact_output(
+    environment: &TestEnvironment,
+    script: &SwapExactOutputScript<WalletUnlocked>,
+    hops: usize,
+) -> u64 {
+    let (asset_in, asset_out) = (environment.tokens[0].id, environment.tokens[hops].id);
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&environment.wallet, &vec![(asset_in, MAX_AMOUNT_IN)]).await;
+
+    script
+        .main(
+            AMOUNT,
+            asset_out,
+            MAX_AMOUNT_IN,
+            environment.pools[..hops].to_vec(),
+            environment.wallet.address().into(),
+            DEADLINE,
+        )
+        .with_contracts(&[&environment.amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap()
+        .gas_used
+}
+
+async fn run() -> Report {
+    let mut script_sizes = vec![];
+    let mut measurements = vec![];
+
+    for pool_type in [PoolType::Volatile, PoolType::Stable] {
+        let environment = route_environment(pool_type).await;
+        let scripts = SwapScripts::load(&environment).await;
+        if script_sizes.is_empty() {
+            script_sizes = scripts.sizes();
+        }
+
+        for hops in 1..=MAX_HOPS {
+            for loader in [false, true] {
+                let (exact_input, exact_output) = if loader {
+                    (&scripts.exact_input_loader, &scripts.exact_output_loader)
+                } else {
+                    (&scripts.exact_input, &scripts.exact_output)
+                };
+                measurements.push(Measurement {
+                    swap: Swap::ExactInput,
+                    pool_type,
+                    hops,
+                    loader,
+                    gas_used: swap_exact_input(&environment, exact_input, hops).await,
+                });
+                measurements.push(Measurement {
+                    swap: Swap::ExactOutput,
+                    pool_type,
+                    hops,
+                    loader,
+                    gas_used: swap_exact_output(&environment, exact_output, hops).await,
+                });
+            }
+        }
+    }
+
+    Report {
+        commit: current_commit(),
+        script_sizes,
+        measurements,
+    }
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// `value`, followed by its difference with `baseline` when there is one
+fn cell(value: u64, baseline: Option<u64>) -> String {
+    match baseline {
+        Some(baseline) if baseline != value => {
+            let delta = i128::from(value) - i128::from(baseline);
+            let percent = delta as f64 * 100.0 / baseline as f64;
+            format!("{value} ({delta:+}, {percent:+.2}%)")
+        }
+        Some(_) => format!("{value} (=)"),
+        None => value.to_string(),
+    }
+}
+
+fn markdown(report: &Report, baseline: Option<&Report>) -> String {
+    let mut markdown = String::from("# Swap Scripts Gas Report\n\n");
+    if let Some(commit) = &report.commit {
+        writeln!(markdown, "Commit: `{commit}`").unwrap();
+    }
+    if let Some(commit) = baseline.and_then(|baseline| baseline.commit.as_ref()) {
+        writeln!(markdown, "Baseline: `{commit}`").unwrap();
+    }
+
+    markdown.push_str("\n## Script Size (bytes)\n\n");
+    markdown.push_str("| Script | Size | Loader Size |\n| --- | --- | --- |\n");
+    for size in &report.script_sizes {
+        let baseline = baseline.and_then(|baseline| baseline.script_size(size.swap));
+        writeln!(
+            markdown,
+            "| `{}` | {} | {} |",
+            size.swap.script_name(),
+            cell(size.size as u64, baseline.map(|size| size.size as u64)),
+            cell(
+                size.loader_size as u64,
+                baseline.map(|size| size.loader_size as u64)
+            ),
+        )
+        .unwrap();
+    }
+
+    for pool_type in [PoolType::Volatile, PoolType::Stable] {
+        writeln!(markdown, "\n## Gas Used, {} Pools\n", pool_type.name()).unwrap();
+        markdown.push_str(
+            "| Hops | Exact Input | Exact Input (loader) | Exact Output | Exact Output (loader) |\n\
+             | --- | --- | --- | --- | --- |\n",
+        );
+        for hops in 1..=MAX_HOPS {
+            write!(markdown, "| {hops} |").unwrap();
+            for (swap, loader) in [
+                (Swap::ExactInput, false),
+                (Swap::ExactInput, true),
+                (Swap::ExactOutput, false),
+                (Swap::ExactOutput, true),
+            ] {
+                let value = report.gas_used(swap, pool_type, hops, loader).unwrap();
+                let baseline =
+                    baseline.and_then(|baseline| baseline.gas_used(swap, pool_type, hops, loader));
+                write!(markdown, " {} |", cell(value, baseline)).unwrap();
+            }
+            markdown.push('\n');
+        }
+    }
+
+    markdown
+}
+
+fn parse_args() -> HashMap<String, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    args.chunks(2)
+        .map(|pair| match pair {
+            [flag, value] if flag == "--out" || flag == "--baseline" => {
+                (flag.clone(), value.clone())
+            }
+            _ => panic!("Usage: gas-benchmark [--out <dir>] [--baseline <gas.json>]"),
+        })
+        .collect()
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args = parse_args();
+    // resolved before moving to the manifest directory, from which the binary paths of the
+    // test harness are relative
+    let current_dir = std::env::current_dir().unwrap();
+    let out_dir = args
+        .get("--out")
+        .map(|out| current_dir.join(out))
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_REPORT_DIR));
+    let baseline: Option<Report> = args.get("--baseline").map(|path| {
+        serde_json::from_str(&fs::read_to_string(current_dir.join(path)).unwrap()).unwrap()
+    });
+    std::env::set_current_dir(env!("CARGO_MANIFEST_DIR")).unwrap();
+
+    let report = run().await;
+
+    fs::create_dir_all(&out_dir).unwrap();
+    fs::write(
+        out_dir.join("gas.json"),
+        serde_json::to_string_pretty(&report).unwrap(),
+    )
+    .unwrap();
+    fs::write(out_dir.join("gas.md"), markdown(&report, baseline.as_ref())).unwrap();
+    println!("Gas report written to {}", out_dir.display());
+}
@@ -0,0 +1,93 @@
+//! Computes per pool volume, fees and TVL series from a saved receipts file, offline, and
+//! writes them to `analytics.csv` and `analytics.json`.
+//!
+//! Usage: `cargo run -p pool-analytics -- --receipts <receipts.json> --amm <contract id>
+//! --fees <lp_volatile,lp_stable,protocol_volatile,protocol_stable> [--bucket <seconds>]
+//! [--out <dir>] [--assets <overrides.json>]`. The receipts file is a JSON array of
+//! `TransactionReceipts`, and the fees are the `fees()` of the AMM in basis points. Buckets last
+//! a day unless given. The symbols of the CSV come from the optional file of `AssetOverride`s.
+
+use std::{collections::HashMap, fs, path::PathBuf, str::FromStr};
+
+use fuels::types::ContractId;
+use test_harness::{
+    analytics::{decode_events, read_receipts, series_to_csv, series_to_json, PoolAnalytics},
+    assets::{read_overrides, AssetRegistry},
+};
+
+const USAGE: &str = "Usage: pool-analytics --receipts <receipts.json> --amm <contract id> \
+    --fees <lp_volatile,lp_stable,protocol_volatile,protocol_stable> [--bucket <seconds>] \
+    [--out <dir>] [--assets <overrides.json>]";
+const FLAGS: [&str; 6] = [
+    "--receipts",
+    "--amm",
+    "--fees",
+    "--bucket",
+    "--out",
+    "--assets",
+];
+const DAY: u64 = 86_400;
+
+fn parse_args() -> HashMap<String, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    args.chunks(2)
+        .map(|pair| match pair {
+            [flag, value] if FLAGS.contains(&flag.as_str()) => (flag.clone(), value.clone()),
+            _ => panic!("{USAGE}"),
+        })
+        .collect()
+}
+
+fn parse_fees(fees: &str) -> (u64, u64, u64, u64) {
+    let fees: Vec<u64> = fees
+        .split(',')
+        .map(|fee| fee.trim().parse().expect(USAGE))
+        .collect();
+    match fees[..] {
+        [lp_volatile, lp_stable, protocol_volatile, protocol_stable] => {
+            (lp_volatile, lp_stable, protocol_volatile, protocol_stable)
+        }
+        _ => panic!("{USAGE}"),
+    }
+}
+
+fn main() {
+    let args = parse_args();
+    let required = |flag: &str| args.get(flag).expect(USAGE);
+    let amm_contract_id = ContractId::from_str(required("--amm")).expect(USAGE);
+    let fees = parse_fees(required("--fees"));
+    let bucket_seconds = args
+        .get("--bucket")
+        .map(|bucket| bucket.parse().expect(USAGE))
+        .unwrap_or(DAY);
+    let out_dir = args
+        .get("--out")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let transactions = read_receipts(fs::File::open(required("--receipts")).unwrap()).unwrap();
+    let events = decode_events(amm_contract_id, &transactions);
+    let mut analytics = PoolAnalytics::new(fees, bucket_seconds);
+    analytics.record_all(&events);
+    let series = analytics.series();
+    let overrides = args
+        .get("--assets")
+        .map(|path| read_overrides(fs::File::open(path).unwrap()).unwrap())
+        .unwrap_or_default();
+    let assets = AssetRegistry::new(amm_contract_id).with_overrides(overrides);
+
+    fs::create_dir_all(&out_dir).unwrap();
+    fs::write(
+        out_dir.join("analytics.csv"),
+        series_to_csv(&series, &assets),
+    )
+    .unwrap();
+    fs::write(out_dir.join("analytics.json"), series_to_json(&series)).unwrap();
+    println!(
+        "{} events of {} transactions bucketed into {} rows, written to {}",
+        events.len(),
+        transactions.len(),
+        series.len(),
+        out_dir.display()
+    );
+}